@@ -0,0 +1,2 @@
+-- Add migration script here
+alter table todos add column due_at timestamptz(3);
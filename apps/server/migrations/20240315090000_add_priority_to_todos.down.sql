@@ -0,0 +1,2 @@
+-- Add down migration script here
+alter table todos drop column priority;
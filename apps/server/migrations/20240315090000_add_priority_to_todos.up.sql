@@ -0,0 +1,4 @@
+-- Add migration script here
+alter table todos add column priority smallint not null default 0;
+
+create index todos_priority_created_at_idx on todos (priority desc, created_at desc);
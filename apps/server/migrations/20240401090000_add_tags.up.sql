@@ -0,0 +1,14 @@
+-- Add migration script here
+create table tags (
+    id text primary key,
+    name varchar(64) not null unique,
+    created_at timestamptz(3) not null
+);
+
+create table todo_tags (
+    todo_id text not null references todos (id) on delete cascade,
+    tag_id text not null references tags (id) on delete cascade,
+    primary key (todo_id, tag_id)
+);
+
+create index todo_tags_tag_id_idx on todo_tags (tag_id);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+alter table todos drop column list_id;
+
+drop table todo_lists;
@@ -0,0 +1,11 @@
+-- Add migration script here
+create table todo_lists (
+    id text primary key,
+    name varchar(255) not null,
+    created_at timestamptz(3) not null,
+    updated_at timestamptz(3) not null
+);
+
+alter table todos add column list_id text references todo_lists (id) on delete cascade;
+
+create index todos_list_id_idx on todos (list_id);
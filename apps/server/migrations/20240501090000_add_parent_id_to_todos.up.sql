@@ -0,0 +1,4 @@
+-- Add migration script here
+alter table todos add column parent_id text references todos (id) on delete cascade;
+
+create index todos_parent_id_idx on todos (parent_id);
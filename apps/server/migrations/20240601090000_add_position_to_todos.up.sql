@@ -0,0 +1,7 @@
+-- Add migration script here
+alter table todos add column position double precision not null default 0;
+
+-- newest todos first, like the default order
+update todos set position = -extract(epoch from created_at);
+
+create index todos_position_idx on todos (position);
@@ -0,0 +1,4 @@
+-- Add migration script here
+alter table todos add column deleted_at timestamptz(3);
+
+create index todos_deleted_at_idx on todos (deleted_at);
@@ -0,0 +1,5 @@
+-- Add migration script here
+alter table todos
+add column search_vector tsvector generated always as (to_tsvector('english', description)) stored;
+
+create index todos_search_vector_idx on todos using gin (search_vector);
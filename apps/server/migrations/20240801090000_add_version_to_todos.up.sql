@@ -0,0 +1,2 @@
+-- Add migration script here
+alter table todos add column version integer not null default 1;
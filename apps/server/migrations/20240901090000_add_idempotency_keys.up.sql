@@ -0,0 +1,12 @@
+-- Add migration script here
+create table idempotency_keys (
+    key varchar(255) primary key,
+    fingerprint varchar(64) not null,
+    -- the response is null while the first request is in progress
+    status integer,
+    headers jsonb,
+    body bytea,
+    expires_at timestamptz(3) not null
+);
+
+create index idempotency_keys_expires_at_idx on idempotency_keys (expires_at);
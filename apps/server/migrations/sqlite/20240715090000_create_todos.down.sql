@@ -0,0 +1,14 @@
+-- Add down migration script here
+drop trigger todos_fts_update;
+drop trigger todos_fts_delete;
+drop trigger todos_fts_insert;
+
+drop table todos_fts;
+
+drop table todo_tags;
+
+drop table tags;
+
+drop table todos;
+
+drop table todo_lists;
@@ -0,0 +1,66 @@
+-- Add migration script here
+create table todo_lists (
+    id text primary key,
+    name text not null,
+    created_at text not null,
+    updated_at text not null
+);
+
+-- `seq` gives the full-text index a stable rowid
+create table todos (
+    seq integer primary key,
+    id text not null unique,
+    description text not null,
+    done boolean not null default false,
+    created_at text not null,
+    updated_at text not null,
+    done_at text,
+    due_at text,
+    priority integer not null default 0,
+    list_id text references todo_lists (id) on delete cascade,
+    parent_id text references todos (id) on delete cascade,
+    recurrence text,
+    position real not null default 0,
+    deleted_at text
+);
+
+create index todos_created_at_idx on todos (created_at desc);
+create index todos_priority_created_at_idx on todos (priority desc, created_at desc);
+create index todos_list_id_idx on todos (list_id);
+create index todos_parent_id_idx on todos (parent_id);
+create index todos_position_idx on todos (position);
+create index todos_deleted_at_idx on todos (deleted_at);
+
+create table tags (
+    id text primary key,
+    name text not null unique,
+    created_at text not null
+);
+
+create table todo_tags (
+    todo_id text not null references todos (id) on delete cascade,
+    tag_id text not null references tags (id) on delete cascade,
+    primary key (todo_id, tag_id)
+);
+
+create index todo_tags_tag_id_idx on todo_tags (tag_id);
+
+create virtual table todos_fts using fts5 (
+    description,
+    content = 'todos',
+    content_rowid = 'seq',
+    tokenize = 'porter unicode61'
+);
+
+create trigger todos_fts_insert after insert on todos begin
+    insert into todos_fts (rowid, description) values (new.seq, new.description);
+end;
+
+create trigger todos_fts_delete after delete on todos begin
+    insert into todos_fts (todos_fts, rowid, description) values ('delete', old.seq, old.description);
+end;
+
+create trigger todos_fts_update after update of description on todos begin
+    insert into todos_fts (todos_fts, rowid, description) values ('delete', old.seq, old.description);
+    insert into todos_fts (rowid, description) values (new.seq, new.description);
+end;
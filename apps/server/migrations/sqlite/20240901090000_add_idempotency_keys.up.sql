@@ -0,0 +1,12 @@
+-- Add migration script here
+create table idempotency_keys (
+    key text primary key,
+    fingerprint text not null,
+    -- the response is null while the first request is in progress
+    status integer,
+    headers text,
+    body blob,
+    expires_at text not null
+);
+
+create index idempotency_keys_expires_at_idx on idempotency_keys (expires_at);
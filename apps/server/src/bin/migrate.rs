@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use server::infra::{
+	migrations::{PG_MIGRATOR, SQLITE_MIGRATOR},
+	pg::create_pg_pool,
+	sqlite::create_sqlite_pool,
+};
+use sqlx::migrate::{Migrate, MigrateError, Migration, Migrator};
+
+const USAGE: &str = "Usage: migrate <status | up | down [version]>
+
+  status          lists the migrations and whether they are applied
+  up              applies the pending migrations
+  down [version]  reverts the migrations applied after `version`, the last one by default";
+
+enum Command {
+	Status,
+	Up,
+	Down(Option<i64>),
+}
+
+#[tokio::main]
+async fn main() {
+	dotenv::dotenv().ok();
+
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	let command = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+		["status"] => Command::Status,
+		["up"] => Command::Up,
+		["down" | "revert"] => Command::Down(None),
+		["down" | "revert", version] => match version.parse() {
+			Ok(version) => Command::Down(Some(version)),
+			Err(_) => exit_with_usage(),
+		},
+		_ => exit_with_usage(),
+	};
+
+	let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL is not set");
+
+	let result = if database_url.starts_with("sqlite:") {
+		let pool = create_sqlite_pool(&database_url).await;
+		let mut conn = pool.acquire().await.expect("Failed to open the SQLite database");
+		run(&SQLITE_MIGRATOR, &mut *conn, command).await
+	} else {
+		let pool = create_pg_pool().await;
+		let mut conn = pool.acquire().await.expect("Failed to connect to Postgres");
+		run(&PG_MIGRATOR, &mut *conn, command).await
+	};
+
+	if let Err(err) = result {
+		eprintln!("Failed to migrate the database: {}", err);
+		std::process::exit(1);
+	}
+}
+
+fn exit_with_usage() -> ! {
+	eprintln!("{}", USAGE);
+	std::process::exit(2);
+}
+
+async fn run<C: Migrate>(
+	migrator: &Migrator,
+	conn: &mut C,
+	command: Command,
+) -> Result<(), MigrateError> {
+	conn.lock().await?;
+	conn.ensure_migrations_table().await?;
+
+	let result = match command {
+		Command::Status => status(migrator, conn).await,
+		Command::Up => up(migrator, conn).await,
+		Command::Down(target) => down(migrator, conn, target).await,
+	};
+
+	conn.unlock().await?;
+
+	result
+}
+
+async fn status<C: Migrate>(migrator: &Migrator, conn: &mut C) -> Result<(), MigrateError> {
+	let applied = applied_checksums(conn).await?;
+	let dirty_version = conn.dirty_version().await?;
+
+	for migration in up_migrations(migrator) {
+		let state = match applied.get(&migration.version) {
+			_ if dirty_version == Some(migration.version) => "failed",
+			Some(checksum) if *checksum != *migration.checksum => "applied, changed since",
+			Some(_) => "applied",
+			None => "pending",
+		};
+
+		println!(
+			"{} {:<24} {}",
+			migration.version, state, migration.description
+		);
+	}
+
+	Ok(())
+}
+
+async fn up<C: Migrate>(migrator: &Migrator, conn: &mut C) -> Result<(), MigrateError> {
+	if let Some(version) = conn.dirty_version().await? {
+		return Err(MigrateError::Dirty(version));
+	}
+
+	let applied = applied_checksums(conn).await?;
+	let mut count = 0;
+
+	for migration in up_migrations(migrator) {
+		match applied.get(&migration.version) {
+			Some(checksum) if *checksum != *migration.checksum => {
+				return Err(MigrateError::VersionMismatch(migration.version));
+			},
+			Some(_) => {},
+			None => {
+				conn.apply(migration).await?;
+				println!("Applied {} {}", migration.version, migration.description);
+				count += 1;
+			},
+		}
+	}
+
+	println!("Applied {} migrations", count);
+
+	Ok(())
+}
+
+/// Reverts the migrations applied after `target`, newest first, or only the last one.
+async fn down<C: Migrate>(
+	migrator: &Migrator,
+	conn: &mut C,
+	target: Option<i64>,
+) -> Result<(), MigrateError> {
+	if let Some(version) = conn.dirty_version().await? {
+		return Err(MigrateError::Dirty(version));
+	}
+
+	let mut versions: Vec<i64> = applied_checksums(conn).await?.into_keys().collect();
+	versions.sort_unstable_by(|a, b| b.cmp(a));
+
+	let versions = match target {
+		Some(target) => versions.into_iter().filter(|version| *version > target).collect(),
+		None => versions.into_iter().take(1).collect::<Vec<_>>(),
+	};
+
+	for version in &versions {
+		let migration = migrator
+			.iter()
+			.find(|migration| {
+				migration.version == *version && migration.migration_type.is_down_migration()
+			})
+			.ok_or(MigrateError::VersionMissing(*version))?;
+
+		conn.revert(migration).await?;
+		println!("Reverted {} {}", migration.version, migration.description);
+	}
+
+	println!("Reverted {} migrations", versions.len());
+
+	Ok(())
+}
+
+fn up_migrations(migrator: &Migrator) -> impl Iterator<Item = &Migration> {
+	migrator
+		.iter()
+		.filter(|migration| !migration.migration_type.is_down_migration())
+}
+
+async fn applied_checksums<C: Migrate>(
+	conn: &mut C,
+) -> Result<HashMap<i64, Vec<u8>>, MigrateError> {
+	Ok(conn
+		.list_applied_migrations()
+		.await?
+		.into_iter()
+		.map(|migration| (migration.version, migration.checksum.into_owned()))
+		.collect())
+}
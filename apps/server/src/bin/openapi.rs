@@ -7,4 +7,10 @@ async fn main() {
 	let doc_json = doc.to_pretty_json().unwrap();
 
 	std::fs::write("openapi.json", doc_json).unwrap();
+
+	let doc_v2: utoipa::openapi::OpenApi = server::infra::api_doc::ApiDocV2::openapi();
+
+	let doc_v2_json = doc_v2.to_pretty_json().unwrap();
+
+	std::fs::write("openapi.v2.json", doc_v2_json).unwrap();
 }
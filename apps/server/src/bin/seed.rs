@@ -1,4 +1,7 @@
-use server::{domain::entity::todo::Todo, infra::pg::create_pg_pool};
+use server::{
+	domain::entity::todo::Todo,
+	infra::{migrations::PG_MIGRATOR, pg::create_pg_pool},
+};
 
 #[tokio::main]
 async fn main() {
@@ -6,10 +9,7 @@ async fn main() {
 
 	let pool = create_pg_pool().await;
 
-	sqlx::migrate!("./migrations")
-		.run(pool)
-		.await
-		.expect("Failed to migrate the database");
+	PG_MIGRATOR.run(pool).await.expect("Failed to migrate the database");
 
 	println!("Migrated the database");
 
@@ -29,19 +29,20 @@ async fn main() {
 }
 
 async fn truncate_todos(pool: &sqlx::Pool<sqlx::Postgres>) -> Result<(), sqlx::Error> {
-	sqlx::query("TRUNCATE TABLE todos").execute(pool).await.map(|_| ())
+	sqlx::query("TRUNCATE TABLE todos CASCADE").execute(pool).await.map(|_| ())
 }
 
 async fn create_todo(pool: &sqlx::Pool<sqlx::Postgres>) -> Result<Todo, sqlx::Error> {
 	let todo = Todo::new(random_word::gen(random_word::Lang::En).to_string());
 
-	sqlx::query_as::<_, Todo>("INSERT INTO todos (id, description, done, created_at, updated_at, done_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *")
+	sqlx::query_as::<_, Todo>("INSERT INTO todos (id, description, done, created_at, updated_at, done_at, position) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *")
 		.bind(todo.id)
 		.bind(todo.description)
 		.bind(todo.done)
 		.bind(todo.created_at)
 		.bind(todo.updated_at)
 		.bind(todo.done_at)
+		.bind(todo.position)
 		.fetch_one(pool)
 		.await
 		.map_err(|err| {
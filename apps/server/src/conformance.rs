@@ -0,0 +1,819 @@
+//! Behaviour every `TodoRepository` implementation has to share, so the use cases do not
+//! depend on the backend they run on.
+
+use std::future::Future;
+
+use chrono::TimeZone;
+
+use crate::domain::{
+	entity::{
+		todo::{DueFilter, Todo, TodoOrder, TodoPriority},
+		todo_list::TodoList,
+		todo_query::{DateRange, SortDirection, TodoQuery, TodoSort, TodoStatus},
+	},
+	repository::{
+		todo_list_repository::DynTodoListRepository,
+		todo_repository::{
+			DeleteError, DynTodoRepository, FindTodoError, RestoreError, UpdateError,
+		},
+	},
+};
+
+/// Repositories of a backend sharing the same storage, the lists being referenced by the
+/// todos.
+pub struct Repositories {
+	pub todos: DynTodoRepository,
+	pub lists: DynTodoListRepository,
+}
+
+/// Runs every check of the suite on an empty backend made by `factory`, panicking on the
+/// first behaviour that differs.
+pub async fn check_todo_repository<F, Fut>(factory: F)
+where
+	F: Fn() -> Fut,
+	Fut: Future<Output = Repositories>,
+{
+	finds_created_todo(factory().await.todos).await;
+	sorts_with_ties_broken_by_id(factory().await.todos).await;
+	filters_todos(factory().await.todos).await;
+	filters_by_due_date(factory().await.todos).await;
+	filters_by_list(factory().await).await;
+	ranks_search_results(factory().await.todos).await;
+	paginates_with_cursor(factory().await.todos).await;
+	paginates_search_with_cursor(factory().await.todos).await;
+	updates_only_current_version(factory().await.todos).await;
+	trashes_and_restores_subtasks(factory().await.todos).await;
+	trashes_done_todos(factory().await.todos).await;
+	purges_trash(factory().await.todos).await;
+	rolls_back_uncommitted_unit_of_work(factory().await.todos).await;
+	finds_siblings_in_manual_order(factory().await.todos).await;
+}
+
+/// Checks the todos written through a repository made by `open` are read back by the next
+/// one, for backends storing them across restarts.
+pub async fn check_todo_repository_persists<F, Fut>(open: F)
+where
+	F: Fn() -> Fut,
+	Fut: Future<Output = DynTodoRepository>,
+{
+	let repo = open().await;
+	create(
+		&repo,
+		vec![
+			Todo {
+				description: "Buy milk".to_string(),
+				tags: vec!["errands".to_string()],
+				..todo("a", 0)
+			},
+			Todo {
+				parent_id: Some("a".to_string()),
+				..todo("b", 1)
+			},
+			todo("c", 2),
+			todo("d", 3),
+		],
+	)
+	.await;
+
+	let b = repo.find_by_id("b".to_string()).await.expect("Failed to find todo");
+	repo.update(Todo {
+		done: true,
+		done_at: Some(todo("", 4).created_at),
+		..b
+	})
+	.await
+	.expect("Failed to update todo");
+	repo.delete("c".to_string()).await.expect("Failed to delete todo");
+	repo.delete("d".to_string()).await.expect("Failed to delete todo");
+	repo.purge_deleted(chrono::Utc::now() + chrono::Duration::hours(1))
+		.await
+		.expect("Failed to purge todos");
+	repo.create_todo(todo("e", 5)).await.expect("Failed to create todo");
+	repo.delete("e".to_string()).await.expect("Failed to delete todo");
+
+	let written = repo.find_many_todos(&TodoQuery::default()).await.expect("Failed to find todos");
+	drop(repo);
+
+	let repo = open().await;
+	let read = repo.find_many_todos(&TodoQuery::default()).await.expect("Failed to find todos");
+
+	assert_eq!(ids(&read), ["b", "a"], "purged todos are not read back");
+	for (read, written) in read.iter().zip(&written) {
+		assert_eq!(read.description, written.description);
+		assert_eq!(read.done, written.done);
+		assert_eq!(read.done_at, written.done_at);
+		assert_eq!(read.tags, written.tags);
+		assert_eq!(read.parent_id, written.parent_id);
+		assert_eq!(read.version, written.version, "todos keep their version");
+	}
+
+	let deleted = repo.find_deleted().await.expect("Failed to find deleted todos");
+	assert_eq!(ids(&deleted), ["e"], "trashed todos stay in the trash");
+}
+
+/// Todo created `minutes` after a fixed date, with no sub-millisecond part so every
+/// backend stores it as is.
+fn todo(id: &str, minutes: i64) -> Todo {
+	let created_at = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+		+ chrono::Duration::minutes(minutes);
+
+	Todo {
+		id: id.to_string(),
+		created_at,
+		updated_at: created_at,
+		position: Todo::default_position(created_at),
+		..Todo::new(format!("Todo {}", id))
+	}
+}
+
+fn ids(todos: &[Todo]) -> Vec<&str> {
+	todos.iter().map(|todo| todo.id.as_str()).collect()
+}
+
+fn sorted_ids(todos: &[Todo]) -> Vec<&str> {
+	let mut ids = ids(todos);
+	ids.sort_unstable();
+	ids
+}
+
+async fn create(repo: &DynTodoRepository, todos: Vec<Todo>) {
+	for todo in todos {
+		repo.create_todo(todo).await.expect("Failed to create todo");
+	}
+}
+
+async fn find_ids(repo: &DynTodoRepository, query: TodoQuery) -> Vec<String> {
+	let todos = repo.find_many_todos(&query).await.expect("Failed to find todos");
+
+	ids(&todos).into_iter().map(String::from).collect()
+}
+
+async fn finds_created_todo(repo: DynTodoRepository) {
+	let created = repo
+		.create_todo(Todo {
+			description: "Buy milk".to_string(),
+			priority: TodoPriority::High,
+			tags: vec!["errands".to_string()],
+			..todo("a", 0)
+		})
+		.await
+		.expect("Failed to create todo");
+
+	assert_eq!(created.version, 1, "a new todo is at version 1");
+
+	let found = repo.find_by_id("a".to_string()).await.expect("Failed to find todo");
+	assert_eq!(found.description, "Buy milk");
+	assert_eq!(found.priority, TodoPriority::High);
+	assert_eq!(found.tags, vec!["errands"]);
+	assert_eq!(found.created_at, created.created_at);
+	assert_eq!(found.version, 1);
+
+	assert!(
+		matches!(
+			repo.find_by_id("missing".to_string()).await,
+			Err(FindTodoError::NotFound)
+		),
+		"finding a missing todo is NotFound"
+	);
+}
+
+async fn sorts_with_ties_broken_by_id(repo: DynTodoRepository) {
+	create(
+		&repo,
+		vec![
+			Todo {
+				priority: TodoPriority::Low,
+				position: 3.0,
+				..todo("a", 1)
+			},
+			Todo {
+				priority: TodoPriority::High,
+				position: 1.0,
+				..todo("b", 2)
+			},
+			Todo {
+				priority: TodoPriority::Low,
+				position: 2.0,
+				..todo("c", 2)
+			},
+			Todo {
+				priority: TodoPriority::High,
+				position: 1.0,
+				..todo("d", 3)
+			},
+		],
+	)
+	.await;
+
+	let sorted = |order: TodoOrder, direction: SortDirection| TodoQuery {
+		sort: TodoSort { order, direction },
+		..Default::default()
+	};
+
+	assert_eq!(
+		find_ids(&repo, TodoQuery::default()).await,
+		["d", "c", "b", "a"],
+		"newest first by default"
+	);
+	assert_eq!(
+		find_ids(&repo, sorted(TodoOrder::CreatedAt, SortDirection::Asc)).await,
+		["a", "b", "c", "d"]
+	);
+	assert_eq!(
+		find_ids(&repo, sorted(TodoOrder::Priority, SortDirection::Desc)).await,
+		["d", "b", "c", "a"],
+		"highest priority, then newest first"
+	);
+	assert_eq!(
+		find_ids(
+			&repo,
+			TodoQuery {
+				sort: TodoSort::new(TodoOrder::Manual),
+				..Default::default()
+			}
+		)
+		.await,
+		["b", "d", "c", "a"],
+		"lowest position first"
+	);
+}
+
+async fn filters_todos(repo: DynTodoRepository) {
+	let done_at = chrono::Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+
+	create(
+		&repo,
+		vec![
+			Todo {
+				description: "Buy milk".to_string(),
+				tags: vec!["errands".to_string()],
+				..todo("a", 1)
+			},
+			Todo {
+				done: true,
+				done_at: Some(done_at),
+				priority: TodoPriority::Urgent,
+				..todo("b", 2)
+			},
+			Todo {
+				tags: vec!["errands".to_string()],
+				..todo("c", 3)
+			},
+			todo("trashed", 4),
+		],
+	)
+	.await;
+	repo.delete("trashed".to_string()).await.expect("Failed to delete todo");
+
+	let filtered = |query: TodoQuery| {
+		let repo = repo.clone();
+		async move {
+			let mut ids = find_ids(&repo, query.clone()).await;
+			ids.sort_unstable();
+
+			let count = repo.count(&query).await.expect("Failed to count todos");
+			assert_eq!(count, ids.len() as i64, "the count matches the todos found");
+
+			ids
+		}
+	};
+
+	assert_eq!(
+		filtered(TodoQuery::default()).await,
+		["a", "b", "c"],
+		"trashed todos are left out"
+	);
+	assert_eq!(
+		filtered(TodoQuery {
+			status: Some(TodoStatus::Done),
+			..Default::default()
+		})
+		.await,
+		["b"]
+	);
+	assert_eq!(
+		filtered(TodoQuery {
+			status: Some(TodoStatus::Pending),
+			..Default::default()
+		})
+		.await,
+		["a", "c"]
+	);
+	assert_eq!(
+		filtered(TodoQuery {
+			priority: Some(TodoPriority::Urgent),
+			..Default::default()
+		})
+		.await,
+		["b"]
+	);
+	assert_eq!(
+		filtered(TodoQuery {
+			tag: Some("errands".to_string()),
+			..Default::default()
+		})
+		.await,
+		["a", "c"]
+	);
+	assert_eq!(
+		filtered(TodoQuery {
+			contains: Some("MILK".to_string()),
+			..Default::default()
+		})
+		.await,
+		["a"],
+		"contains ignores case"
+	);
+	assert_eq!(
+		filtered(TodoQuery {
+			created: DateRange {
+				from: Some(todo("", 2).created_at),
+				to: Some(todo("", 3).created_at),
+			},
+			..Default::default()
+		})
+		.await,
+		["b"],
+		"date ranges include their start only"
+	);
+	assert_eq!(
+		filtered(TodoQuery {
+			created: DateRange {
+				from: None,
+				to: Some(todo("", 3).created_at),
+			},
+			tag: Some("errands".to_string()),
+			..Default::default()
+		})
+		.await,
+		["a"]
+	);
+	assert_eq!(
+		filtered(TodoQuery {
+			done: DateRange {
+				from: Some(done_at),
+				to: None,
+			},
+			..Default::default()
+		})
+		.await,
+		["b"]
+	);
+	assert_eq!(
+		filtered(TodoQuery {
+			status: Some(TodoStatus::Done),
+			tag: Some("errands".to_string()),
+			..Default::default()
+		})
+		.await,
+		Vec::<String>::new(),
+		"every criterion has to match"
+	);
+}
+
+async fn filters_by_due_date(repo: DynTodoRepository) {
+	let today = chrono::Utc::now().date_naive().and_hms_opt(12, 0, 0).unwrap().and_utc();
+	let due = |id: &str, days: i64| Todo {
+		due_at: Some(today + chrono::Duration::days(days)),
+		..todo(id, 0)
+	};
+
+	create(
+		&repo,
+		vec![
+			due("overdue", -1),
+			Todo {
+				done: true,
+				done_at: Some(today),
+				..due("done", -1)
+			},
+			due("today", 0),
+			due("upcoming", 2),
+			todo("undated", 0),
+		],
+	)
+	.await;
+
+	let filtered = |due: DueFilter| {
+		let repo = repo.clone();
+		async move {
+			let query = TodoQuery {
+				due: Some(due),
+				..Default::default()
+			};
+			let ids = find_ids(&repo, query.clone()).await;
+
+			let count = repo.count(&query).await.expect("Failed to count todos");
+			assert_eq!(count, ids.len() as i64, "the count matches the todos found");
+
+			ids
+		}
+	};
+
+	assert_eq!(
+		filtered(DueFilter::Overdue).await,
+		["overdue"],
+		"done todos are not overdue"
+	);
+	assert_eq!(filtered(DueFilter::Today).await, ["today"]);
+	assert_eq!(filtered(DueFilter::Upcoming).await, ["upcoming"]);
+}
+
+async fn filters_by_list(repos: Repositories) {
+	let list = repos
+		.lists
+		.create_list(TodoList::new("Groceries".to_string()))
+		.await
+		.expect("Failed to create list");
+
+	create(
+		&repos.todos,
+		vec![
+			Todo {
+				list_id: Some(list.id.clone()),
+				..todo("a", 1)
+			},
+			todo("b", 2),
+			Todo {
+				list_id: Some(list.id.clone()),
+				tags: vec!["errands".to_string()],
+				..todo("c", 3)
+			},
+		],
+	)
+	.await;
+
+	let in_list = |tag: Option<&str>| TodoQuery {
+		list_id: Some(list.id.clone()),
+		tag: tag.map(String::from),
+		..Default::default()
+	};
+
+	assert_eq!(find_ids(&repos.todos, in_list(None)).await, ["c", "a"]);
+	assert_eq!(
+		repos.todos.count(&in_list(None)).await.expect("Failed to count todos"),
+		2
+	);
+	assert_eq!(
+		find_ids(&repos.todos, in_list(Some("errands"))).await,
+		["c"]
+	);
+	assert!(
+		find_ids(
+			&repos.todos,
+			TodoQuery {
+				list_id: Some("missing".to_string()),
+				..Default::default()
+			}
+		)
+		.await
+		.is_empty(),
+		"a missing list has no todos"
+	);
+}
+
+/// Todos matching the search more closely come first, ties being broken by id.
+async fn ranks_search_results(repo: DynTodoRepository) {
+	create(
+		&repo,
+		vec![
+			Todo {
+				description: "Buy milk".to_string(),
+				..todo("a", 1)
+			},
+			Todo {
+				description: "Milk milk".to_string(),
+				..todo("b", 2)
+			},
+			Todo {
+				description: "Buy bread".to_string(),
+				..todo("c", 3)
+			},
+			Todo {
+				description: "Buy milk".to_string(),
+				..todo("d", 0)
+			},
+		],
+	)
+	.await;
+
+	let search = |search: &str| TodoQuery {
+		search: Some(search.to_string()),
+		..Default::default()
+	};
+
+	assert_eq!(find_ids(&repo, search("milk")).await, ["b", "d", "a"]);
+	assert_eq!(
+		repo.count(&search("milk")).await.expect("Failed to count todos"),
+		3
+	);
+	assert_eq!(
+		find_ids(&repo, search("buy milk")).await,
+		["d", "a"],
+		"every word has to match"
+	);
+	assert!(find_ids(&repo, search("cheese")).await.is_empty());
+}
+
+async fn paginates_with_cursor(repo: DynTodoRepository) {
+	create(&repo, (1..=5).map(|i| todo(&i.to_string(), i)).collect()).await;
+
+	let page = |cursor: Option<&str>| TodoQuery {
+		cursor: cursor.map(String::from),
+		limit: Some(2),
+		..Default::default()
+	};
+
+	assert_eq!(find_ids(&repo, page(None)).await, ["5", "4"]);
+	assert_eq!(find_ids(&repo, page(Some("4"))).await, ["3", "2"]);
+	assert_eq!(find_ids(&repo, page(Some("2"))).await, ["1"]);
+	assert!(find_ids(&repo, page(Some("missing"))).await.is_empty());
+
+	assert_eq!(
+		repo.count(&page(Some("4"))).await.expect("Failed to count todos"),
+		5,
+		"the count ignores the page"
+	);
+}
+
+async fn paginates_search_with_cursor(repo: DynTodoRepository) {
+	create(
+		&repo,
+		vec![
+			Todo {
+				description: "Milk milk".to_string(),
+				..todo("a", 0)
+			},
+			Todo {
+				description: "Buy milk".to_string(),
+				..todo("b", 1)
+			},
+			Todo {
+				description: "Buy milk".to_string(),
+				..todo("c", 2)
+			},
+			Todo {
+				description: "Buy bread".to_string(),
+				..todo("d", 3)
+			},
+		],
+	)
+	.await;
+
+	let page = |cursor: Option<&str>| TodoQuery {
+		search: Some("milk".to_string()),
+		cursor: cursor.map(String::from),
+		limit: Some(2),
+		..Default::default()
+	};
+
+	assert_eq!(find_ids(&repo, page(None)).await, ["a", "c"]);
+	assert_eq!(
+		find_ids(&repo, page(Some("c"))).await,
+		["b"],
+		"the cursor follows the rank"
+	);
+	assert_eq!(
+		repo.count(&page(Some("c"))).await.expect("Failed to count todos"),
+		3,
+		"the count ignores the page"
+	);
+}
+
+async fn updates_only_current_version(repo: DynTodoRepository) {
+	create(&repo, vec![todo("a", 0)]).await;
+	let todo = repo.find_by_id("a".to_string()).await.expect("Failed to find todo");
+
+	let updated = repo
+		.update(Todo {
+			description: "Updated".to_string(),
+			..todo.clone()
+		})
+		.await
+		.expect("Failed to update todo");
+	assert_eq!(updated.version, 2, "an update increments the version");
+	assert_eq!(updated.description, "Updated");
+
+	let found = repo.find_by_id("a".to_string()).await.expect("Failed to find todo");
+	assert_eq!(found.description, "Updated");
+	assert_eq!(found.version, 2);
+
+	assert!(
+		matches!(repo.update(todo).await, Err(UpdateError::Conflict)),
+		"updating a former version is a Conflict"
+	);
+	assert!(
+		matches!(
+			repo.update(self::todo("missing", 0)).await,
+			Err(UpdateError::NotFound)
+		),
+		"updating a missing todo is NotFound"
+	);
+}
+
+async fn trashes_and_restores_subtasks(repo: DynTodoRepository) {
+	create(
+		&repo,
+		vec![
+			todo("parent", 0),
+			Todo {
+				parent_id: Some("parent".to_string()),
+				..todo("child", 1)
+			},
+			Todo {
+				parent_id: Some("child".to_string()),
+				..todo("grandchild", 2)
+			},
+		],
+	)
+	.await;
+
+	let descendants = repo
+		.find_descendants("parent".to_string())
+		.await
+		.expect("Failed to find subtasks");
+	assert_eq!(sorted_ids(&descendants), ["child", "grandchild"]);
+
+	repo.delete("parent".to_string()).await.expect("Failed to delete todo");
+
+	for id in ["parent", "child", "grandchild"] {
+		assert!(
+			matches!(
+				repo.find_by_id(id.to_string()).await,
+				Err(FindTodoError::NotFound)
+			),
+			"deleting a todo trashes its subtasks"
+		);
+	}
+
+	let deleted = repo.find_deleted().await.expect("Failed to find deleted todos");
+	assert_eq!(sorted_ids(&deleted), ["child", "grandchild", "parent"]);
+
+	assert!(
+		matches!(
+			repo.delete("parent".to_string()).await,
+			Err(DeleteError::NotFound)
+		),
+		"deleting a trashed todo is NotFound"
+	);
+	assert!(
+		matches!(
+			repo.delete("missing".to_string()).await,
+			Err(DeleteError::NotFound)
+		),
+		"deleting a missing todo is NotFound"
+	);
+
+	let restored = repo.restore("child".to_string()).await.expect("Failed to restore todo");
+	assert_eq!(restored.id, "child");
+	assert!(restored.deleted_at.is_none());
+
+	assert!(
+		find_ids(&repo, TodoQuery::default()).await.len() == 3,
+		"restoring a todo restores its subtasks and parents"
+	);
+	assert!(repo.find_deleted().await.expect("Failed to find deleted todos").is_empty());
+
+	assert!(
+		matches!(
+			repo.restore("child".to_string()).await,
+			Err(RestoreError::NotFound)
+		),
+		"restoring a todo out of the trash is NotFound"
+	);
+}
+
+async fn trashes_done_todos(repo: DynTodoRepository) {
+	create(
+		&repo,
+		vec![
+			Todo {
+				done: true,
+				done_at: Some(chrono::Utc::now()),
+				..todo("done", 0)
+			},
+			Todo {
+				parent_id: Some("done".to_string()),
+				..todo("subtask", 1)
+			},
+			todo("pending", 2),
+		],
+	)
+	.await;
+
+	let trashed = repo.delete_where_done(None).await.expect("Failed to delete done todos");
+	assert_eq!(
+		sorted_ids(&trashed),
+		["done", "subtask"],
+		"the subtasks of the done todos are trashed along with them"
+	);
+	assert_eq!(find_ids(&repo, TodoQuery::default()).await, ["pending"]);
+}
+
+async fn purges_trash(repo: DynTodoRepository) {
+	create(&repo, vec![todo("a", 0), todo("b", 1)]).await;
+	repo.delete("a".to_string()).await.expect("Failed to delete todo");
+
+	let now = chrono::Utc::now();
+
+	assert_eq!(
+		repo.purge_deleted(now - chrono::Duration::hours(1))
+			.await
+			.expect("Failed to purge todos"),
+		0,
+		"todos trashed after the date are kept"
+	);
+	assert_eq!(
+		repo.purge_deleted(now + chrono::Duration::hours(1))
+			.await
+			.expect("Failed to purge todos"),
+		1
+	);
+	assert!(repo.find_deleted().await.expect("Failed to find deleted todos").is_empty());
+	assert!(
+		matches!(
+			repo.restore("a".to_string()).await,
+			Err(RestoreError::NotFound)
+		),
+		"purged todos are gone"
+	);
+	assert_eq!(find_ids(&repo, TodoQuery::default()).await, ["b"]);
+}
+
+async fn rolls_back_uncommitted_unit_of_work(repo: DynTodoRepository) {
+	create(&repo, vec![todo("a", 0)]).await;
+
+	{
+		let mut uow = repo.begin().await.expect("Failed to begin unit of work");
+		let todo = uow.find_by_id("a".to_string()).await.expect("Failed to find todo");
+		uow.update(todo).await.expect("Failed to update todo");
+		uow.create_todo(self::todo("b", 1)).await.expect("Failed to create todo");
+
+		// a read meanwhile, which a cache must not keep past the rollback
+		find_ids(&repo, TodoQuery::default()).await;
+		repo.count(&TodoQuery::default()).await.expect("Failed to count todos");
+	}
+
+	assert_eq!(
+		repo.find_by_id("a".to_string()).await.expect("Failed to find todo").version,
+		1,
+		"a unit of work dropped uncommitted is rolled back"
+	);
+	assert_eq!(find_ids(&repo, TodoQuery::default()).await, ["a"]);
+	assert_eq!(
+		repo.count(&TodoQuery::default()).await.expect("Failed to count todos"),
+		1
+	);
+
+	let mut uow = repo.begin().await.expect("Failed to begin unit of work");
+	let todo = uow.find_by_id("a".to_string()).await.expect("Failed to find todo");
+	uow.update(todo).await.expect("Failed to update todo");
+	uow.delete("a".to_string()).await.expect("Failed to delete todo");
+	uow.commit().await.expect("Failed to commit unit of work");
+
+	assert!(matches!(
+		repo.find_by_id("a".to_string()).await,
+		Err(FindTodoError::NotFound)
+	));
+	assert!(
+		matches!(
+			repo.restore("a".to_string()).await,
+			Ok(Todo { version: 4, .. })
+		),
+		"a committed unit of work is kept"
+	);
+}
+
+async fn finds_siblings_in_manual_order(repo: DynTodoRepository) {
+	let subtask = |id: &str, position: f64| Todo {
+		parent_id: Some("parent".to_string()),
+		position,
+		..todo(id, 1)
+	};
+	create(
+		&repo,
+		vec![
+			todo("parent", 0),
+			Todo {
+				position: 5.0,
+				..todo("root", 2)
+			},
+			subtask("a", 2.0),
+			subtask("c", 1.0),
+			subtask("b", 1.0),
+			subtask("trashed", 0.0),
+		],
+	)
+	.await;
+	repo.delete("trashed".to_string()).await.expect("Failed to delete todo");
+
+	let mut uow = repo.begin().await.expect("Failed to begin unit of work");
+	let siblings = uow
+		.find_siblings(None, Some("parent".to_string()))
+		.await
+		.expect("Failed to find siblings");
+	assert_eq!(ids(&siblings), ["b", "c", "a"], "ties are broken by id");
+
+	let siblings = uow.find_siblings(None, None).await.expect("Failed to find siblings");
+	assert_eq!(ids(&siblings), ["parent", "root"]);
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Response to the first request made with an idempotency key, replayed to its retries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredResponse {
+	pub status: u16,
+	pub headers: Vec<(String, String)>,
+	pub body: Vec<u8>,
+}
+
+/// Key sent by a client in the `Idempotency-Key` header, so that the retries of a request
+/// are answered without being handled again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyKey {
+	pub key: String,
+	/// Hash of the method, path and body of the request
+	pub fingerprint: String,
+	/// `None` while the first request is still being handled
+	pub response: Option<StoredResponse>,
+	pub expires_at: chrono::DateTime<chrono::Utc>,
+}
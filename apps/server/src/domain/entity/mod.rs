@@ -1,2 +1,7 @@
 pub mod health;
+pub mod idempotency_key;
+pub mod recurrence;
+pub mod tag;
 pub mod todo;
+pub mod todo_list;
+pub mod todo_query;
@@ -0,0 +1,361 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+	Daily,
+	Weekly,
+	Monthly,
+	Yearly,
+}
+
+/// Largest `INTERVAL` accepted, keeping the date arithmetic and the weekly scan bounded.
+const MAX_INTERVAL: u32 = 1000;
+
+/// Periods tried for a monthly or yearly day, enough for Feb 29 across a skipped leap year.
+const MAX_SKIPPED_PERIODS: u32 = 400;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseRecurrenceError {
+	InvalidRule,
+}
+
+/// Subset of the iCalendar RRULE (RFC 5545): `FREQ`, `INTERVAL` up to 1000, `BYDAY` for
+/// weekly rules and `UNTIL`, like `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH`.
+///
+/// `daily`, `weekly`, `monthly` and `yearly` are accepted as shortcuts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+	pub freq: Frequency,
+	pub interval: u32,
+	pub by_day: Vec<Weekday>,
+	/// Last day an occurrence can be due, inclusive
+	pub until: Option<NaiveDate>,
+}
+
+impl Recurrence {
+	pub fn new(freq: Frequency) -> Self {
+		Self {
+			freq,
+			interval: 1,
+			by_day: vec![],
+			until: None,
+		}
+	}
+
+	/// Returns the first occurrence strictly after `after`, keeping its time of day.
+	///
+	/// Monthly and yearly rules skip the months without the day of `after`, as RFC 5545
+	/// does, so a todo due on the 31st stays on the 31st.
+	pub fn next_after(
+		&self,
+		after: chrono::DateTime<chrono::Utc>,
+	) -> Option<chrono::DateTime<chrono::Utc>> {
+		let next = match self.freq {
+			Frequency::Daily => after.checked_add_days(chrono::Days::new(self.interval.into())),
+			Frequency::Weekly if self.by_day.is_empty() => {
+				after.checked_add_days(chrono::Days::new(7 * u64::from(self.interval)))
+			},
+			Frequency::Weekly => {
+				let week_start = after.date_naive().week(Weekday::Mon).first_day();
+
+				(1..=7 * i64::from(self.interval))
+					.map_while(|days| after.checked_add_signed(chrono::Duration::days(days)))
+					.find(|day| {
+						let weeks = (day.date_naive() - week_start).num_weeks();
+
+						weeks % i64::from(self.interval) == 0
+							&& self.by_day.contains(&day.weekday())
+					})
+			},
+			Frequency::Monthly => self.same_day_after(after, self.interval),
+			Frequency::Yearly => self.same_day_after(after, 12u32.checked_mul(self.interval)?),
+		}?;
+
+		match self.until {
+			Some(until) if next.date_naive() > until => None,
+			_ => Some(next),
+		}
+	}
+
+	/// First date a multiple of `months` after `after` falling on the same day of month.
+	fn same_day_after(
+		&self,
+		after: chrono::DateTime<chrono::Utc>,
+		months: u32,
+	) -> Option<chrono::DateTime<chrono::Utc>> {
+		(1..=MAX_SKIPPED_PERIODS)
+			.map_while(|periods| months.checked_mul(periods))
+			.map_while(|months| after.checked_add_months(Months::new(months)))
+			.find(|next| next.day() == after.day())
+	}
+}
+
+impl FromStr for Recurrence {
+	type Err = ParseRecurrenceError;
+
+	fn from_str(rule: &str) -> Result<Self, Self::Err> {
+		let rule = rule.trim().to_uppercase();
+		let rule = rule.strip_prefix("RRULE:").unwrap_or(&rule);
+
+		let shortcut = match rule {
+			"DAILY" => Some(Frequency::Daily),
+			"WEEKLY" => Some(Frequency::Weekly),
+			"MONTHLY" => Some(Frequency::Monthly),
+			"YEARLY" => Some(Frequency::Yearly),
+			_ => None,
+		};
+		if let Some(freq) = shortcut {
+			return Ok(Recurrence::new(freq));
+		}
+
+		let mut freq: Option<Frequency> = None;
+		let mut recurrence = Recurrence::new(Frequency::Daily);
+
+		for part in rule.split(';').filter(|part| !part.is_empty()) {
+			let (key, value) = part.split_once('=').ok_or(ParseRecurrenceError::InvalidRule)?;
+
+			match key {
+				"FREQ" => {
+					freq = Some(match value {
+						"DAILY" => Frequency::Daily,
+						"WEEKLY" => Frequency::Weekly,
+						"MONTHLY" => Frequency::Monthly,
+						"YEARLY" => Frequency::Yearly,
+						_ => return Err(ParseRecurrenceError::InvalidRule),
+					})
+				},
+				"INTERVAL" => {
+					recurrence.interval = value
+						.parse()
+						.ok()
+						.filter(|interval| (1..=MAX_INTERVAL).contains(interval))
+						.ok_or(ParseRecurrenceError::InvalidRule)?
+				},
+				"BYDAY" => {
+					recurrence.by_day = value
+						.split(',')
+						.map(parse_weekday)
+						.collect::<Result<Vec<Weekday>, ParseRecurrenceError>>()?;
+					recurrence.by_day.sort_by_key(|day| day.num_days_from_monday());
+					recurrence.by_day.dedup();
+				},
+				"UNTIL" => {
+					// only the date part of `20241231T235959Z` is kept
+					recurrence.until = value
+						.get(..8)
+						.and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+						.map(Some)
+						.ok_or(ParseRecurrenceError::InvalidRule)?
+				},
+				_ => return Err(ParseRecurrenceError::InvalidRule),
+			}
+		}
+
+		recurrence.freq = freq.ok_or(ParseRecurrenceError::InvalidRule)?;
+
+		if !recurrence.by_day.is_empty() && recurrence.freq != Frequency::Weekly {
+			return Err(ParseRecurrenceError::InvalidRule);
+		}
+
+		Ok(recurrence)
+	}
+}
+
+/// Formats the normalized RRULE, without the `RRULE:` prefix.
+impl Display for Recurrence {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let freq = match self.freq {
+			Frequency::Daily => "DAILY",
+			Frequency::Weekly => "WEEKLY",
+			Frequency::Monthly => "MONTHLY",
+			Frequency::Yearly => "YEARLY",
+		};
+
+		write!(f, "FREQ={}", freq)?;
+
+		if self.interval > 1 {
+			write!(f, ";INTERVAL={}", self.interval)?;
+		}
+		if !self.by_day.is_empty() {
+			let days: Vec<&str> = self.by_day.iter().map(format_weekday).collect();
+
+			write!(f, ";BYDAY={}", days.join(","))?;
+		}
+		if let Some(until) = self.until {
+			write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+		}
+
+		Ok(())
+	}
+}
+
+fn parse_weekday(day: &str) -> Result<Weekday, ParseRecurrenceError> {
+	match day {
+		"MO" => Ok(Weekday::Mon),
+		"TU" => Ok(Weekday::Tue),
+		"WE" => Ok(Weekday::Wed),
+		"TH" => Ok(Weekday::Thu),
+		"FR" => Ok(Weekday::Fri),
+		"SA" => Ok(Weekday::Sat),
+		"SU" => Ok(Weekday::Sun),
+		_ => Err(ParseRecurrenceError::InvalidRule),
+	}
+}
+
+fn format_weekday(day: &Weekday) -> &'static str {
+	match day {
+		Weekday::Mon => "MO",
+		Weekday::Tue => "TU",
+		Weekday::Wed => "WE",
+		Weekday::Thu => "TH",
+		Weekday::Fri => "FR",
+		Weekday::Sat => "SA",
+		Weekday::Sun => "SU",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::{TimeZone, Utc};
+
+	use super::*;
+
+	fn at(year: i32, month: u32, day: u32) -> chrono::DateTime<Utc> {
+		Utc.with_ymd_and_hms(year, month, day, 9, 30, 0).unwrap()
+	}
+
+	fn rule(rule: &str) -> Recurrence {
+		rule.parse().unwrap()
+	}
+
+	#[test]
+	fn parses_shortcuts() {
+		assert_eq!(rule("daily"), Recurrence::new(Frequency::Daily));
+		assert_eq!(rule(" Weekly "), Recurrence::new(Frequency::Weekly));
+		assert_eq!(rule("monthly").to_string(), "FREQ=MONTHLY");
+		assert_eq!(rule("YEARLY").to_string(), "FREQ=YEARLY");
+	}
+
+	#[test]
+	fn normalizes_rules() {
+		assert_eq!(
+			rule("RRULE:freq=weekly;byday=th,mo,th;interval=2;until=20241231T235959Z").to_string(),
+			"FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;UNTIL=20241231"
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_rules() {
+		for invalid in [
+			"",
+			"hourly",
+			"INTERVAL=2",
+			"FREQ=DAILY;INTERVAL=0",
+			"FREQ=DAILY;INTERVAL=1001",
+			"FREQ=YEARLY;INTERVAL=400000000",
+			"FREQ=MONTHLY;BYDAY=MO",
+			"FREQ=WEEKLY;BYDAY=XX",
+			"FREQ=DAILY;UNTIL=tomorrow",
+			"FREQ=DAILY;COUNT=3",
+		] {
+			assert_eq!(
+				invalid.parse::<Recurrence>(),
+				Err(ParseRecurrenceError::InvalidRule),
+				"{invalid}"
+			);
+		}
+	}
+
+	#[test]
+	fn repeats_daily_and_weekly() {
+		assert_eq!(
+			rule("FREQ=DAILY;INTERVAL=3").next_after(at(2024, 2, 27)),
+			Some(at(2024, 3, 1))
+		);
+		assert_eq!(
+			rule("weekly").next_after(at(2024, 1, 4)),
+			Some(at(2024, 1, 11))
+		);
+	}
+
+	#[test]
+	fn repeats_weekly_days_every_interval() {
+		let recurrence = rule("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH");
+
+		// Thursday, January 4th: the rest of its week has no listed day, the next week
+		// is skipped
+		assert_eq!(recurrence.next_after(at(2024, 1, 4)), Some(at(2024, 1, 15)));
+		assert_eq!(
+			recurrence.next_after(at(2024, 1, 15)),
+			Some(at(2024, 1, 18))
+		);
+		assert_eq!(
+			recurrence.next_after(at(2024, 1, 18)),
+			Some(at(2024, 1, 29))
+		);
+	}
+
+	#[test]
+	fn skips_months_without_the_day() {
+		let monthly = rule("monthly");
+
+		assert_eq!(monthly.next_after(at(2024, 1, 31)), Some(at(2024, 3, 31)));
+		assert_eq!(monthly.next_after(at(2024, 3, 31)), Some(at(2024, 5, 31)));
+		assert_eq!(monthly.next_after(at(2024, 1, 30)), Some(at(2024, 3, 30)));
+		assert_eq!(
+			rule("FREQ=MONTHLY;INTERVAL=2").next_after(at(2024, 8, 31)),
+			Some(at(2024, 10, 31))
+		);
+	}
+
+	#[test]
+	fn skips_years_without_february_29th() {
+		assert_eq!(
+			rule("yearly").next_after(at(2024, 2, 29)),
+			Some(at(2028, 2, 29))
+		);
+		assert_eq!(
+			rule("yearly").next_after(at(2096, 2, 29)),
+			Some(at(2104, 2, 29))
+		);
+		assert_eq!(
+			rule("yearly").next_after(at(2024, 2, 28)),
+			Some(at(2025, 2, 28))
+		);
+	}
+
+	#[test]
+	fn stops_after_until() {
+		let recurrence = rule("FREQ=DAILY;UNTIL=20240102");
+
+		assert_eq!(recurrence.next_after(at(2024, 1, 1)), Some(at(2024, 1, 2)));
+		assert_eq!(recurrence.next_after(at(2024, 1, 2)), None);
+		assert_eq!(
+			rule("FREQ=MONTHLY;UNTIL=20240330").next_after(at(2024, 1, 31)),
+			None
+		);
+	}
+
+	#[test]
+	fn bounds_the_largest_intervals() {
+		assert_eq!(
+			rule("FREQ=WEEKLY;INTERVAL=1000;BYDAY=MO").next_after(at(2024, 1, 1)),
+			Some(at(2024, 1, 1) + chrono::Duration::weeks(1000))
+		);
+		assert_eq!(
+			rule("FREQ=YEARLY;INTERVAL=1000").next_after(at(2024, 6, 1)),
+			Some(at(3024, 6, 1))
+		);
+		assert_eq!(
+			rule("FREQ=YEARLY;INTERVAL=1000").next_after(at(262_000, 1, 1)),
+			None,
+			"dates past the supported range end the recurrence"
+		);
+		assert_eq!(
+			rule("FREQ=WEEKLY;INTERVAL=1000;BYDAY=MO").next_after(at(262_142, 12, 1)),
+			None
+		);
+	}
+}
@@ -0,0 +1,47 @@
+use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
+
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Default, Debug, Clone, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Tag {
+	pub id: String,
+	pub name: String,
+	pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Tag {
+	pub fn new(name: String) -> Self {
+		Self {
+			id: nanoid!(),
+			name: Tag::normalize_name(&name),
+			created_at: chrono::Utc::now(),
+		}
+	}
+
+	pub fn rename(&mut self, name: String) -> &mut Self {
+		self.name = Tag::normalize_name(&name);
+
+		self
+	}
+
+	/// Tags are matched case-insensitively, so names are stored trimmed and lowercased.
+	pub fn normalize_name(name: &str) -> String {
+		name.trim().to_lowercase()
+	}
+
+	/// Normalizes, deduplicates and sorts a list of tag names, dropping empty ones.
+	pub fn normalize_names(names: &[String]) -> Vec<String> {
+		let mut names: Vec<String> = names
+			.iter()
+			.map(|name| Tag::normalize_name(name))
+			.filter(|name| !name.is_empty())
+			.collect();
+
+		names.sort();
+		names.dedup();
+
+		names
+	}
+}
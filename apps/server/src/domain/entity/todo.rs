@@ -1,11 +1,13 @@
 use std::fmt::Display;
 
 use nanoid::nanoid;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use utoipa::ToSchema;
 
-#[derive(ToSchema, Serialize, Default, Debug, Clone, sqlx::FromRow)]
+use super::{recurrence::Recurrence, tag::Tag, todo_query::DateRange};
+
+#[derive(ToSchema, Serialize, Deserialize, Default, Debug, Clone, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct Todo {
 	pub id: String,
@@ -14,20 +16,128 @@ pub struct Todo {
 	pub created_at: chrono::DateTime<chrono::Utc>,
 	pub updated_at: chrono::DateTime<chrono::Utc>,
 	pub done_at: Option<chrono::DateTime<chrono::Utc>>,
+	pub due_at: Option<chrono::DateTime<chrono::Utc>>,
+	pub priority: TodoPriority,
+	#[sqlx(default)]
+	pub tags: Vec<String>,
+	/// `None` when the todo does not belong to a list
+	pub list_id: Option<String>,
+	/// `None` for top level todos, otherwise the id of the todo this one is a subtask of
+	pub parent_id: Option<String>,
+	/// Normalized recurrence rule, like `FREQ=WEEKLY;BYDAY=MO`
+	#[schema(example = "FREQ=WEEKLY;BYDAY=MO")]
+	pub recurrence: Option<String>,
+	/// Rank in the manual order, lowest first
+	pub position: f64,
+	/// Set while the todo is in the trash
+	pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+	/// Incremented on every write, sent as the `ETag` of the todo
+	pub version: i32,
+	/// Excerpt of the description with the matched words wrapped in `<mark>`, only set
+	/// by searches
+	#[sqlx(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[schema(example = "Buy <mark>milk</mark> and bread")]
+	pub snippet: Option<String>,
 }
 
 impl Todo {
 	pub fn new(description: String) -> Self {
+		let created_at = chrono::Utc::now();
+
 		Self {
 			id: nanoid!(),
 			description,
 			done: false,
-			created_at: chrono::Utc::now(),
+			created_at,
 			updated_at: chrono::Utc::now(),
 			done_at: None,
+			due_at: None,
+			priority: TodoPriority::None,
+			tags: vec![],
+			list_id: None,
+			parent_id: None,
+			recurrence: None,
+			position: Todo::default_position(created_at),
+			deleted_at: None,
+			version: 1,
+			snippet: None,
 		}
 	}
 
+	/// Whether the todo is at one of the `versions` of an `If-Match`, `None` matching any
+	/// version.
+	pub fn has_version(&self, versions: Option<&[i32]>) -> bool {
+		versions.map_or(true, |versions| versions.contains(&self.version))
+	}
+
+	/// New todos go on top of the manual order, as with the default order.
+	pub fn default_position(created_at: chrono::DateTime<chrono::Utc>) -> f64 {
+		-(created_at.timestamp_micros() as f64 / 1_000_000.0)
+	}
+
+	/// Moves the todo between two neighbours of the manual order, `None` standing for
+	/// the top or the bottom of the list. Only this todo changes, its position becomes
+	/// the middle of the neighbours positions.
+	///
+	/// Returns `None` when the neighbours are not in order or too close to fit a
+	/// position between them.
+	pub fn move_between(&mut self, after: Option<f64>, before: Option<f64>) -> Option<&mut Self> {
+		let position = match (after, before) {
+			(Some(after), Some(before)) => after / 2.0 + before / 2.0,
+			(Some(after), None) => after + 1.0,
+			(None, Some(before)) => before - 1.0,
+			(None, None) => return None,
+		};
+
+		if after.map_or(false, |after| position <= after)
+			|| before.map_or(false, |before| position >= before)
+		{
+			return None;
+		}
+
+		self.position = position;
+		self.updated_at = chrono::Utc::now();
+
+		Some(self)
+	}
+
+	pub fn update_recurrence(&mut self, recurrence: Option<Recurrence>) -> &mut Self {
+		self.recurrence = recurrence.map(|recurrence| recurrence.to_string());
+		self.updated_at = chrono::Utc::now();
+
+		self
+	}
+
+	/// Builds the pending todo following this one when it has a recurrence rule, which
+	/// moves to the new todo.
+	///
+	/// The next due date follows the due date, or `now` when there is none, skipping
+	/// occurrences already in the past.
+	pub fn next_occurrence(&mut self, now: chrono::DateTime<chrono::Utc>) -> Option<Todo> {
+		let recurrence: Recurrence = self.recurrence.as_ref()?.parse().ok()?;
+
+		let mut due_at = self.due_at.unwrap_or(now);
+		let today = now.date_naive();
+		loop {
+			due_at = recurrence.next_after(due_at)?;
+
+			if due_at.date_naive() >= today {
+				break;
+			}
+		}
+
+		let mut next = Todo::new(self.description.clone());
+		next.due_at = Some(due_at);
+		next.priority = self.priority;
+		next.tags = self.tags.clone();
+		next.list_id = self.list_id.clone();
+		next.parent_id = self.parent_id.clone();
+		next.recurrence = self.recurrence.take();
+
+		Some(next)
+	}
+
 	pub fn mark_as_done(&mut self, done: bool) -> &mut Self {
 		self.done = done;
 		self.done_at = if done { Some(chrono::Utc::now()) } else { None };
@@ -35,17 +145,161 @@ impl Todo {
 
 		self
 	}
+
+	pub fn is_overdue(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+		DueFilter::Overdue.matches(self, now)
+	}
+
+	pub fn update_description(&mut self, description: String) -> &mut Self {
+		self.description = description;
+		self.updated_at = chrono::Utc::now();
+
+		self
+	}
+
+	pub fn update_priority(&mut self, priority: TodoPriority) -> &mut Self {
+		self.priority = priority;
+		self.updated_at = chrono::Utc::now();
+
+		self
+	}
+
+	pub fn update_tags(&mut self, tags: Vec<String>) -> &mut Self {
+		self.tags = Tag::normalize_names(&tags);
+		self.updated_at = chrono::Utc::now();
+
+		self
+	}
+}
+
+/// Done subtasks over all the direct subtasks of a todo.
+#[derive(ToSchema, Serialize, Default, Debug, Clone, Copy, PartialEq)]
+pub struct SubtaskProgress {
+	pub done: usize,
+	pub total: usize,
+}
+
+impl SubtaskProgress {
+	pub fn of(subtasks: &[Todo]) -> Self {
+		Self {
+			done: subtasks.iter().filter(|subtask| subtask.done).count(),
+			total: subtasks.len(),
+		}
+	}
+}
+
+impl Display for SubtaskProgress {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}/{}", self.done, self.total)
+	}
+}
+
+/// A todo along with its subtasks, recursively.
+#[derive(ToSchema, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoTree {
+	#[serde(flatten)]
+	pub todo: Todo,
+	pub progress: SubtaskProgress,
+	pub subtasks: Vec<TodoTree>,
+}
+
+#[derive(
+	ToSchema,
+	Serialize,
+	Deserialize,
+	Default,
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Hash,
+	PartialOrd,
+	Ord,
+	sqlx::Type,
+)]
+#[serde(rename_all = "lowercase")]
+#[repr(i16)]
+pub enum TodoPriority {
+	#[default]
+	None = 0,
+	Low = 1,
+	Medium = 2,
+	High = 3,
+	Urgent = 4,
+}
+
+impl Display for TodoPriority {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TodoOrder {
+	#[default]
+	CreatedAt,
+	/// Priority, then creation date
+	Priority,
+	/// By position, as arranged by the user
+	Manual,
+}
+
+/// Due date buckets, computed on UTC calendar days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DueFilter {
+	/// Pending todos due before today
+	Overdue,
+	Today,
+	/// Todos due after today
+	Upcoming,
+}
+
+impl DueFilter {
+	/// Returns the `[from, to)` range of `due_at` matched by the filter.
+	pub fn bounds(
+		&self,
+		now: chrono::DateTime<chrono::Utc>,
+	) -> (
+		Option<chrono::DateTime<chrono::Utc>>,
+		Option<chrono::DateTime<chrono::Utc>>,
+	) {
+		let today = now.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+		let tomorrow = today + chrono::Duration::days(1);
+
+		match self {
+			DueFilter::Overdue => (None, Some(today)),
+			DueFilter::Today => (Some(today), Some(tomorrow)),
+			DueFilter::Upcoming => (Some(tomorrow), None),
+		}
+	}
+
+	pub fn matches(&self, todo: &Todo, now: chrono::DateTime<chrono::Utc>) -> bool {
+		let Some(due_at) = todo.due_at else {
+			return false;
+		};
+
+		if *self == DueFilter::Overdue && todo.done {
+			return false;
+		}
+
+		let (from, to) = self.bounds(now);
+
+		DateRange { from, to }.contains(Some(due_at))
+	}
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub enum TodoOperation {
 	Create,
 	Read,
-	#[allow(dead_code)]
 	Update,
 	MarkAsDone,
 	MarkAsUndone,
+	Move,
 	Delete,
+	Restore,
 }
 
 impl Display for TodoOperation {
@@ -62,6 +316,22 @@ pub struct TodoView {
 	pub created_at: String,
 	pub updated_at: String,
 	pub done_at: String,
+	pub due_at: String,
+	pub overdue: bool,
+	pub priority: String,
+	pub tags: Vec<String>,
+	/// Nesting level, `0` for top level todos
+	pub depth: usize,
+	/// Subtasks progress like `3/5`, empty when the todo has no subtask
+	pub progress: String,
+	/// Normalized recurrence rule, empty when the todo does not repeat
+	pub recurrence: String,
+	/// `daily`, `weekly`, `monthly` or `yearly` for plain rules, the rule otherwise
+	pub recurrence_label: String,
+	pub position: f64,
+	pub deleted_at: String,
+	/// Escaped search excerpt, only the `<mark>` tags are kept as HTML
+	pub snippet: String,
 	pub kind: String,
 	pub can: String,
 }
@@ -80,6 +350,13 @@ impl Display for TodoCan {
 
 impl TodoView {
 	pub fn new(todo: Todo, kind: TodoOperation, can: TodoCan) -> Self {
+		let overdue = todo.is_overdue(chrono::Utc::now());
+		let recurrence = todo.recurrence.unwrap_or_default();
+		let recurrence_label = match recurrence.strip_prefix("FREQ=") {
+			Some(freq) if !freq.contains(';') => freq.to_lowercase(),
+			_ => recurrence.clone(),
+		};
+
 		Self {
 			id: todo.id,
 			description: todo.description,
@@ -90,8 +367,60 @@ impl TodoView {
 				.done_at
 				.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
 				.unwrap_or_default(),
+			due_at: todo.due_at.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+			overdue,
+			priority: todo.priority.to_string().to_lowercase(),
+			tags: todo.tags,
+			depth: 0,
+			progress: String::new(),
+			recurrence,
+			recurrence_label,
+			position: todo.position,
+			deleted_at: todo
+				.deleted_at
+				.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+				.unwrap_or_default(),
+			snippet: todo.snippet.as_deref().map(escape_snippet).unwrap_or_default(),
 			kind: kind.to_string(),
 			can: can.to_string().to_uppercase(),
 		}
 	}
+
+	/// Flattens the tree depth first, each subtask following its parent.
+	pub fn from_tree(tree: TodoTree, kind: TodoOperation, can: TodoCan) -> Vec<Self> {
+		fn flatten(
+			tree: TodoTree,
+			depth: usize,
+			kind: &TodoOperation,
+			can: &TodoCan,
+			views: &mut Vec<TodoView>,
+		) {
+			let mut view = TodoView::new(tree.todo, kind.clone(), can.clone());
+			view.depth = depth;
+			if tree.progress.total > 0 {
+				view.progress = tree.progress.to_string();
+			}
+			views.push(view);
+
+			for subtask in tree.subtasks {
+				flatten(subtask, depth + 1, kind, can, views);
+			}
+		}
+
+		let mut views = vec![];
+		flatten(tree, 0, &kind, &can, &mut views);
+
+		views
+	}
+}
+
+fn escape_snippet(snippet: &str) -> String {
+	snippet
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&#x27;")
+		.replace("&lt;mark&gt;", "<mark>")
+		.replace("&lt;/mark&gt;", "</mark>")
 }
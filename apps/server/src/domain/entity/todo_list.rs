@@ -0,0 +1,31 @@
+use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
+
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Default, Debug, Clone, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoList {
+	pub id: String,
+	pub name: String,
+	pub created_at: chrono::DateTime<chrono::Utc>,
+	pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TodoList {
+	pub fn new(name: String) -> Self {
+		Self {
+			id: nanoid!(),
+			name: name.trim().to_string(),
+			created_at: chrono::Utc::now(),
+			updated_at: chrono::Utc::now(),
+		}
+	}
+
+	pub fn rename(&mut self, name: String) -> &mut Self {
+		self.name = name.trim().to_string();
+		self.updated_at = chrono::Utc::now();
+
+		self
+	}
+}
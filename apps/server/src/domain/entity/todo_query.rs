@@ -0,0 +1,101 @@
+use super::todo::{DueFilter, TodoOrder, TodoPriority};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TodoStatus {
+	Pending,
+	Done,
+}
+
+impl TodoStatus {
+	pub fn is_done(&self) -> bool {
+		*self == TodoStatus::Done
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortDirection {
+	Asc,
+	#[default]
+	Desc,
+}
+
+impl SortDirection {
+	pub fn as_sql(&self) -> &'static str {
+		match self {
+			SortDirection::Asc => "ASC",
+			SortDirection::Desc => "DESC",
+		}
+	}
+}
+
+/// Sort field and direction, ties are broken by id in the same direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TodoSort {
+	pub order: TodoOrder,
+	pub direction: SortDirection,
+}
+
+impl TodoSort {
+	/// Sorts newest and highest priority first, and the manual order by position.
+	pub fn new(order: TodoOrder) -> Self {
+		let direction = match order {
+			TodoOrder::CreatedAt | TodoOrder::Priority => SortDirection::Desc,
+			TodoOrder::Manual => SortDirection::Asc,
+		};
+
+		Self { order, direction }
+	}
+}
+
+impl Default for TodoSort {
+	fn default() -> Self {
+		Self::new(TodoOrder::default())
+	}
+}
+
+/// `[from, to)` range of dates, unbounded on the missing sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DateRange {
+	pub from: Option<chrono::DateTime<chrono::Utc>>,
+	pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl DateRange {
+	pub fn is_unbounded(&self) -> bool {
+		self.from.is_none() && self.to.is_none()
+	}
+
+	/// Missing dates only match an unbounded range.
+	pub fn contains(&self, date: Option<chrono::DateTime<chrono::Utc>>) -> bool {
+		match date {
+			Some(date) => {
+				self.from.map_or(true, |from| date >= from) && self.to.map_or(true, |to| date < to)
+			},
+			None => self.is_unbounded(),
+		}
+	}
+}
+
+/// Specification of the todos to find or count, trashed todos excepted. Every criterion is
+/// optional and all of them have to match.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TodoQuery {
+	pub status: Option<TodoStatus>,
+	pub due: Option<DueFilter>,
+	pub priority: Option<TodoPriority>,
+	/// Normalized tag name
+	pub tag: Option<String>,
+	pub list_id: Option<String>,
+	pub created: DateRange,
+	pub done: DateRange,
+	/// Case-insensitive substring of the description
+	pub contains: Option<String>,
+	/// Full-text search on the description, the matching todos are then ranked by relevance
+	/// instead of `sort`
+	pub search: Option<String>,
+	pub sort: TodoSort,
+	/// Id of the last todo of the previous page
+	pub cursor: Option<String>,
+	/// `None` returns every todo after the cursor
+	pub limit: Option<i64>,
+}
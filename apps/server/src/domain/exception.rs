@@ -2,11 +2,70 @@ use serde::Serialize;
 
 #[derive(Debug, thiserror::Error, Serialize)]
 pub enum TodoException {
-	#[error("[409] Todo already exists")]
+	#[error("Todo already exists")]
 	#[allow(dead_code)]
 	AlreadyExists,
-	#[error("[422] Todo not exists")]
+	#[error("Invalid todo status")]
+	InvalidStatus,
+	#[error("Invalid todo query")]
+	InvalidQuery,
+	#[error("Invalid recurrence rule")]
+	InvalidRecurrence,
+	#[error("Invalid todo move")]
+	InvalidMove,
+	#[error("A batch holds from 1 to 500 operations")]
+	InvalidBatch,
+	#[error("Invalid todo patch")]
+	InvalidPatch,
+	/// The patch changes other fields than `description` and `done`
+	#[error("Only the description and done fields of a todo can be patched")]
+	ReadOnlyField,
+	#[error("Todo not exists")]
 	NotFound,
-	#[error("[500] Unknown error")]
-	Unknown,
+	#[error("Todo was modified meanwhile")]
+	Conflict,
+	#[error("Todo version does not match")]
+	PreconditionFailed,
+	/// The repository failed to read or write the todos
+	#[error("Todos could not be read or written")]
+	Storage,
+}
+
+#[derive(Debug, thiserror::Error, Serialize)]
+pub enum TagException {
+	#[error("Tag name can not be empty")]
+	InvalidName,
+	#[error("Tag already exists")]
+	AlreadyExists,
+	#[error("Tag not exists")]
+	NotFound,
+	/// The repository failed to read or write the tags
+	#[error("Tags could not be read or written")]
+	Storage,
+}
+
+#[derive(Debug, thiserror::Error, Serialize)]
+pub enum TodoListException {
+	#[error("List name can not be empty")]
+	InvalidName,
+	#[error("List not exists")]
+	NotFound,
+	/// The repository failed to read or write the lists
+	#[error("Lists could not be read or written")]
+	Storage,
+}
+
+#[derive(Debug, thiserror::Error, Serialize)]
+pub enum IdempotencyException {
+	#[error("Idempotency key must hold 1 to 255 visible ASCII characters")]
+	InvalidKey,
+	/// The first request made with the key is still being handled
+	#[error("A request with this idempotency key is in progress")]
+	InProgress,
+	/// The key was first sent with another method, path or body
+	#[error("Idempotency key was used for another request")]
+	KeyReused,
+	/// The repository failed to read or write the keys
+	#[error("Idempotency keys could not be read or written")]
+	Storage,
 }
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::domain::entity::idempotency_key::{IdempotencyKey, StoredResponse};
+
+#[derive(Debug)]
+pub enum ClaimKeyError {
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum CompleteKeyError {
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum ReleaseKeyError {
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum PurgeKeysError {
+	DBInternalError,
+}
+
+#[async_trait]
+pub trait IdempotencyRepository {
+	/// Stores the key, unless it is already held by an unexpired key which is returned
+	/// instead. An expired key is replaced.
+	async fn claim_key(&self, key: IdempotencyKey)
+		-> Result<Option<IdempotencyKey>, ClaimKeyError>;
+	/// Saves the response to the request which claimed the key.
+	async fn complete_key(
+		&self,
+		key: String,
+		response: StoredResponse,
+	) -> Result<(), CompleteKeyError>;
+	/// Frees the key for a new request, when the one which claimed it failed.
+	async fn release_key(&self, key: String) -> Result<(), ReleaseKeyError>;
+	/// Deletes the keys expired at `now`, returns how many were deleted.
+	async fn purge_expired_keys(
+		&self,
+		now: chrono::DateTime<chrono::Utc>,
+	) -> Result<u64, PurgeKeysError>;
+}
+
+pub type DynIdempotencyRepository = Arc<dyn IdempotencyRepository + Send + Sync>;
@@ -1 +1,4 @@
+pub mod idempotency_repository;
+pub mod tag_repository;
+pub mod todo_list_repository;
 pub mod todo_repository;
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::domain::entity::tag::Tag;
+
+#[derive(Debug)]
+pub enum CreateTagError {
+	AlreadyExists,
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum FindTagError {
+	NotFound,
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum FindManyTagError {
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum UpdateTagError {
+	NotFound,
+	AlreadyExists,
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum DeleteTagError {
+	NotFound,
+	DBInternalError,
+}
+
+/// Tags are shared between todos, deleting or renaming a tag applies to every todo using it.
+#[async_trait]
+pub trait TagRepository {
+	async fn create_tag(&self, tag: Tag) -> Result<Tag, CreateTagError>;
+	async fn find_tag_by_id(&self, id: String) -> Result<Tag, FindTagError>;
+	async fn find_many_tags(&self) -> Result<Vec<Tag>, FindManyTagError>;
+	async fn update_tag(&self, tag: Tag) -> Result<Tag, UpdateTagError>;
+	async fn delete_tag(&self, id: String) -> Result<(), DeleteTagError>;
+}
+
+pub type DynTagRepository = Arc<dyn TagRepository + Send + Sync>;
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::domain::entity::todo_list::TodoList;
+
+#[derive(Debug)]
+pub enum CreateTodoListError {
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum FindTodoListError {
+	NotFound,
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum FindManyTodoListError {
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum UpdateTodoListError {
+	NotFound,
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum DeleteTodoListError {
+	NotFound,
+	DBInternalError,
+}
+
+/// Deleting a list also deletes every todo it contains.
+#[async_trait]
+pub trait TodoListRepository {
+	async fn create_list(&self, list: TodoList) -> Result<TodoList, CreateTodoListError>;
+	async fn find_list_by_id(&self, id: String) -> Result<TodoList, FindTodoListError>;
+	async fn find_many_lists(&self) -> Result<Vec<TodoList>, FindManyTodoListError>;
+	async fn update_list(&self, list: TodoList) -> Result<TodoList, UpdateTodoListError>;
+	async fn delete_list(&self, id: String) -> Result<(), DeleteTodoListError>;
+}
+
+pub type DynTodoListRepository = Arc<dyn TodoListRepository + Send + Sync>;
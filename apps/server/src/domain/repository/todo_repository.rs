@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use axum::async_trait;
 
-use crate::domain::entity::todo::Todo;
+use crate::domain::entity::{todo::Todo, todo_query::TodoQuery};
 
 #[derive(Debug)]
 pub enum CreateTodoError {
@@ -12,7 +12,6 @@ pub enum CreateTodoError {
 #[derive(Debug)]
 pub enum FindTodoError {
 	NotFound,
-	#[allow(dead_code)]
 	DBInternalError,
 }
 
@@ -24,6 +23,8 @@ pub enum FindManyTodoError {
 #[derive(Debug)]
 pub enum UpdateError {
 	NotFound,
+	/// The todo was written since it was read, its version changed
+	Conflict,
 	DBInternalError,
 }
 
@@ -33,19 +34,87 @@ pub enum DeleteError {
 	DBInternalError,
 }
 
+#[derive(Debug)]
+pub enum DeleteManyError {
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum RestoreError {
+	NotFound,
+	DBInternalError,
+}
+
+#[derive(Debug)]
+pub enum PurgeError {
+	DBInternalError,
+}
+
 #[derive(Debug)]
 pub enum CountTodoError {
 	DBInternalError,
 }
 
+#[derive(Debug)]
+pub enum UnitOfWorkError {
+	DBInternalError,
+}
+
 #[async_trait]
 pub trait TodoRepository {
 	async fn create_todo(&self, todo: Todo) -> Result<Todo, CreateTodoError>;
 	async fn find_by_id(&self, id: String) -> Result<Todo, FindTodoError>;
-	async fn find_many_todos(&self, done: Option<&bool>) -> Result<Vec<Todo>, FindManyTodoError>;
+	async fn find_many_todos(&self, query: &TodoQuery) -> Result<Vec<Todo>, FindManyTodoError>;
+	/// Returns the subtasks of the todo at any depth, in no particular order.
+	async fn find_descendants(&self, id: String) -> Result<Vec<Todo>, FindManyTodoError>;
+	/// Saves the todo if its version is still the stored one, then increments it.
 	async fn update(&self, todo: Todo) -> Result<Todo, UpdateError>;
+	/// Moves the todo and its subtasks to the trash.
 	async fn delete(&self, id: String) -> Result<(), DeleteError>;
-	async fn count(&self, done: Option<&bool>) -> Result<i64, CountTodoError>;
+	/// Moves the done todos and their subtasks to the trash.
+	async fn delete_where_done(
+		&self,
+		list_id: Option<&String>,
+	) -> Result<Vec<Todo>, DeleteManyError>;
+	/// Returns the todos in the trash, most recently deleted first.
+	async fn find_deleted(&self) -> Result<Vec<Todo>, FindManyTodoError>;
+	/// Takes the todo out of the trash, along with the subtasks deleted with it and its
+	/// deleted parents.
+	async fn restore(&self, id: String) -> Result<Todo, RestoreError>;
+	/// Permanently deletes the todos moved to the trash before `deleted_before`, returns
+	/// how many were purged.
+	async fn purge_deleted(
+		&self,
+		deleted_before: chrono::DateTime<chrono::Utc>,
+	) -> Result<u64, PurgeError>;
+	/// Counts the todos matching the query, regardless of its sort and page.
+	async fn count(&self, query: &TodoQuery) -> Result<i64, CountTodoError>;
+	/// Starts a unit of work, for use cases reading then writing todos atomically.
+	async fn begin(&self) -> Result<Box<dyn TodoUnitOfWork>, UnitOfWorkError>;
+}
+
+/// Todo operations run in a single transaction, rolled back when dropped uncommitted.
+///
+/// The todos read are locked until the end of the unit of work, so concurrent writes
+/// wait for it instead of interleaving.
+#[async_trait]
+pub trait TodoUnitOfWork: Send {
+	async fn create_todo(&mut self, todo: Todo) -> Result<Todo, CreateTodoError>;
+	async fn find_by_id(&mut self, id: String) -> Result<Todo, FindTodoError>;
+	/// Returns the subtasks of the todo at any depth, in no particular order.
+	async fn find_descendants(&mut self, id: String) -> Result<Vec<Todo>, FindManyTodoError>;
+	/// Returns the todos of the list under the parent, `None` standing for no list or no
+	/// parent, in manual order.
+	async fn find_siblings(
+		&mut self,
+		list_id: Option<String>,
+		parent_id: Option<String>,
+	) -> Result<Vec<Todo>, FindManyTodoError>;
+	/// Saves the todo if its version is still the stored one, then increments it.
+	async fn update(&mut self, todo: Todo) -> Result<Todo, UpdateError>;
+	/// Moves the todo and its subtasks to the trash.
+	async fn delete(&mut self, id: String) -> Result<(), DeleteError>;
+	async fn commit(self: Box<Self>) -> Result<(), UnitOfWorkError>;
 }
 
 pub type DynTodoRepository = Arc<dyn TodoRepository + Send + Sync>;
@@ -1,28 +1,212 @@
-use utoipa::OpenApi;
+use json_patch::{
+	AddOperation, CopyOperation, MoveOperation, Patch, PatchOperation, RemoveOperation,
+	ReplaceOperation, TestOperation,
+};
+use utoipa::{
+	openapi::{
+		path::{ParameterBuilder, ParameterIn, PathItemType},
+		Content, Deprecated, ObjectBuilder, Ref, Required, SchemaType,
+	},
+	Modify, OpenApi,
+};
 
 use crate::{
-	domain::entity::{health::Health, todo::Todo},
-	usecase::create_todo_usecase::CreateTodoParams,
+	domain::entity::{
+		health::Health,
+		tag::Tag,
+		todo::{SubtaskProgress, Todo, TodoPriority, TodoTree},
+		todo_list::TodoList,
+	},
+	usecase::{
+		batch_todos_usecase::{BatchMode, BatchOperation, BatchTodosParams},
+		create_tag_usecase::CreateTagParams,
+		create_todo_list_usecase::CreateTodoListParams,
+		create_todo_usecase::CreateTodoParams,
+		move_todo_usecase::MoveTodoParams,
+		rename_tag_usecase::RenameTagParams,
+		rename_todo_list_usecase::RenameTodoListParams,
+		update_todo_usecase::UpdateTodoParams,
+	},
 };
 
 use super::api_response::{
-	ApiResponseErrorObject, ApiResponseObject, ListInformations, TodoParams,
+	ApiResponseObject, BatchOperationResult, ListInformations, Page, ProblemDetails, TodoCount,
+	TodoParams,
 };
+use super::repository::todo_cached_repo::TodoCacheStats;
 
 #[derive(OpenApi)]
 #[openapi(
 	paths(
 		super::controller::common_ctrl::health,
+		super::controller::common_ctrl::problem_type_ctrl,
+		super::controller::common_ctrl::cache_stats_ctrl,
 		super::controller::todo_ctrl::create_todo_ctrl,
+		super::controller::todo_ctrl::create_subtask_ctrl,
 		super::controller::todo_ctrl::get_all_todos_ctrl,
+		super::controller::todo_ctrl::get_todo_ctrl,
+		super::controller::todo_ctrl::update_todo_ctrl,
 		super::controller::todo_ctrl::delete_todo_ctrl,
+		super::controller::todo_ctrl::delete_many_todos_ctrl,
 		super::controller::todo_ctrl::mark_as_done_todo_ctrl,
 		super::controller::todo_ctrl::mark_as_undone_todo_ctrl,
+		super::controller::todo_ctrl::move_todo_ctrl,
+		super::controller::todo_ctrl::batch_todos_ctrl,
+		super::controller::todo_list_ctrl::create_todo_list_ctrl,
+		super::controller::todo_list_ctrl::get_all_todo_lists_ctrl,
+		super::controller::todo_list_ctrl::get_todo_list_ctrl,
+		super::controller::todo_list_ctrl::rename_todo_list_ctrl,
+		super::controller::todo_list_ctrl::delete_todo_list_ctrl,
+		super::controller::todo_list_ctrl::get_list_todos_ctrl,
+		super::controller::todo_list_ctrl::create_list_todo_ctrl,
+		super::controller::trash_ctrl::get_trash_ctrl,
+		super::controller::trash_ctrl::restore_todo_ctrl,
+		super::controller::tag_ctrl::create_tag_ctrl,
+		super::controller::tag_ctrl::get_all_tags_ctrl,
+		super::controller::tag_ctrl::rename_tag_ctrl,
+		super::controller::tag_ctrl::delete_tag_ctrl,
 	),
-	components(schemas(Health, Todo, ListInformations, TodoParams, ApiResponseObject<Todo, TodoParams>,ApiResponseObject<Vec<Todo>,ListInformations>,ApiResponseErrorObject,CreateTodoParams)),
+	components(schemas(Health, TodoCacheStats, Todo, TodoPriority, ListInformations, TodoParams, ApiResponseObject<Todo, TodoParams>,ApiResponseObject<Vec<Todo>,ListInformations>,TodoTree,SubtaskProgress,ApiResponseObject<TodoTree, TodoParams>,ProblemDetails,CreateTodoParams,UpdateTodoParams,MoveTodoParams,Tag,ApiResponseObject<Tag, TodoParams>,ApiResponseObject<Vec<Tag>,ListInformations>,CreateTagParams,RenameTagParams,TodoList,ApiResponseObject<TodoList, TodoParams>,ApiResponseObject<Vec<TodoList>,ListInformations>,CreateTodoListParams,RenameTodoListParams,BatchMode,BatchOperation,BatchTodosParams,BatchOperationResult,ApiResponseObject<Vec<BatchOperationResult>, TodoParams>,Patch,PatchOperation,AddOperation,RemoveOperation,ReplaceOperation,MoveOperation,CopyOperation,TestOperation)),
+	modifiers(&TODO_PATCH_CONTENT_TYPES, &IdempotencyKeyHeader, &DeprecatedV1),
 	security(),
 	tags(
 		(name = "Todo", description = "Todo items management API"),
+		(name = "List", description = "Todo lists management API"),
+		(name = "Tag", description = "Todo tags management API"),
+		(name = "Trash", description = "Deleted todos management API"),
 	)
 )]
 pub struct ApiDoc;
+
+/// Document of the `/api/v2` surface, sending plain resources, pages and problems.
+#[derive(OpenApi)]
+#[openapi(
+	info(version = "2.0.0"),
+	paths(
+		super::controller::common_ctrl::health,
+		super::controller::common_ctrl::problem_type_ctrl,
+		super::controller::v2::common_ctrl::cache_stats_ctrl,
+		super::controller::v2::todo_ctrl::create_todo_ctrl,
+		super::controller::v2::todo_ctrl::create_subtask_ctrl,
+		super::controller::v2::todo_ctrl::get_all_todos_ctrl,
+		super::controller::v2::todo_ctrl::count_todos_ctrl,
+		super::controller::v2::todo_ctrl::get_todo_ctrl,
+		super::controller::v2::todo_ctrl::update_todo_ctrl,
+		super::controller::v2::todo_ctrl::delete_todo_ctrl,
+		super::controller::v2::todo_ctrl::delete_many_todos_ctrl,
+		super::controller::v2::todo_ctrl::move_todo_ctrl,
+		super::controller::v2::todo_ctrl::batch_todos_ctrl,
+		super::controller::v2::todo_list_ctrl::create_todo_list_ctrl,
+		super::controller::v2::todo_list_ctrl::get_all_todo_lists_ctrl,
+		super::controller::v2::todo_list_ctrl::get_todo_list_ctrl,
+		super::controller::v2::todo_list_ctrl::rename_todo_list_ctrl,
+		super::controller::v2::todo_list_ctrl::delete_todo_list_ctrl,
+		super::controller::v2::todo_list_ctrl::get_list_todos_ctrl,
+		super::controller::v2::todo_list_ctrl::create_list_todo_ctrl,
+		super::controller::v2::trash_ctrl::get_trash_ctrl,
+		super::controller::v2::trash_ctrl::restore_todo_ctrl,
+		super::controller::v2::tag_ctrl::create_tag_ctrl,
+		super::controller::v2::tag_ctrl::get_all_tags_ctrl,
+		super::controller::v2::tag_ctrl::rename_tag_ctrl,
+		super::controller::v2::tag_ctrl::delete_tag_ctrl,
+	),
+	components(schemas(Health, TodoCacheStats, Todo, TodoPriority, TodoTree, SubtaskProgress, Tag, TodoList, Page<Todo>, Page<TodoList>, Page<Tag>, TodoCount, ProblemDetails, CreateTodoParams, UpdateTodoParams, MoveTodoParams, CreateTagParams, RenameTagParams, CreateTodoListParams, RenameTodoListParams, BatchMode, BatchOperation, BatchTodosParams, BatchOperationResult, Patch, PatchOperation, AddOperation, RemoveOperation, ReplaceOperation, MoveOperation, CopyOperation, TestOperation)),
+	modifiers(&TODO_PATCH_CONTENT_TYPES_V2, &IdempotencyKeyHeader),
+	security(),
+	tags(
+		(name = "Todo", description = "Todo items management API"),
+		(name = "List", description = "Todo lists management API"),
+		(name = "Tag", description = "Todo tags management API"),
+		(name = "Trash", description = "Deleted todos management API"),
+	)
+)]
+pub struct ApiDocV2;
+
+/// Adds the patch documents to the request body of the todo update at the given path,
+/// utoipa documenting a single content type per request body.
+struct TodoPatchContentTypes(&'static str);
+
+const TODO_PATCH_CONTENT_TYPES: TodoPatchContentTypes = TodoPatchContentTypes("/api/todos/{id}");
+const TODO_PATCH_CONTENT_TYPES_V2: TodoPatchContentTypes =
+	TodoPatchContentTypes("/api/v2/todos/{id}");
+
+impl Modify for TodoPatchContentTypes {
+	fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+		let Some(request_body) = openapi
+			.paths
+			.paths
+			.get_mut(self.0)
+			.and_then(|path| path.operations.get_mut(&PathItemType::Patch))
+			.and_then(|operation| operation.request_body.as_mut())
+		else {
+			return;
+		};
+
+		let merge_patch = ObjectBuilder::new()
+			.description(Some("Writable fields of the todo item"))
+			.property(
+				"description",
+				ObjectBuilder::new().schema_type(SchemaType::String),
+			)
+			.property(
+				"done",
+				ObjectBuilder::new().schema_type(SchemaType::Boolean),
+			);
+
+		request_body.content.insert(
+			"application/merge-patch+json".to_string(),
+			Content::new(merge_patch),
+		);
+		request_body.content.insert(
+			"application/json-patch+json".to_string(),
+			Content::new(Ref::from_schema_name("Patch")),
+		);
+	}
+}
+
+/// Documents the `Idempotency-Key` header on every `POST` and `PATCH` of the API, where the
+/// idempotency middleware handles it.
+struct IdempotencyKeyHeader;
+
+impl Modify for IdempotencyKeyHeader {
+	fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+		let header = ParameterBuilder::new()
+			.name("Idempotency-Key")
+			.parameter_in(ParameterIn::Header)
+			.required(Required::False)
+			.description(Some(
+				"Unique key of the request, its retries with the same key and body get the first response back",
+			))
+			.schema(Some(ObjectBuilder::new().schema_type(SchemaType::String)))
+			.build();
+
+		for (path, item) in openapi.paths.paths.iter_mut() {
+			if !path.starts_with("/api/") {
+				continue;
+			}
+
+			for (_, operation) in item
+				.operations
+				.iter_mut()
+				.filter(|(method, _)| matches!(method, PathItemType::Post | PathItemType::Patch))
+			{
+				operation.parameters.get_or_insert_with(Vec::new).push(header.clone());
+			}
+		}
+	}
+}
+
+/// Flags the operations of the v1 API as deprecated, in favour of `/api/v2`.
+struct DeprecatedV1;
+
+impl Modify for DeprecatedV1 {
+	fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+		for (_, item) in
+			openapi.paths.paths.iter_mut().filter(|(path, _)| path.starts_with("/api/"))
+		{
+			for operation in item.operations.values_mut() {
+				operation.deprecated = Some(Deprecated::True);
+			}
+		}
+	}
+}
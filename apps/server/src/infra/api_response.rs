@@ -1,15 +1,29 @@
+use std::convert::Infallible;
+
 use axum::{
-	http::StatusCode,
-	response::{IntoResponse, Response},
-	Json,
+	extract::{
+		rejection::{JsonRejection, PathRejection, QueryRejection},
+		Request,
+	},
+	http::{header, HeaderValue, StatusCode},
+	middleware::Next,
+	response::{IntoResponse, IntoResponseParts, Response, ResponseParts},
+	Extension, Json,
 };
-
-use regex::Regex;
+use opentelemetry::trace::TraceContextExt;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use serde::Serialize;
 use utoipa::ToSchema;
 
-use crate::domain::entity::todo::Todo;
+use crate::domain::{
+	entity::{
+		tag::Tag,
+		todo::{Todo, TodoTree},
+		todo_list::TodoList,
+	},
+	exception::{IdempotencyException, TagException, TodoException, TodoListException},
+};
 
 pub enum ApiResponseType {
 	SuccessWithData,
@@ -57,9 +71,11 @@ where
 	}
 	#[allow(dead_code)]
 	pub fn error(message: &'static str) -> Self {
-		let err = anyhow::format_err!(message);
-
-		Self::Error(err.into())
+		Self::Error(ApiResponseError::new(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			"internal-error",
+			message,
+		))
 	}
 }
 
@@ -89,16 +105,33 @@ where
 	}
 }
 #[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ListInformations {
 	pub total: i64,
+	/// Cursor to pass to fetch the next page, absent on the last page
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub next_cursor: Option<String>,
 }
 #[derive(Serialize, ToSchema)]
 pub struct TodoParams {}
 
+/// Result of an operation of a batch, in the order of the operations.
+#[derive(Serialize, ToSchema)]
+pub struct BatchOperationResult {
+	/// Status the operation would have answered on its own
+	#[schema(example = 200)]
+	pub status: u16,
+	/// Todo the operation applied to, absent when it failed
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub todo: Option<Todo>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<ProblemDetails>,
+}
+
 #[derive(Serialize, ToSchema)]
 // it's not possible to use a generic type as a field in a struct with utoipa
 // it's not ideal but we can use aliases to workaround this limitation
-#[aliases(ApiResponseTodo = ApiResponseObject<Todo, TodoParams>, ApiResponseListTodos = ApiResponseObject<Vec<Todo>, ListInformations>)]
+#[aliases(ApiResponseTodo = ApiResponseObject<Todo, TodoParams>, ApiResponseTodoTree = ApiResponseObject<TodoTree, TodoParams>, ApiResponseListTodos = ApiResponseObject<Vec<Todo>, ListInformations>, ApiResponseTag = ApiResponseObject<Tag, TodoParams>, ApiResponseListTags = ApiResponseObject<Vec<Tag>, ListInformations>, ApiResponseTodoList = ApiResponseObject<TodoList, TodoParams>, ApiResponseListTodoLists = ApiResponseObject<Vec<TodoList>, ListInformations>, ApiResponseBatchTodos = ApiResponseObject<Vec<BatchOperationResult>, TodoParams>)]
 pub struct ApiResponseObject<T, I>
 where
 	T: Serialize,
@@ -109,59 +142,430 @@ where
 	data: T,
 }
 
-pub type ApiResponse<T, I> = Result<ApiResponseData<T, I>, ApiResponseError>;
+/// Page of resources of the v2 API, sent as is without envelope.
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[aliases(TodoPage = Page<Todo>, TodoListPage = Page<TodoList>, TagPage = Page<Tag>)]
+pub struct Page<T>
+where
+	T: Serialize,
+{
+	pub items: Vec<T>,
+	/// Number of resources matching the query, across all pages
+	pub total: i64,
+	/// Cursor to pass to fetch the next page, `null` on the last page
+	pub next_cursor: Option<String>,
+}
 
-pub struct ApiResponseError(anyhow::Error);
+impl<T> Page<T>
+where
+	T: Serialize,
+{
+	/// Single page holding every resource
+	pub fn all(items: Vec<T>) -> Self {
+		Self {
+			total: items.len() as i64,
+			items,
+			next_cursor: None,
+		}
+	}
+}
 
 #[derive(Serialize, ToSchema)]
-pub struct ApiResponseErrorObject {
-	status: String,
-	error: String,
+pub struct TodoCount {
+	pub total: i64,
+}
+
+pub type ApiResponse<T, I> = Result<ApiResponseData<T, I>, ApiResponseError>;
+
+/// Response of the v2 API, the resource itself or a problem
+pub type ApiResult<T> = Result<T, ApiResponseError>;
+
+/// Response of a single todo, along with its version as `ETag`
+pub type ApiResponseWithETag<T, I> = Result<(ETag, ApiResponseData<T, I>), ApiResponseError>;
+
+/// Version of a todo, sent back in `If-Match` to write the todo only if unchanged.
+pub struct ETag(pub i32);
+
+impl IntoResponseParts for ETag {
+	type Error = Infallible;
+
+	fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+		res.headers_mut().insert(
+			header::ETAG,
+			HeaderValue::from_str(&format!("\"{}\"", self.0)).unwrap(),
+		);
+
+		Ok(res)
+	}
+}
+
+/// Error of an API call, rendered as an RFC 7807 problem.
+pub struct ApiResponseError {
+	status: StatusCode,
+	problem_type: &'static str,
+	detail: String,
+}
+
+/// Slugs and descriptions of the problem types, served under `/problems/<slug>`.
+pub const PROBLEM_TYPES: &[(&str, &str)] = &[
+	("internal-error", "The server failed to handle the request."),
+	(
+		"storage-error",
+		"The storage of the todos failed, the request may be retried.",
+	),
+	(
+		"invalid-body",
+		"The request body is not valid JSON or misses required fields.",
+	),
+	(
+		"invalid-query",
+		"The query string has an unknown value or a value of the wrong type.",
+	),
+	(
+		"payload-too-large",
+		"The request body is larger than the server accepts.",
+	),
+	(
+		"unsupported-media-type",
+		"The Content-Type of the request body is not one the endpoint accepts.",
+	),
+	(
+		"cache-disabled",
+		"The todo cache is not enabled on this server.",
+	),
+	(
+		"todo-not-found",
+		"No todo has the id, or it is in the trash.",
+	),
+	(
+		"todo-already-exists",
+		"A todo with the same id already exists.",
+	),
+	(
+		"todo-conflict",
+		"The todo was modified by another request meanwhile, read it again before retrying.",
+	),
+	(
+		"todo-version-mismatch",
+		"The todo version does not match the If-Match header.",
+	),
+	(
+		"invalid-todo-status",
+		"The status is not one of `all`, `pending` or `done`.",
+	),
+	(
+		"invalid-todo-query",
+		"A filter, sort or cursor of the todo query is invalid.",
+	),
+	(
+		"invalid-recurrence",
+		"The recurrence is not a supported RRULE nor a shortcut.",
+	),
+	(
+		"invalid-todo-move",
+		"The neighbours of the move are missing, not in order or not in the list and under the parent of the todo.",
+	),
+	(
+		"invalid-batch",
+		"The batch is empty, too large or has an invalid operation.",
+	),
+	(
+		"invalid-todo-patch",
+		"The patch is malformed or does not apply to the todo.",
+	),
+	(
+		"read-only-todo-field",
+		"The patch changes a field that can not be changed.",
+	),
+	("tag-not-found", "No tag has the id."),
+	(
+		"tag-already-exists",
+		"A tag with the same name already exists.",
+	),
+	("invalid-tag-name", "The tag name is empty."),
+	("list-not-found", "No list has the id."),
+	("invalid-list-name", "The list name is empty."),
+	(
+		"invalid-idempotency-key",
+		"The Idempotency-Key header is empty, too long or not visible ASCII.",
+	),
+	(
+		"idempotent-request-in-progress",
+		"A request with the same Idempotency-Key is still being handled.",
+	),
+	(
+		"idempotency-key-reused",
+		"The Idempotency-Key was already used for a different request.",
+	),
+];
+
+impl ApiResponseError {
+	/// `problem_type` is the slug of the problem, one of `PROBLEM_TYPES`.
+	pub fn new(status: StatusCode, problem_type: &'static str, detail: impl Into<String>) -> Self {
+		debug_assert!(
+			PROBLEM_TYPES.iter().any(|(slug, _)| *slug == problem_type),
+			"undocumented problem type {}",
+			problem_type
+		);
+
+		Self {
+			status,
+			problem_type,
+			detail: detail.into(),
+		}
+	}
+}
+
+/// HTTP status and problem type slug of a domain error.
+pub trait ApiProblem: std::fmt::Display {
+	fn status(&self) -> StatusCode;
+	fn problem_type(&self) -> &'static str;
+}
+
+impl ApiProblem for TodoException {
+	fn status(&self) -> StatusCode {
+		match self {
+			TodoException::InvalidStatus
+			| TodoException::InvalidQuery
+			| TodoException::InvalidRecurrence
+			| TodoException::InvalidMove
+			| TodoException::InvalidBatch
+			| TodoException::InvalidPatch
+			| TodoException::ReadOnlyField => StatusCode::BAD_REQUEST,
+			TodoException::AlreadyExists | TodoException::Conflict => StatusCode::CONFLICT,
+			TodoException::NotFound => StatusCode::UNPROCESSABLE_ENTITY,
+			TodoException::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+			TodoException::Storage => StatusCode::INTERNAL_SERVER_ERROR,
+		}
+	}
+
+	fn problem_type(&self) -> &'static str {
+		match self {
+			TodoException::AlreadyExists => "todo-already-exists",
+			TodoException::InvalidStatus => "invalid-todo-status",
+			TodoException::InvalidQuery => "invalid-todo-query",
+			TodoException::InvalidRecurrence => "invalid-recurrence",
+			TodoException::InvalidMove => "invalid-todo-move",
+			TodoException::InvalidBatch => "invalid-batch",
+			TodoException::InvalidPatch => "invalid-todo-patch",
+			TodoException::ReadOnlyField => "read-only-todo-field",
+			TodoException::NotFound => "todo-not-found",
+			TodoException::Conflict => "todo-conflict",
+			TodoException::PreconditionFailed => "todo-version-mismatch",
+			TodoException::Storage => "storage-error",
+		}
+	}
+}
+
+impl ApiProblem for TagException {
+	fn status(&self) -> StatusCode {
+		match self {
+			TagException::InvalidName => StatusCode::BAD_REQUEST,
+			TagException::AlreadyExists => StatusCode::CONFLICT,
+			TagException::NotFound => StatusCode::UNPROCESSABLE_ENTITY,
+			TagException::Storage => StatusCode::INTERNAL_SERVER_ERROR,
+		}
+	}
+
+	fn problem_type(&self) -> &'static str {
+		match self {
+			TagException::InvalidName => "invalid-tag-name",
+			TagException::AlreadyExists => "tag-already-exists",
+			TagException::NotFound => "tag-not-found",
+			TagException::Storage => "storage-error",
+		}
+	}
+}
+
+impl ApiProblem for TodoListException {
+	fn status(&self) -> StatusCode {
+		match self {
+			TodoListException::InvalidName => StatusCode::BAD_REQUEST,
+			TodoListException::NotFound => StatusCode::UNPROCESSABLE_ENTITY,
+			TodoListException::Storage => StatusCode::INTERNAL_SERVER_ERROR,
+		}
+	}
+
+	fn problem_type(&self) -> &'static str {
+		match self {
+			TodoListException::InvalidName => "invalid-list-name",
+			TodoListException::NotFound => "list-not-found",
+			TodoListException::Storage => "storage-error",
+		}
+	}
+}
+
+impl ApiProblem for IdempotencyException {
+	fn status(&self) -> StatusCode {
+		match self {
+			IdempotencyException::InvalidKey => StatusCode::BAD_REQUEST,
+			IdempotencyException::InProgress => StatusCode::CONFLICT,
+			IdempotencyException::KeyReused => StatusCode::UNPROCESSABLE_ENTITY,
+			IdempotencyException::Storage => StatusCode::INTERNAL_SERVER_ERROR,
+		}
+	}
+
+	fn problem_type(&self) -> &'static str {
+		match self {
+			IdempotencyException::InvalidKey => "invalid-idempotency-key",
+			IdempotencyException::InProgress => "idempotent-request-in-progress",
+			IdempotencyException::KeyReused => "idempotency-key-reused",
+			IdempotencyException::Storage => "storage-error",
+		}
+	}
+}
+
+impl From<JsonRejection> for ApiResponseError {
+	fn from(rejection: JsonRejection) -> Self {
+		Self::new(rejection.status(), "invalid-body", rejection.body_text())
+	}
+}
+
+impl From<QueryRejection> for ApiResponseError {
+	fn from(rejection: QueryRejection) -> Self {
+		Self::new(rejection.status(), "invalid-query", rejection.body_text())
+	}
+}
+
+impl From<PathRejection> for ApiResponseError {
+	fn from(rejection: PathRejection) -> Self {
+		Self::new(rejection.status(), "invalid-path", rejection.body_text())
+	}
 }
 
 impl<E> From<E> for ApiResponseError
 where
-	E: Into<anyhow::Error>,
+	E: ApiProblem,
 {
 	fn from(err: E) -> Self {
-		Self(err.into())
+		Self::new(err.status(), err.problem_type(), err.to_string())
 	}
 }
 
+/// RFC 7807 problem details, sent as `application/problem+json`.
+#[derive(Serialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProblemDetails {
+	/// Reference to the documentation of the problem type
+	#[serde(rename = "type")]
+	#[schema(example = "/problems/todo-not-found")]
+	pub problem_type: String,
+	/// Reason phrase of the status
+	#[schema(example = "Unprocessable Entity")]
+	pub title: String,
+	pub status: u16,
+	#[schema(example = "Todo not exists")]
+	pub detail: String,
+	/// Path of the request that failed
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[schema(example = "/api/todos/V1StGXR8_Z5jdHi6B-myT")]
+	pub instance: Option<String>,
+	/// OpenTelemetry trace of the request, when tracing is enabled
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub trace_id: Option<String>,
+}
+
+impl ProblemDetails {
+	/// Turns a missing resource answered with 422, as v1 does, into a 404 problem.
+	pub fn into_not_found(self) -> Self {
+		if self.status != StatusCode::UNPROCESSABLE_ENTITY.as_u16()
+			|| !self.problem_type.ends_with("-not-found")
+		{
+			return self;
+		}
+
+		Self {
+			title: StatusCode::NOT_FOUND.canonical_reason().unwrap_or_default().to_string(),
+			status: StatusCode::NOT_FOUND.as_u16(),
+			..self
+		}
+	}
+
+	fn into_response(self) -> Response {
+		let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+		(
+			status,
+			[(header::CONTENT_TYPE, PROBLEM_JSON)],
+			Extension(self.clone()),
+			Json(self),
+		)
+			.into_response()
+	}
+}
+
+const PROBLEM_JSON: &str = "application/problem+json";
+
 impl IntoResponse for ApiResponseError {
 	fn into_response(self) -> Response {
-		let msg = self.0.to_string();
-
-		// parse msg to get status code and error message
-		// "[409] Todo already exists"
-		let re = Regex::new(r"^\[(\d+)\] (.+)$").unwrap();
-
-		match re.captures(&msg) {
-			Some(captures) => {
-				let status_code = match captures.get(1).unwrap().as_str().parse::<u16>() {
-					Ok(status_code) => StatusCode::from_u16(status_code)
-						.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
-					Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
-				};
-
-				let error = captures.get(2).unwrap().as_str();
-
-				(
-					status_code,
-					Json(ApiResponseErrorObject {
-						status: status_code.to_string(),
-						error: error.to_string(),
-					}),
-				)
-			},
-			None => (
-				StatusCode::INTERNAL_SERVER_ERROR,
-				Json(ApiResponseErrorObject {
-					status: StatusCode::INTERNAL_SERVER_ERROR.to_string(),
-					error: msg,
-				}),
-			),
+		if self.status.is_server_error() {
+			tracing::error!("{}: {}", self.problem_type, self.detail);
+		}
+
+		ProblemDetails::from(self).into_response()
+	}
+}
+
+impl From<ApiResponseError> for ProblemDetails {
+	fn from(err: ApiResponseError) -> Self {
+		Self {
+			problem_type: format!("/problems/{}", err.problem_type),
+			title: err.status.canonical_reason().unwrap_or_default().to_string(),
+			status: err.status.as_u16(),
+			detail: err.detail,
+			instance: None,
+			trace_id: None,
 		}
-		.into_response()
 	}
 }
+
+/// Completes the problems of the responses with the request path and trace id, which
+/// the handlers do not know of.
+pub async fn complete_problem(request: Request, next: Next) -> Response {
+	let instance = request.uri().path().to_string();
+
+	let mut response = next.run(request).await;
+
+	let Some(problem) = response.extensions_mut().remove::<ProblemDetails>() else {
+		return response;
+	};
+
+	// keeps the headers set along the problem, such as the deprecation ones
+	let (mut parts, _) = response.into_parts();
+	parts.headers.remove(header::CONTENT_LENGTH);
+
+	let mut response = ProblemDetails {
+		instance: Some(instance),
+		trace_id: current_trace_id(),
+		..problem
+	}
+	.into_response();
+	response.headers_mut().extend(parts.headers);
+
+	response
+}
+
+/// Answers the missing resources with 404, for the v2 API whose paths name them.
+pub async fn not_found_problem(request: Request, next: Next) -> Response {
+	let mut response = next.run(request).await;
+
+	let Some(problem) = response.extensions_mut().remove::<ProblemDetails>() else {
+		return response;
+	};
+
+	let (mut parts, _) = response.into_parts();
+	parts.headers.remove(header::CONTENT_LENGTH);
+
+	let mut response = problem.into_not_found().into_response();
+	response.headers_mut().extend(parts.headers);
+
+	response
+}
+
+fn current_trace_id() -> Option<String> {
+	let context = tracing::Span::current().context();
+	let span_context = context.span().span_context().clone();
+
+	span_context.is_valid().then(|| span_context.trace_id().to_string())
+}
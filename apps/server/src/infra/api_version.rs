@@ -0,0 +1,46 @@
+use axum::{
+	extract::{Request, State},
+	http::{header, HeaderName, HeaderValue},
+	middleware::Next,
+	response::Response,
+};
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::server::AppState;
+
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Successor of the deprecated v1 API, advertised in the `Link` header.
+const V2_LINK: &str = "</api/v2>; rel=\"successor-version\"";
+
+/// Day `/api/v2` shipped, from when `/api` is deprecated.
+fn v1_deprecated_at() -> DateTime<Utc> {
+	Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap()
+}
+
+/// Marks the responses of the v1 API as deprecated (RFC 9745) in favour of `/api/v2`,
+/// along with the date it stops being served (RFC 8594) once one is set.
+pub async fn deprecated_v1(
+	State(app_state): State<AppState>,
+	request: Request,
+	next: Next,
+) -> Response {
+	let mut response = next.run(request).await;
+	let headers = response.headers_mut();
+
+	headers.insert(
+		DEPRECATION,
+		HeaderValue::from_str(&format!("@{}", v1_deprecated_at().timestamp())).unwrap(),
+	);
+	headers.append(header::LINK, HeaderValue::from_static(V2_LINK));
+
+	if let Some(sunset) = app_state.api_v1_sunset {
+		headers.insert(
+			SUNSET,
+			HeaderValue::from_str(&sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string()).unwrap(),
+		);
+	}
+
+	response
+}
@@ -1,4 +1,5 @@
 use axum::{
+	extract::{Path, State},
 	http::{header, StatusCode, Uri},
 	response::IntoResponse,
 	Json,
@@ -6,6 +7,12 @@ use axum::{
 use rust_embed::RustEmbed;
 use tracing::instrument;
 
+use crate::infra::{
+	api_response::{ApiResponseError, PROBLEM_TYPES},
+	repository::todo_cached_repo::TodoCacheStats,
+	server::AppState,
+};
+
 #[utoipa::path(
 	tag = "Core",
 	get,
@@ -23,6 +30,55 @@ pub async fn health() -> impl IntoResponse {
 	(StatusCode::OK, Json(heatlh))
 }
 
+#[utoipa::path(
+	tag = "Core",
+	get,
+	path = "/api/cache",
+	responses(
+		(status = 200, description = "Hits and misses of the todo cache", body = TodoCacheStats),
+		(status = 404, description = "Todo cache disabled", body = ProblemDetails, content_type = "application/problem+json"),
+	)
+)]
+pub async fn cache_stats_ctrl(
+	State(app_state): State<AppState>,
+) -> Result<Json<TodoCacheStats>, ApiResponseError> {
+	match &app_state.todo_cache {
+		Some(cache) => Ok(Json(cache.stats())),
+		None => Err(ApiResponseError::new(
+			StatusCode::NOT_FOUND,
+			"cache-disabled",
+			"Todo cache is disabled",
+		)),
+	}
+}
+
+#[utoipa::path(
+	tag = "Core",
+	get,
+	path = "/problems/{problem_type}",
+	params(
+		("problem_type" = String, Path, description = "Slug of the problem type"),
+	),
+	responses(
+		(status = 200, description = "Description of the problem type", body = String, content_type = "text/plain"),
+		(status = 404, description = "Unknown problem type"),
+	)
+)]
+pub async fn problem_type_ctrl(Path(problem_type): Path<String>) -> impl IntoResponse {
+	match PROBLEM_TYPES.iter().find(|(slug, _)| *slug == problem_type) {
+		Some((slug, description)) => (
+			StatusCode::OK,
+			[(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+			format!("{}\n\n{}\n", slug, description),
+		),
+		None => (
+			StatusCode::NOT_FOUND,
+			[(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+			"404 Not Found".to_string(),
+		),
+	}
+}
+
 #[derive(RustEmbed)]
 #[folder = "assets/"]
 #[include = "*.css"]
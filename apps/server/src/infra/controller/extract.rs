@@ -0,0 +1,66 @@
+//! Extractors of the API handlers, rejecting the requests they can not parse with a
+//! problem instead of the plain text of axum.
+
+use axum::{
+	async_trait,
+	extract::{FromRequest, FromRequestParts, Path, Query, Request},
+	http::request::Parts,
+	Json,
+};
+use serde::de::DeserializeOwned;
+
+use crate::infra::api_response::ApiResponseError;
+
+/// JSON body, rejected as an `invalid-body` problem.
+pub struct JsonBody<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+	T: DeserializeOwned,
+	S: Send + Sync,
+{
+	type Rejection = ApiResponseError;
+
+	async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+		let Json(value) = Json::<T>::from_request(request, state).await?;
+
+		Ok(Self(value))
+	}
+}
+
+/// Query string, rejected as an `invalid-query` problem.
+pub struct QueryParams<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for QueryParams<T>
+where
+	T: DeserializeOwned,
+	S: Send + Sync,
+{
+	type Rejection = ApiResponseError;
+
+	async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+		let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+
+		Ok(Self(value))
+	}
+}
+
+/// Path parameters, rejected as an `invalid-path` problem.
+pub struct PathParam<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for PathParam<T>
+where
+	T: DeserializeOwned + Send,
+	S: Send + Sync,
+{
+	type Rejection = ApiResponseError;
+
+	async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+		let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+
+		Ok(Self(value))
+	}
+}
@@ -1,15 +1,65 @@
 use std::collections::HashMap;
 
-use axum::http::HeaderMap;
+use axum::http::{header, HeaderMap};
 use url::Url;
 
-pub fn extract_status_from_header(headers: HeaderMap) -> Option<String> {
-	let current_url = headers.get("hx-current-url").or(headers.get("referer"));
+pub fn extract_status_from_header(headers: &HeaderMap) -> Option<String> {
+	extract_query_param_from_header(headers, "status")
+}
+
+pub fn extract_due_from_header(headers: &HeaderMap) -> Option<String> {
+	extract_query_param_from_header(headers, "due")
+}
+
+pub fn extract_priority_from_header(headers: &HeaderMap) -> Option<String> {
+	extract_query_param_from_header(headers, "priority")
+}
+
+pub fn extract_tag_from_header(headers: &HeaderMap) -> Option<String> {
+	extract_query_param_from_header(headers, "tag")
+}
+
+pub fn extract_list_from_header(headers: &HeaderMap) -> Option<String> {
+	extract_query_param_from_header(headers, "list")
+}
+
+pub fn extract_order_from_header(headers: &HeaderMap) -> Option<String> {
+	extract_query_param_from_header(headers, "order")
+}
+
+/// Versions listed by the `If-Match` header, `None` when it is absent or `*`. Weak tags
+/// never match and are left out.
+pub fn extract_if_match_from_header(headers: &HeaderMap) -> Option<Vec<i32>> {
+	let if_match = headers.get(header::IF_MATCH)?.to_str().unwrap_or_default().trim();
+
+	if if_match == "*" {
+		return None;
+	}
+
+	Some(
+		if_match
+			.split(',')
+			.filter_map(|tag| tag.trim().strip_prefix('"')?.strip_suffix('"')?.parse().ok())
+			.collect(),
+	)
+}
+
+/// Media type of the `Content-Type` header, lowercased and without its parameters.
+pub fn extract_content_type_from_header(headers: &HeaderMap) -> Option<String> {
+	let content_type = headers.get(header::CONTENT_TYPE)?.to_str().ok()?;
+
+	content_type
+		.split(';')
+		.next()
+		.map(|media_type| media_type.trim().to_lowercase())
+}
+
+/// Query parameter of the page the request comes from, `None` when its URL is invalid.
+fn extract_query_param_from_header(headers: &HeaderMap, param: &str) -> Option<String> {
+	let current_url = headers.get("hx-current-url").or(headers.get("referer"))?;
+	let url = Url::parse(current_url.to_str().ok()?).ok()?;
 
-	current_url.and_then(|url| {
-		let hash_query: HashMap<_, _> =
-			Url::parse(url.to_str().unwrap()).unwrap().query_pairs().into_owned().collect();
+	let hash_query: HashMap<_, _> = url.query_pairs().into_owned().collect();
 
-		hash_query.get("status").map(|s| s.to_string())
-	})
+	hash_query.get(param).map(|s| s.to_string())
 }
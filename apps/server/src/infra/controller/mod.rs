@@ -1,5 +1,10 @@
 pub mod catchers_ctrl;
 pub mod common_ctrl;
+pub mod extract;
 pub mod helper;
+pub mod tag_ctrl;
 pub mod todo_ctrl;
+pub mod todo_list_ctrl;
 pub mod todos_views_ctrl;
+pub mod trash_ctrl;
+pub mod v2;
@@ -0,0 +1,125 @@
+use axum::{extract::State, http::StatusCode};
+
+use crate::{
+	domain::entity::tag::Tag,
+	infra::{
+		api_response::{ApiResponse, ApiResponseData, ListInformations, TodoParams},
+		controller::extract::{JsonBody, PathParam},
+		server::AppState,
+	},
+	usecase::{
+		create_tag_usecase::{self, CreateTagParams},
+		delete_tag_usecase, get_all_tags_usecase,
+		rename_tag_usecase::{self, RenameTagParams},
+	},
+};
+
+#[utoipa::path(
+	tag = "Tag",
+	post,
+	path = "/api/tags",
+	request_body = CreateTagParams,
+	responses(
+		(status = 201, description = "Tag created successfully", body = ApiResponseTag),
+		(status = 400, description = "Tag name is empty", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Tag already exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn create_tag_ctrl(
+	State(app_state): State<AppState>,
+	JsonBody(params): JsonBody<CreateTagParams>,
+) -> ApiResponse<Tag, TodoParams> {
+	let create_tag_usecase = create_tag_usecase::CreateTagUsecase::new(&app_state.tag_repo);
+
+	let tag = create_tag_usecase.exec(params).await?;
+
+	Ok(ApiResponseData::success_with_data(
+		tag,
+		None,
+		StatusCode::CREATED,
+	))
+}
+
+#[utoipa::path(
+	tag = "Tag",
+	get,
+	path = "/api/tags",
+	responses(
+		(status = 200, description = "Tags retrieved successfully", body = ApiResponseListTags),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_all_tags_ctrl(
+	State(app_state): State<AppState>,
+) -> ApiResponse<Vec<Tag>, ListInformations> {
+	let get_all_tags_usecase = get_all_tags_usecase::GetAllTagsUsecase::new(&app_state.tag_repo);
+
+	let tags = get_all_tags_usecase.exec().await?;
+	let total = tags.len() as i64;
+
+	Ok(ApiResponseData::success_with_data(
+		tags,
+		Some(ListInformations {
+			total,
+			next_cursor: None,
+		}),
+		StatusCode::OK,
+	))
+}
+
+#[utoipa::path(
+	tag = "Tag",
+	patch,
+	path = "/api/tags/{id}",
+	params(
+		("id" = String, Path, description = "Tag id"),
+	),
+	request_body = RenameTagParams,
+	responses(
+		(status = 200, description = "Tag renamed successfully", body = ApiResponseTag),
+		(status = 400, description = "Tag name is empty", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Tag already exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "Tag not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn rename_tag_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	JsonBody(params): JsonBody<RenameTagParams>,
+) -> ApiResponse<Tag, TodoParams> {
+	let rename_tag_usecase = rename_tag_usecase::RenameTagUsecase::new(&app_state.tag_repo);
+
+	let tag = rename_tag_usecase.exec(id, params).await?;
+
+	Ok(ApiResponseData::success_with_data(
+		tag,
+		None,
+		StatusCode::OK,
+	))
+}
+
+#[utoipa::path(
+	tag = "Tag",
+	delete,
+	path = "/api/tags/{id}",
+	params(
+		("id" = String, Path, description = "Tag id"),
+	),
+	responses(
+		(status = 204, description = "Tag deleted successfully"),
+		(status = 422, description = "Tag not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn delete_tag_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResponse<(), ()> {
+	let delete_tag_usecase = delete_tag_usecase::DeleteTagUsecase::new(&app_state.tag_repo);
+
+	delete_tag_usecase.exec(id).await?;
+
+	Ok(ApiResponseData::status_code(StatusCode::NO_CONTENT))
+}
@@ -1,5 +1,6 @@
 use axum::{
-	extract::{Path, Query, State},
+	body::Bytes,
+	extract::State,
 	http::{HeaderMap, StatusCode},
 	Json,
 };
@@ -7,18 +8,38 @@ use serde::Deserialize;
 use utoipa::IntoParams;
 
 use crate::{
-	domain::entity::todo::Todo,
+	domain::{
+		entity::todo::{Todo, TodoCan, TodoOperation, TodoTree, TodoView},
+		exception::TodoException,
+	},
 	infra::{
-		api_response::{ApiResponse, ApiResponseData, ListInformations, TodoParams},
+		api_response::{
+			ApiProblem, ApiResponse, ApiResponseData, ApiResponseError, ApiResponseWithETag,
+			BatchOperationResult, ETag, ListInformations, TodoParams,
+		},
+		controller::{
+			extract::{JsonBody, PathParam, QueryParams},
+			todos_views_ctrl::UpdateTodoTmpl,
+		},
 		server::AppState,
 	},
 	usecase::{
+		batch_todos_usecase::{self, BatchFailure, BatchOutcome, BatchTodosParams},
+		clear_completed_todos_usecase, create_subtask_usecase,
 		create_todo_usecase::{self, CreateTodoParams},
-		delete_todo_usecase, get_all_todos_usecase, mark_as_done_todo_usecase,
+		delete_todo_usecase,
+		get_all_todos_usecase::{self, TodoQueryParams},
+		get_todo_tree_usecase, mark_as_done_todo_usecase,
+		move_todo_usecase::{self, MoveTodoParams},
+		patch_todo_usecase::{self, TodoPatch},
+		update_todo_usecase::{self, UpdateTodoParams},
 	},
 };
 
-use super::helper::extract_status_from_header;
+use super::helper::{
+	extract_content_type_from_header, extract_due_from_header, extract_if_match_from_header,
+	extract_priority_from_header, extract_status_from_header, extract_tag_from_header,
+};
 
 #[utoipa::path(
 	tag = "Todo",
@@ -26,30 +47,116 @@ use super::helper::extract_status_from_header;
 	path = "/api/todos",
 	request_body = CreateTodoParams,
 	responses(
-		(status = 201, description = "Todo item created successfully", body = ApiResponseTodo),
-		(status = 409, description = "Todo already exists", body = ApiResponseErrorObject),
-		(status = 500, description = "Internal Server Error", body = ApiResponseErrorObject)
+		(status = 201, description = "Todo item created successfully", body = ApiResponseTodo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 400, description = "Invalid recurrence rule", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Todo already exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
 	)
 )]
 pub async fn create_todo_ctrl(
 	State(app_state): State<AppState>,
-	Json(params): Json<CreateTodoParams>,
-) -> ApiResponse<Todo, TodoParams> {
+	JsonBody(params): JsonBody<CreateTodoParams>,
+) -> ApiResponseWithETag<Todo, TodoParams> {
 	let create_todo_usecase = create_todo_usecase::CreateTodoUsecase::new(&app_state.todo_repo);
 
-	let todo = create_todo_usecase.exec(params).await.unwrap();
+	let todo = create_todo_usecase.exec(params).await?;
 
-	Ok(ApiResponseData::success_with_data(
-		todo,
-		None,
-		StatusCode::CREATED,
+	broadcast_outcome(&app_state, BatchOutcome::Created(todo.clone()));
+
+	Ok((
+		ETag(todo.version),
+		ApiResponseData::success_with_data(todo, None, StatusCode::CREATED),
+	))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	post,
+	path = "/api/todos/{id}/subtasks",
+	params(
+		("id" = String, Path, description = "Parent todo item id"),
+	),
+	request_body = CreateTodoParams,
+	responses(
+		(status = 201, description = "Subtask created successfully", body = ApiResponseTodo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 400, description = "Invalid recurrence rule", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "Parent todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn create_subtask_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	JsonBody(params): JsonBody<CreateTodoParams>,
+) -> ApiResponseWithETag<Todo, TodoParams> {
+	let create_subtask_usecase =
+		create_subtask_usecase::CreateSubtaskUsecase::new(&app_state.todo_repo);
+
+	let todo = create_subtask_usecase.exec(id, params).await?;
+
+	broadcast_outcome(&app_state, BatchOutcome::Created(todo.clone()));
+
+	Ok((
+		ETag(todo.version),
+		ApiResponseData::success_with_data(todo, None, StatusCode::CREATED),
 	))
 }
 
 #[derive(Deserialize, IntoParams, Clone, Debug)]
 #[into_params(parameter_in = Query)]
 pub struct GetAllTodosQuery {
+	/// One of `pending`, `done` or `all` (default)
 	pub status: Option<String>,
+	/// One of `overdue`, `today` or `upcoming`
+	pub due: Option<String>,
+	/// One of `none`, `low`, `medium`, `high` or `urgent`
+	pub priority: Option<String>,
+	/// Tag name
+	pub tag: Option<String>,
+	/// Created at or after this date, RFC 3339 or `YYYY-MM-DD`
+	pub created_after: Option<String>,
+	/// Created before this date, RFC 3339 or `YYYY-MM-DD`
+	pub created_before: Option<String>,
+	/// Done at or after this date, RFC 3339 or `YYYY-MM-DD`
+	pub done_after: Option<String>,
+	/// Done before this date, RFC 3339 or `YYYY-MM-DD`
+	pub done_before: Option<String>,
+	/// Case-insensitive text the description contains
+	pub contains: Option<String>,
+	/// Full-text search on the description, the todos are then ranked by relevance and
+	/// `order` is ignored
+	pub q: Option<String>,
+	/// `created_at` (default), `priority` or `manual`
+	pub order: Option<String>,
+	/// `asc` or `desc`, defaults to `asc` for the manual order and `desc` otherwise
+	pub direction: Option<String>,
+	/// `nextCursor` of the previous page
+	pub cursor: Option<String>,
+	/// Page size, 200 at most. 50 by default on `/api/v2`, the v1 routes returning every todo
+	/// after the cursor without it
+	pub limit: Option<i64>,
+}
+
+impl GetAllTodosQuery {
+	pub fn params(&self, list_id: Option<String>) -> TodoQueryParams {
+		TodoQueryParams {
+			status: self.status.clone(),
+			due: self.due.clone(),
+			priority: self.priority.clone(),
+			tag: self.tag.clone(),
+			list_id,
+			created_after: self.created_after.clone(),
+			created_before: self.created_before.clone(),
+			done_after: self.done_after.clone(),
+			done_before: self.done_before.clone(),
+			contains: self.contains.clone(),
+			search: self.q.clone(),
+			order: self.order.clone(),
+			direction: self.direction.clone(),
+			cursor: self.cursor.clone(),
+			limit: self.limit,
+		}
+	}
 }
 
 #[utoipa::path(
@@ -59,77 +166,259 @@ pub struct GetAllTodosQuery {
 	params(GetAllTodosQuery),
 	responses(
 		(status = 200, description = "Todo items retrieved successfully", body = ApiResponseListTodos),
-		(status = 500, description = "Internal Server Error", body = ApiResponseErrorObject)
+		(status = 400, description = "Invalid todo status or query", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
 	)
 )]
 pub async fn get_all_todos_ctrl(
 	State(app_state): State<AppState>,
-	query: Query<GetAllTodosQuery>,
+	QueryParams(query): QueryParams<GetAllTodosQuery>,
 ) -> ApiResponse<Vec<Todo>, ListInformations> {
 	let get_all_todos_usecase =
 		get_all_todos_usecase::GetAllTodosUsecase::new(&app_state.todo_repo);
 	let count_todos_usecase =
 		crate::usecase::count_todos_usecase::CountTodosUsecase::new(&app_state.todo_repo);
 
-	let count = count_todos_usecase.exec(query.status.as_ref()).await;
-	let todos = get_all_todos_usecase.exec(query.status.as_ref()).await?;
+	let params = query.params(None);
+
+	let count = count_todos_usecase.exec(&params).await?;
+	let page = get_all_todos_usecase.exec(&params, None).await?;
 
 	Ok(ApiResponseData::success_with_data(
-		todos,
-		Some(ListInformations { total: count }),
+		page.todos,
+		Some(ListInformations {
+			total: count,
+			next_cursor: page.next_cursor,
+		}),
 		StatusCode::OK,
 	))
 }
 
+#[utoipa::path(
+	tag = "Todo",
+	patch,
+	path = "/api/todos/{id}",
+	params(
+		("id" = String, Path, description = "Todo item id"),
+		("If-Match" = Option<String>, Header, description = "ETag the todo item must match"),
+	),
+	request_body(content = UpdateTodoParams, description = "Changes of the todo item, or a `application/merge-patch+json` or `application/json-patch+json` patch of its `description` and `done` fields"),
+	responses(
+		(status = 200, description = "Todo item updated successfully", body = ApiResponseTodo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 400, description = "Invalid recurrence rule or patch", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Todo item was modified meanwhile", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 412, description = "Todo item version does not match If-Match", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 415, description = "Unsupported content type", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "Todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn update_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	headers: HeaderMap,
+	body: Bytes,
+) -> ApiResponseWithETag<Todo, TodoParams> {
+	let todo = update_todo(&app_state, id, &headers, body).await?;
+
+	Ok((
+		ETag(todo.version),
+		ApiResponseData::success_with_data(todo, None, StatusCode::OK),
+	))
+}
+
+/// Updates the todo from changes, a merge patch or a JSON patch, told apart by the
+/// `Content-Type` of the body, then sends it to the views.
+pub async fn update_todo(
+	app_state: &AppState,
+	id: String,
+	headers: &HeaderMap,
+	body: Bytes,
+) -> Result<Todo, ApiResponseError> {
+	let if_match = extract_if_match_from_header(headers);
+
+	let todo = match extract_content_type_from_header(headers).as_deref() {
+		Some("application/json") => {
+			let Json(params) = Json::<UpdateTodoParams>::from_bytes(&body).map_err(ApiResponseError::from)?;
+
+			update_todo_usecase::UpdateTodoUsecase::new(&app_state.todo_repo)
+				.exec(id, params, if_match.as_deref())
+				.await?
+		},
+		Some("application/merge-patch+json") => {
+			let patch = serde_json::from_slice(&body).map_err(|_| TodoException::InvalidPatch)?;
+
+			patch_todo_usecase::PatchTodoUsecase::new(&app_state.todo_repo)
+				.exec(id, TodoPatch::Merge(patch), if_match.as_deref())
+				.await?
+		},
+		Some("application/json-patch+json") => {
+			let patch = serde_json::from_slice(&body).map_err(|_| TodoException::InvalidPatch)?;
+
+			patch_todo_usecase::PatchTodoUsecase::new(&app_state.todo_repo)
+				.exec(id, TodoPatch::Json(patch), if_match.as_deref())
+				.await?
+		},
+		_ => {
+			return Err(ApiResponseError::new(
+				StatusCode::UNSUPPORTED_MEDIA_TYPE,
+				"unsupported-media-type",
+				"Expected application/json, application/merge-patch+json or application/json-patch+json",
+			))
+		},
+	};
+
+	broadcast_outcome(app_state, BatchOutcome::Updated(todo.clone()));
+
+	Ok(todo)
+}
+
 #[utoipa::path(
 	tag = "Todo",
 	delete,
 	path = "/api/todos/{id}",
 	params(
 		("id" = String, Path, description = "Todo item id"),
+		("If-Match" = Option<String>, Header, description = "ETag the todo item must match"),
 	),
 	responses(
-		(status = 204, description = "Todo item deleted successfully"),
-		(status = 500, description = "Internal Server Error", body = ApiResponseErrorObject)
+		(status = 204, description = "Todo item and its subtasks moved to the trash successfully"),
+		(status = 412, description = "Todo item version does not match If-Match", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "Todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
 	)
 )]
 pub async fn delete_todo_ctrl(
 	State(app_state): State<AppState>,
-	Path(id): Path<String>,
+	PathParam(id): PathParam<String>,
+	headers: HeaderMap,
 ) -> ApiResponse<(), ()> {
 	let delete_todo_usecase = delete_todo_usecase::DeleteTodoUsecase::new(&app_state.todo_repo);
 
-	delete_todo_usecase.exec(id).await?;
+	let if_match = extract_if_match_from_header(&headers);
+	let output = delete_todo_usecase.exec(id, if_match.as_deref()).await?;
+
+	broadcast_outcome(&app_state, BatchOutcome::Deleted(output));
 
 	Ok(ApiResponseData::status_code(StatusCode::NO_CONTENT))
 }
 
+#[derive(Deserialize, IntoParams, Clone, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteManyTodosQuery {
+	/// Only `done` is supported
+	pub status: Option<String>,
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	delete,
+	path = "/api/todos",
+	params(DeleteManyTodosQuery),
+	responses(
+		(status = 200, description = "Completed todo items moved to the trash successfully", body = ApiResponseListTodos),
+		(status = 400, description = "Invalid todo status", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn delete_many_todos_ctrl(
+	State(app_state): State<AppState>,
+	QueryParams(query): QueryParams<DeleteManyTodosQuery>,
+) -> ApiResponse<Vec<Todo>, ListInformations> {
+	if query.status.as_deref() != Some("done") {
+		return Err(TodoException::InvalidStatus.into());
+	}
+
+	let clear_completed_todos_usecase =
+		clear_completed_todos_usecase::ClearCompletedTodosUsecase::new(&app_state.todo_repo);
+
+	let todos = clear_completed_todos_usecase.exec(None).await?;
+
+	for todo in todos.iter() {
+		app_state.broadcast_update_to_view(UpdateTodoTmpl {
+			todo: TodoView::new(todo.clone(), TodoOperation::Delete, TodoCan::Read),
+		});
+	}
+
+	let total = todos.len() as i64;
+
+	Ok(ApiResponseData::success_with_data(
+		todos,
+		Some(ListInformations {
+			total,
+			next_cursor: None,
+		}),
+		StatusCode::OK,
+	))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	get,
+	path = "/api/todos/{id}",
+	params(
+		("id" = String, Path, description = "Todo item id"),
+	),
+	responses(
+		(status = 200, description = "Todo item retrieved with its subtasks successfully", body = ApiResponseTodoTree, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 422, description = "Todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResponseWithETag<TodoTree, TodoParams> {
+	let get_todo_tree_usecase =
+		get_todo_tree_usecase::GetTodoTreeUsecase::new(&app_state.todo_repo);
+
+	let tree = get_todo_tree_usecase.exec(id).await?;
+
+	Ok((
+		ETag(tree.todo.version),
+		ApiResponseData::success_with_data(tree, None, StatusCode::OK),
+	))
+}
+
 #[utoipa::path(
 	tag = "Todo",
 	patch,
 	path = "/api/todos/{id}/mark_as_done",
 	params(
 		("id" = String, Path, description = "Todo item id"),
+		("If-Match" = Option<String>, Header, description = "ETag the todo item must match"),
 	),
 	responses(
-		(status = 200, description = "Todo item marked as done successfully", body = ApiResponseTodo),
-		(status = 422, description = "Todo item not exists", body = ApiResponseErrorObject),
-		(status = 500, description = "Internal Server Error", body = ApiResponseErrorObject)
+		(status = 200, description = "Todo item and its pending subtasks marked as done successfully", body = ApiResponseTodo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 409, description = "Todo item was modified meanwhile", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 412, description = "Todo item version does not match If-Match", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "Todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
 	)
 )]
 pub async fn mark_as_done_todo_ctrl(
 	State(app_state): State<AppState>,
-	Path(id): Path<String>,
-) -> ApiResponse<Todo, TodoParams> {
+	PathParam(id): PathParam<String>,
+	headers: HeaderMap,
+) -> ApiResponseWithETag<Todo, TodoParams> {
 	let mark_as_done_usecase =
 		mark_as_done_todo_usecase::MarkAsDoneTodoUsecase::new(&app_state.todo_repo);
 
-	let todo = mark_as_done_usecase.exec(id, true).await?;
+	let if_match = extract_if_match_from_header(&headers);
+	let output = mark_as_done_usecase.exec(id, true, if_match.as_deref()).await?;
+	let todo = output.todo.clone();
 
-	Ok(ApiResponseData::success_with_data(
-		todo,
-		None,
-		StatusCode::OK,
+	broadcast_outcome(
+		&app_state,
+		BatchOutcome::Marked {
+			output: Box::new(output),
+			done: true,
+		},
+	);
+
+	Ok((
+		ETag(todo.version),
+		ApiResponseData::success_with_data(todo, None, StatusCode::OK),
 	))
 }
 
@@ -139,33 +428,229 @@ pub async fn mark_as_done_todo_ctrl(
 	path = "/api/todos/{id}/mark_as_undone",
 	params(
 		("id" = String, Path, description = "Todo item id"),
+		("If-Match" = Option<String>, Header, description = "ETag the todo item must match"),
 	),
 	responses(
-		(status = 200, description = "Todo item marked as undone successfully", body = ApiResponseTodo),
-		(status = 422, description = "Todo item not exists", body = ApiResponseErrorObject),
-		(status = 500, description = "Internal Server Error", body = ApiResponseErrorObject)
+		(status = 200, description = "Todo item and its done parents marked as undone successfully", body = ApiResponseTodo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 409, description = "Todo item was modified meanwhile", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 412, description = "Todo item version does not match If-Match", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "Todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
 	)
 )]
 pub async fn mark_as_undone_todo_ctrl(
 	State(app_state): State<AppState>,
-	Path(id): Path<String>,
-) -> ApiResponse<Todo, TodoParams> {
+	PathParam(id): PathParam<String>,
+	headers: HeaderMap,
+) -> ApiResponseWithETag<Todo, TodoParams> {
 	let mark_as_done_usecase =
 		mark_as_done_todo_usecase::MarkAsDoneTodoUsecase::new(&app_state.todo_repo);
 
-	let todo = mark_as_done_usecase.exec(id, false).await?;
+	let if_match = extract_if_match_from_header(&headers);
+	let output = mark_as_done_usecase.exec(id, false, if_match.as_deref()).await?;
+	let todo = output.todo.clone();
+
+	broadcast_outcome(
+		&app_state,
+		BatchOutcome::Marked {
+			output: Box::new(output),
+			done: false,
+		},
+	);
+
+	Ok((
+		ETag(todo.version),
+		ApiResponseData::success_with_data(todo, None, StatusCode::OK),
+	))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	post,
+	path = "/api/todos/{id}/move",
+	params(
+		("id" = String, Path, description = "Todo item id"),
+		("If-Match" = Option<String>, Header, description = "ETag the todo item must match"),
+	),
+	request_body = MoveTodoParams,
+	responses(
+		(status = 200, description = "Todo item moved successfully", body = ApiResponseTodo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 400, description = "Neighbours are missing, not in order or not siblings of the todo", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Todo item was modified meanwhile", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 412, description = "Todo item version does not match If-Match", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "Todo item or neighbour not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn move_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	headers: HeaderMap,
+	JsonBody(params): JsonBody<MoveTodoParams>,
+) -> ApiResponseWithETag<Todo, TodoParams> {
+	let move_todo_usecase = move_todo_usecase::MoveTodoUsecase::new(&app_state.todo_repo);
+
+	let if_match = extract_if_match_from_header(&headers);
+	let todo = move_todo_usecase.exec(id, params, if_match.as_deref()).await?;
+
+	app_state.broadcast_update_to_view(UpdateTodoTmpl {
+		todo: TodoView::new(todo.clone(), TodoOperation::Move, TodoCan::Write),
+	});
+
+	Ok((
+		ETag(todo.version),
+		ApiResponseData::success_with_data(todo, None, StatusCode::OK),
+	))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	post,
+	path = "/api/todos/batch",
+	request_body = BatchTodosParams,
+	responses(
+		(status = 200, description = "Operations applied, each with its own status in best effort mode", body = ApiResponseBatchTodos),
+		(status = 400, description = "Empty or too large batch, or invalid operation of an atomic batch", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Todo item of an atomic batch was modified meanwhile", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 412, description = "Todo item of an atomic batch is not at the given version", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "Todo item of an atomic batch not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn batch_todos_ctrl(
+	State(app_state): State<AppState>,
+	JsonBody(params): JsonBody<BatchTodosParams>,
+) -> ApiResponse<Vec<BatchOperationResult>, TodoParams> {
+	let results = batch_todos(&app_state, params).await?;
 
 	Ok(ApiResponseData::success_with_data(
-		todo,
+		results,
 		None,
 		StatusCode::OK,
 	))
 }
 
+/// Runs the batch and broadcasts its changes, returns the result of each operation.
+pub async fn batch_todos(
+	app_state: &AppState,
+	params: BatchTodosParams,
+) -> Result<Vec<BatchOperationResult>, ApiResponseError> {
+	let batch_todos_usecase = batch_todos_usecase::BatchTodosUsecase::new(&app_state.todo_repo);
+
+	let outcomes = match batch_todos_usecase.exec(params).await {
+		Ok(outcomes) => outcomes,
+		Err(BatchFailure {
+			index: Some(index),
+			error,
+		}) => {
+			return Err(ApiResponseError::new(
+				error.status(),
+				error.problem_type(),
+				format!("Operation {index}: {error}"),
+			))
+		},
+		Err(BatchFailure { index: None, error }) => return Err(error.into()),
+	};
+
+	let results = outcomes
+		.into_iter()
+		.map(|outcome| match outcome {
+			Ok(outcome) => {
+				let status = match outcome {
+					BatchOutcome::Created(_) => StatusCode::CREATED,
+					_ => StatusCode::OK,
+				};
+				let todo = outcome.todo().clone();
+
+				broadcast_outcome(app_state, outcome);
+
+				BatchOperationResult {
+					status: status.as_u16(),
+					todo: Some(todo),
+					error: None,
+				}
+			},
+			Err(error) => BatchOperationResult {
+				status: error.status().as_u16(),
+				todo: None,
+				error: Some(ApiResponseError::from(error).into()),
+			},
+		})
+		.collect();
+
+	Ok(results)
+}
+
+/// Sends the todos changed by an operation, alone or in a batch, to the views.
+pub fn broadcast_outcome(app_state: &AppState, outcome: BatchOutcome) {
+	let updates = match outcome {
+		BatchOutcome::Created(todo) => vec![(todo, TodoOperation::Create)],
+		BatchOutcome::Updated(todo) => vec![(todo, TodoOperation::Update)],
+		BatchOutcome::Marked { output, done } => {
+			let operation = match done {
+				true => TodoOperation::MarkAsDone,
+				false => TodoOperation::MarkAsUndone,
+			};
+
+			std::iter::once((output.todo, operation.clone()))
+				.chain(output.cascaded.into_iter().map(|todo| (todo, operation.clone())))
+				.chain(output.next_occurrence.map(|todo| (todo, TodoOperation::Create)))
+				.collect()
+		},
+		BatchOutcome::Deleted(output) => std::iter::once(output.todo)
+			.chain(output.subtasks)
+			.map(|todo| (todo, TodoOperation::Delete))
+			.collect(),
+	};
+
+	for (todo, operation) in updates {
+		app_state.broadcast_update_to_view(UpdateTodoTmpl {
+			todo: TodoView::new(todo, operation, TodoCan::Read),
+		});
+	}
+}
+
 #[derive(Deserialize, IntoParams, Clone, Debug)]
 #[into_params(parameter_in = Query)]
 pub struct CountTodosQuery {
+	/// One of `pending`, `done` or `all` (default)
 	pub status: Option<String>,
+	/// One of `overdue`, `today` or `upcoming`
+	pub due: Option<String>,
+	/// One of `none`, `low`, `medium`, `high` or `urgent`
+	pub priority: Option<String>,
+	/// Tag name
+	pub tag: Option<String>,
+	/// Created at or after this date, RFC 3339 or `YYYY-MM-DD`
+	pub created_after: Option<String>,
+	/// Created before this date, RFC 3339 or `YYYY-MM-DD`
+	pub created_before: Option<String>,
+	/// Done at or after this date, RFC 3339 or `YYYY-MM-DD`
+	pub done_after: Option<String>,
+	/// Done before this date, RFC 3339 or `YYYY-MM-DD`
+	pub done_before: Option<String>,
+	/// Case-insensitive text the description contains
+	pub contains: Option<String>,
+	/// Full-text search on the description
+	pub q: Option<String>,
+}
+
+impl CountTodosQuery {
+	pub fn params(&self) -> TodoQueryParams {
+		TodoQueryParams {
+			status: self.status.clone(),
+			due: self.due.clone(),
+			priority: self.priority.clone(),
+			tag: self.tag.clone(),
+			created_after: self.created_after.clone(),
+			created_before: self.created_before.clone(),
+			done_after: self.done_after.clone(),
+			done_before: self.done_before.clone(),
+			contains: self.contains.clone(),
+			search: self.q.clone(),
+			..Default::default()
+		}
+	}
 }
 
 #[utoipa::path(
@@ -175,20 +660,34 @@ pub struct CountTodosQuery {
 	params(CountTodosQuery),
 	responses(
 		(status = 200, description = "Todo length", body = ApiResponseListTodos),
-		(status = 500, description = "Internal Server Error", body = ApiResponseErrorObject)
+		(status = 400, description = "Invalid todo status or query", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
 	)
 )]
 pub async fn count_todos_ctrl(
 	State(app_state): State<AppState>,
-	query: Query<CountTodosQuery>,
+	QueryParams(query): QueryParams<CountTodosQuery>,
 	headers: HeaderMap,
 ) -> ApiResponse<i64, TodoParams> {
-	let status: Option<String> = extract_status_from_header(headers);
+	let status: Option<String> = extract_status_from_header(&headers);
+	let due: Option<String> = extract_due_from_header(&headers);
+	let priority: Option<String> = extract_priority_from_header(&headers);
+	let tag: Option<String> = extract_tag_from_header(&headers);
 
 	let count_todos_usecase =
 		crate::usecase::count_todos_usecase::CountTodosUsecase::new(&app_state.todo_repo);
 
-	let count = count_todos_usecase.exec(query.status.clone().or(status).as_ref()).await;
+	let params = query.params();
+
+	let count = count_todos_usecase
+		.exec(&TodoQueryParams {
+			status: params.status.or(status),
+			due: params.due.or(due),
+			priority: params.priority.or(priority),
+			tag: params.tag.or(tag),
+			..params
+		})
+		.await?;
 
 	Ok(ApiResponseData::success_with_data(
 		count,
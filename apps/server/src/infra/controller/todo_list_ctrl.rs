@@ -0,0 +1,244 @@
+use axum::{extract::State, http::StatusCode};
+
+use crate::{
+	domain::entity::{todo::Todo, todo_list::TodoList},
+	infra::{
+		api_response::{ApiResponse, ApiResponseData, ListInformations, TodoParams},
+		controller::extract::{JsonBody, PathParam, QueryParams},
+		server::AppState,
+	},
+	usecase::{
+		batch_todos_usecase::BatchOutcome,
+		count_todos_usecase,
+		create_todo_list_usecase::{self, CreateTodoListParams},
+		create_todo_usecase::{self, CreateTodoParams},
+		delete_todo_list_usecase, get_all_todo_lists_usecase, get_all_todos_usecase,
+		get_todo_list_usecase,
+		rename_todo_list_usecase::{self, RenameTodoListParams},
+	},
+};
+
+use super::todo_ctrl::{self, GetAllTodosQuery};
+
+#[utoipa::path(
+	tag = "List",
+	post,
+	path = "/api/lists",
+	request_body = CreateTodoListParams,
+	responses(
+		(status = 201, description = "List created successfully", body = ApiResponseTodoList),
+		(status = 400, description = "List name is empty", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn create_todo_list_ctrl(
+	State(app_state): State<AppState>,
+	JsonBody(params): JsonBody<CreateTodoListParams>,
+) -> ApiResponse<TodoList, TodoParams> {
+	let create_todo_list_usecase =
+		create_todo_list_usecase::CreateTodoListUsecase::new(&app_state.todo_list_repo);
+
+	let list = create_todo_list_usecase.exec(params).await?;
+
+	Ok(ApiResponseData::success_with_data(
+		list,
+		None,
+		StatusCode::CREATED,
+	))
+}
+
+#[utoipa::path(
+	tag = "List",
+	get,
+	path = "/api/lists",
+	responses(
+		(status = 200, description = "Lists retrieved successfully", body = ApiResponseListTodoLists),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_all_todo_lists_ctrl(
+	State(app_state): State<AppState>,
+) -> ApiResponse<Vec<TodoList>, ListInformations> {
+	let get_all_todo_lists_usecase =
+		get_all_todo_lists_usecase::GetAllTodoListsUsecase::new(&app_state.todo_list_repo);
+
+	let lists = get_all_todo_lists_usecase.exec().await?;
+	let total = lists.len() as i64;
+
+	Ok(ApiResponseData::success_with_data(
+		lists,
+		Some(ListInformations {
+			total,
+			next_cursor: None,
+		}),
+		StatusCode::OK,
+	))
+}
+
+#[utoipa::path(
+	tag = "List",
+	get,
+	path = "/api/lists/{id}",
+	params(
+		("id" = String, Path, description = "List id"),
+	),
+	responses(
+		(status = 200, description = "List retrieved successfully", body = ApiResponseTodoList),
+		(status = 422, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_todo_list_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResponse<TodoList, TodoParams> {
+	let get_todo_list_usecase =
+		get_todo_list_usecase::GetTodoListUsecase::new(&app_state.todo_list_repo);
+
+	let list = get_todo_list_usecase.exec(id).await?;
+
+	Ok(ApiResponseData::success_with_data(
+		list,
+		None,
+		StatusCode::OK,
+	))
+}
+
+#[utoipa::path(
+	tag = "List",
+	patch,
+	path = "/api/lists/{id}",
+	params(
+		("id" = String, Path, description = "List id"),
+	),
+	request_body = RenameTodoListParams,
+	responses(
+		(status = 200, description = "List renamed successfully", body = ApiResponseTodoList),
+		(status = 400, description = "List name is empty", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn rename_todo_list_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	JsonBody(params): JsonBody<RenameTodoListParams>,
+) -> ApiResponse<TodoList, TodoParams> {
+	let rename_todo_list_usecase =
+		rename_todo_list_usecase::RenameTodoListUsecase::new(&app_state.todo_list_repo);
+
+	let list = rename_todo_list_usecase.exec(id, params).await?;
+
+	Ok(ApiResponseData::success_with_data(
+		list,
+		None,
+		StatusCode::OK,
+	))
+}
+
+#[utoipa::path(
+	tag = "List",
+	delete,
+	path = "/api/lists/{id}",
+	params(
+		("id" = String, Path, description = "List id"),
+	),
+	responses(
+		(status = 204, description = "List and its todo items deleted successfully"),
+		(status = 422, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn delete_todo_list_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResponse<(), ()> {
+	let delete_todo_list_usecase =
+		delete_todo_list_usecase::DeleteTodoListUsecase::new(&app_state.todo_list_repo);
+
+	delete_todo_list_usecase.exec(id).await?;
+
+	Ok(ApiResponseData::status_code(StatusCode::NO_CONTENT))
+}
+
+#[utoipa::path(
+	tag = "List",
+	get,
+	path = "/api/lists/{id}/todos",
+	params(
+		("id" = String, Path, description = "List id"),
+		GetAllTodosQuery,
+	),
+	responses(
+		(status = 200, description = "Todo items of the list retrieved successfully", body = ApiResponseListTodos),
+		(status = 400, description = "Invalid todo status or query", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_list_todos_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	QueryParams(query): QueryParams<GetAllTodosQuery>,
+) -> ApiResponse<Vec<Todo>, ListInformations> {
+	let get_todo_list_usecase =
+		get_todo_list_usecase::GetTodoListUsecase::new(&app_state.todo_list_repo);
+	let get_all_todos_usecase =
+		get_all_todos_usecase::GetAllTodosUsecase::new(&app_state.todo_repo);
+	let count_todos_usecase = count_todos_usecase::CountTodosUsecase::new(&app_state.todo_repo);
+
+	let list = get_todo_list_usecase.exec(id).await?;
+
+	let params = query.params(Some(list.id));
+
+	let count = count_todos_usecase.exec(&params).await?;
+	let page = get_all_todos_usecase.exec(&params, None).await?;
+
+	Ok(ApiResponseData::success_with_data(
+		page.todos,
+		Some(ListInformations {
+			total: count,
+			next_cursor: page.next_cursor,
+		}),
+		StatusCode::OK,
+	))
+}
+
+#[utoipa::path(
+	tag = "List",
+	post,
+	path = "/api/lists/{id}/todos",
+	params(
+		("id" = String, Path, description = "List id"),
+	),
+	request_body = CreateTodoParams,
+	responses(
+		(status = 201, description = "Todo item created in the list successfully", body = ApiResponseTodo),
+		(status = 400, description = "Invalid recurrence rule", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 422, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn create_list_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	JsonBody(mut params): JsonBody<CreateTodoParams>,
+) -> ApiResponse<Todo, TodoParams> {
+	let get_todo_list_usecase =
+		get_todo_list_usecase::GetTodoListUsecase::new(&app_state.todo_list_repo);
+	let create_todo_usecase = create_todo_usecase::CreateTodoUsecase::new(&app_state.todo_repo);
+
+	let list = get_todo_list_usecase.exec(id).await?;
+
+	params.list_id = Some(list.id);
+
+	let todo = create_todo_usecase.exec(params).await?;
+
+	todo_ctrl::broadcast_outcome(&app_state, BatchOutcome::Created(todo.clone()));
+
+	Ok(ApiResponseData::success_with_data(
+		todo,
+		None,
+		StatusCode::CREATED,
+	))
+}
@@ -16,20 +16,47 @@ use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt as _};
 use utoipa::IntoParams;
 
 use crate::{
-	domain::entity::todo::{TodoCan, TodoOperation, TodoView},
-	infra::server::AppState,
+	domain::{
+		entity::{
+			todo::{TodoCan, TodoOperation, TodoPriority, TodoView},
+			todo_list::TodoList,
+		},
+		exception::TodoException,
+	},
+	infra::{api_response::ApiProblem, server::AppState},
 	usecase::{
+		clear_completed_todos_usecase, create_subtask_usecase,
+		create_todo_list_usecase::{self, CreateTodoListParams},
 		create_todo_usecase::{self, CreateTodoParams},
-		delete_todo_usecase, get_all_todos_usecase, mark_as_done_todo_usecase,
+		delete_todo_usecase, get_all_todo_lists_usecase,
+		get_all_todos_usecase::{self, TodoQueryParams, DEFAULT_PAGE_SIZE},
+		get_todo_tree_usecase::build_forest,
+		get_trash_usecase, mark_as_done_todo_usecase,
+		move_todo_usecase::{self, MoveTodoParams},
+		restore_todo_usecase,
+		update_todo_usecase::{self, UpdateTodoParams},
 	},
 };
 
-use super::helper::extract_status_from_header;
+use super::helper::{
+	extract_due_from_header, extract_list_from_header, extract_order_from_header,
+	extract_priority_from_header, extract_status_from_header, extract_tag_from_header,
+};
 
 #[derive(Template)]
 #[template(path = "views/index.html")]
 pub struct IndexTemplate {
 	num_items: i64,
+	lists: Vec<TodoList>,
+	current_list: Option<String>,
+	list_query: String,
+}
+
+#[derive(Template)]
+#[template(path = "views/trash.html")]
+pub struct TrashTmpl {
+	pub todos: Vec<TodoView>,
+	pub retention_days: i64,
 }
 
 #[derive(Template)]
@@ -37,6 +64,8 @@ pub struct IndexTemplate {
 pub struct StreamTmpl {
 	pub num_items: i64,
 	pub todos: Vec<TodoView>,
+	pub sortable: bool,
+	pub next_cursor: Option<String>,
 }
 
 #[derive(Template, Clone, Debug)]
@@ -45,33 +74,77 @@ pub struct UpdateTodoTmpl {
 	pub todo: TodoView,
 }
 
+#[derive(Template, Clone, Debug)]
+#[template(path = "responses/edit_todo.html")]
+pub struct EditTodoTmpl {
+	pub todo: TodoView,
+}
+
 #[derive(Deserialize, IntoParams, Clone, Debug)]
 #[into_params(parameter_in = Query)]
 pub struct SearchTodosQuery {
 	pub status: Option<String>,
+	pub due: Option<String>,
+	pub priority: Option<String>,
+	pub tag: Option<String>,
+	pub list: Option<String>,
+	pub order: Option<String>,
+	pub cursor: Option<String>,
+	pub q: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IndexQuery {
+	pub list: Option<String>,
 }
 
-pub async fn render_index_ctrl() -> Result<IndexTemplate, ()> {
-	Ok(IndexTemplate { num_items: 0 })
+pub async fn render_index_ctrl(
+	State(app_state): State<AppState>,
+	Query(query): Query<IndexQuery>,
+) -> Result<IndexTemplate, StatusCode> {
+	let get_all_todo_lists_usecase =
+		get_all_todo_lists_usecase::GetAllTodoListsUsecase::new(&app_state.todo_list_repo);
+
+	let lists = match get_all_todo_lists_usecase.exec().await {
+		Ok(lists) => lists,
+		Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+	};
+
+	let list_query = match &query.list {
+		Some(list) => format!("list={}&", list),
+		None => String::new(),
+	};
+
+	Ok(IndexTemplate {
+		num_items: 0,
+		lists,
+		current_list: query.list,
+		list_query,
+	})
 }
 
 pub async fn stream_ctrl(State(app_state): State<AppState>) -> Result<StreamTmpl, ()> {
 	let get_all_todos_usecase =
 		get_all_todos_usecase::GetAllTodosUsecase::new(&app_state.todo_repo);
 
-	let todos = match get_all_todos_usecase.exec(None).await {
-		Ok(todos) => todos,
+	let params = TodoQueryParams::default();
+
+	let page = match get_all_todos_usecase.exec(&params, Some(DEFAULT_PAGE_SIZE)).await {
+		Ok(page) => page,
 		Err(_) => return Err(()),
 	};
 
 	let count_todos_usecase =
 		crate::usecase::count_todos_usecase::CountTodosUsecase::new(&app_state.todo_repo);
 
-	let count = count_todos_usecase.exec(None).await;
+	let count = count_todos_usecase.exec(&params).await.unwrap_or(0);
 
 	Ok(StreamTmpl {
 		num_items: count,
-		todos: todos
+		sortable: false,
+		next_cursor: page.next_cursor,
+		todos: page
+			.todos
 			.into_iter()
 			.map(|todo| TodoView::new(todo, TodoOperation::Read, TodoCan::Read))
 			.collect(),
@@ -83,47 +156,108 @@ pub async fn stream_ctrl(State(app_state): State<AppState>) -> Result<StreamTmpl
 pub struct ListTodosTmpl {
 	pub todos: Vec<TodoView>,
 	pub num_items: i64,
+	/// Todos can be dragged around in the manual order
+	pub sortable: bool,
+	/// Renders a button loading the next page
+	pub next_cursor: Option<String>,
 }
 
 pub async fn list_todos_ctrl(
 	State(app_state): State<AppState>,
 	Query(query): Query<SearchTodosQuery>,
 	headers: HeaderMap,
-) -> impl IntoResponse {
+) -> Result<ListTodosTmpl, StatusCode> {
 	let get_all_todos_usecase =
 		get_all_todos_usecase::GetAllTodosUsecase::new(&app_state.todo_repo);
 
-	let header_status = extract_status_from_header(headers);
+	let header_status = extract_status_from_header(&headers);
 	let status = query.status.clone().or(header_status);
+	let header_due = extract_due_from_header(&headers);
+	let due = query.due.clone().or(header_due);
+	let header_priority = extract_priority_from_header(&headers);
+	let priority = query.priority.clone().or(header_priority);
+	let header_tag = extract_tag_from_header(&headers);
+	let tag = query.tag.clone().or(header_tag);
+	let header_list = extract_list_from_header(&headers);
+	let list = query.list.clone().or(header_list);
+	let header_order = extract_order_from_header(&headers);
+	let order = query.order.clone().or(header_order);
+
+	let params = TodoQueryParams {
+		status,
+		due,
+		priority,
+		tag,
+		list_id: list,
+		search: query.q.clone(),
+		order: order.clone(),
+		cursor: query.cursor.clone(),
+		..Default::default()
+	};
 
-	let todos = get_all_todos_usecase.exec(status.as_ref()).await.unwrap();
+	let page = get_all_todos_usecase
+		.exec(&params, Some(DEFAULT_PAGE_SIZE))
+		.await
+		.map_err(status_code)?;
 
 	let count_todos_usecase =
 		crate::usecase::count_todos_usecase::CountTodosUsecase::new(&app_state.todo_repo);
 
-	let count = count_todos_usecase.exec(status.as_ref()).await;
+	let count = count_todos_usecase.exec(&params).await.map_err(status_code)?;
 
-	ListTodosTmpl {
-		todos: todos
+	Ok(ListTodosTmpl {
+		sortable: order.as_deref() == Some("manual"),
+		next_cursor: page.next_cursor,
+		todos: build_forest(page.todos)
 			.into_iter()
-			.map(|todo| TodoView::new(todo, TodoOperation::Read, TodoCan::Write))
+			.flat_map(|tree| TodoView::from_tree(tree, TodoOperation::Read, TodoCan::Write))
 			.collect(),
 		num_items: count,
-	}
+	})
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CreateTodoForm {
 	pub description: String,
+	pub due_at: Option<String>,
+	#[serde(default)]
+	pub priority: TodoPriority,
+	/// Comma separated tag names
+	pub tags: Option<String>,
+	pub recurrence: Option<String>,
 }
 
 pub async fn create_todo_ctrl(
 	State(app_state): State<AppState>,
-	Form(CreateTodoForm { description }): Form<CreateTodoForm>,
-) -> impl IntoResponse {
+	headers: HeaderMap,
+	Form(CreateTodoForm {
+		description,
+		due_at,
+		priority,
+		tags,
+		recurrence,
+	}): Form<CreateTodoForm>,
+) -> Result<impl IntoResponse, StatusCode> {
 	let usecase = create_todo_usecase::CreateTodoUsecase::new(&app_state.todo_repo);
 
-	let todo = usecase.exec(CreateTodoParams { description }).await.unwrap();
+	// the date input sends an empty string when no due date is picked
+	let due_at = due_at
+		.and_then(|due_at| chrono::NaiveDate::parse_from_str(&due_at, "%Y-%m-%d").ok())
+		.map(|due_at| due_at.and_hms_opt(0, 0, 0).unwrap().and_utc());
+
+	let todo = usecase
+		.exec(CreateTodoParams {
+			description,
+			due_at,
+			priority,
+			tags: split_tags(tags),
+			// the select sends an empty string for todos that do not repeat
+			recurrence: recurrence.filter(|recurrence| !recurrence.is_empty()),
+			list_id: extract_list_from_header(&headers),
+			parent_id: None,
+		})
+		.await
+		.map_err(status_code)?;
 
 	let todo_view = TodoView::new(todo, TodoOperation::Create, TodoCan::Write);
 
@@ -137,54 +271,181 @@ pub async fn create_todo_ctrl(
 		"watch-count-todos".parse().unwrap(),
 	);
 
-	(new_headers, update)
+	Ok((new_headers, update))
 }
 
-pub async fn mark_as_done_todo_ctrl(
+/// The description comes from the `hx-prompt` of the add subtask button.
+pub async fn create_subtask_ctrl(
 	State(app_state): State<AppState>,
 	Path(id): Path<String>,
 	headers: HeaderMap,
 ) -> impl IntoResponse {
-	let mark_as_done_usecase =
-		mark_as_done_todo_usecase::MarkAsDoneTodoUsecase::new(&app_state.todo_repo);
+	let mut new_headers = HeaderMap::new();
 
-	let todo = mark_as_done_usecase.exec(id, true).await.unwrap();
+	let description = match headers.get("HX-Prompt").and_then(|value| value.to_str().ok()) {
+		Some(description) if !description.trim().is_empty() => description.trim().to_string(),
+		_ => return (StatusCode::UNPROCESSABLE_ENTITY, new_headers),
+	};
 
-	let todo_view = TodoView::new(todo, TodoOperation::MarkAsDone, TodoCan::Write);
+	let create_subtask_usecase =
+		create_subtask_usecase::CreateSubtaskUsecase::new(&app_state.todo_repo);
+
+	let params = CreateTodoParams {
+		description,
+		due_at: None,
+		priority: TodoPriority::None,
+		tags: vec![],
+		recurrence: None,
+		list_id: None,
+		parent_id: None,
+	};
+
+	let todo = match create_subtask_usecase.exec(id, params).await {
+		Ok(todo) => todo,
+		Err(_) => return (StatusCode::UNPROCESSABLE_ENTITY, new_headers),
+	};
+
+	app_state.broadcast_update_to_view(UpdateTodoTmpl {
+		todo: TodoView::new(todo, TodoOperation::Create, TodoCan::Write),
+	});
+
+	// reload the list so the subtask shows up under its parent
+	new_headers.insert("HX-Trigger", "watch-list-todos".parse().unwrap());
+
+	(StatusCode::CREATED, new_headers)
+}
+
+pub async fn get_todo_ctrl(
+	State(app_state): State<AppState>,
+	Path(id): Path<String>,
+) -> Result<UpdateTodoTmpl, StatusCode> {
+	let todo = match app_state.todo_repo.find_by_id(id).await {
+		Ok(todo) => todo,
+		Err(_) => return Err(StatusCode::UNPROCESSABLE_ENTITY),
+	};
+
+	Ok(UpdateTodoTmpl {
+		todo: TodoView::new(todo, TodoOperation::Read, TodoCan::Write),
+	})
+}
+
+pub async fn edit_todo_ctrl(
+	State(app_state): State<AppState>,
+	Path(id): Path<String>,
+) -> Result<EditTodoTmpl, StatusCode> {
+	let todo = match app_state.todo_repo.find_by_id(id).await {
+		Ok(todo) => todo,
+		Err(_) => return Err(StatusCode::UNPROCESSABLE_ENTITY),
+	};
+
+	Ok(EditTodoTmpl {
+		todo: TodoView::new(todo, TodoOperation::Read, TodoCan::Write),
+	})
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTodoForm {
+	pub description: String,
+	pub priority: Option<TodoPriority>,
+	/// Comma separated tag names
+	pub tags: Option<String>,
+	pub recurrence: Option<String>,
+}
+
+pub async fn update_todo_ctrl(
+	State(app_state): State<AppState>,
+	Path(id): Path<String>,
+	Form(UpdateTodoForm {
+		description,
+		priority,
+		tags,
+		recurrence,
+	}): Form<UpdateTodoForm>,
+) -> impl IntoResponse {
+	let update_todo_usecase = update_todo_usecase::UpdateTodoUsecase::new(&app_state.todo_repo);
+
+	let params = UpdateTodoParams {
+		description: Some(description),
+		priority,
+		tags: Some(split_tags(tags)),
+		recurrence,
+	};
+
+	let todo = match update_todo_usecase.exec(id, params, None).await {
+		Ok(todo) => todo,
+		Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
+	};
+
+	let todo_view = TodoView::new(todo, TodoOperation::Update, TodoCan::Write);
 
 	let update = UpdateTodoTmpl { todo: todo_view };
 
 	app_state.broadcast_update_to_view(update.clone());
 
+	update.into_response()
+}
+
+pub async fn mark_as_done_todo_ctrl(
+	State(app_state): State<AppState>,
+	Path(id): Path<String>,
+	headers: HeaderMap,
+) -> Result<impl IntoResponse, StatusCode> {
+	let mark_as_done_usecase =
+		mark_as_done_todo_usecase::MarkAsDoneTodoUsecase::new(&app_state.todo_repo);
+
+	let output = mark_as_done_usecase.exec(id, true, None).await.map_err(status_code)?;
+
 	let mut new_headers = HeaderMap::new();
 	new_headers.insert(
 		"HX-Trigger-After-Swap",
 		"watch-count-todos".parse().unwrap(),
 	);
 
-	let status = extract_status_from_header(headers);
-	if status == Some("pending".to_string()) {
+	// subtasks progress, cascaded states and next occurrences are only refreshed by
+	// reloading the list
+	if output.todo.parent_id.is_some()
+		|| !output.cascaded.is_empty()
+		|| output.next_occurrence.is_some()
+	{
+		new_headers.insert("HX-Trigger", "watch-list-todos".parse().unwrap());
+	}
+
+	if let Some(todo) = output.next_occurrence {
+		app_state.broadcast_update_to_view(UpdateTodoTmpl {
+			todo: TodoView::new(todo, TodoOperation::Create, TodoCan::Write),
+		});
+	}
+
+	for todo in output.cascaded {
+		app_state.broadcast_update_to_view(UpdateTodoTmpl {
+			todo: TodoView::new(todo, TodoOperation::MarkAsDone, TodoCan::Write),
+		});
+	}
+
+	let todo_view = TodoView::new(output.todo, TodoOperation::MarkAsDone, TodoCan::Write);
+
+	let update = UpdateTodoTmpl { todo: todo_view };
+
+	app_state.broadcast_update_to_view(update.clone());
+
+	let status = extract_status_from_header(&headers);
+	let due = extract_due_from_header(&headers);
+	if status == Some("pending".to_string()) || due == Some("overdue".to_string()) {
 		new_headers.insert("HX-Reswap", "delete".parse().unwrap());
 	}
 
-	(new_headers, update)
+	Ok((new_headers, update))
 }
 
 pub async fn mark_as_undone_todo_ctrl(
 	State(app_state): State<AppState>,
 	Path(id): Path<String>,
 	headers: HeaderMap,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, StatusCode> {
 	let mark_as_done_usecase =
 		mark_as_done_todo_usecase::MarkAsDoneTodoUsecase::new(&app_state.todo_repo);
 
-	let todo = mark_as_done_usecase.exec(id, false).await.unwrap();
-
-	let todo_view = TodoView::new(todo, TodoOperation::MarkAsUndone, TodoCan::Write);
-
-	let update = UpdateTodoTmpl { todo: todo_view };
-
-	app_state.broadcast_update_to_view(update.clone());
+	let output = mark_as_done_usecase.exec(id, false, None).await.map_err(status_code)?;
 
 	let mut new_headers = HeaderMap::new();
 	new_headers.insert(
@@ -192,32 +453,95 @@ pub async fn mark_as_undone_todo_ctrl(
 		"watch-count-todos".parse().unwrap(),
 	);
 
-	let status = extract_status_from_header(headers);
+	// subtasks progress, cascaded states and next occurrences are only refreshed by
+	// reloading the list
+	if output.todo.parent_id.is_some()
+		|| !output.cascaded.is_empty()
+		|| output.next_occurrence.is_some()
+	{
+		new_headers.insert("HX-Trigger", "watch-list-todos".parse().unwrap());
+	}
+
+	if let Some(todo) = output.next_occurrence {
+		app_state.broadcast_update_to_view(UpdateTodoTmpl {
+			todo: TodoView::new(todo, TodoOperation::Create, TodoCan::Write),
+		});
+	}
+
+	for todo in output.cascaded {
+		app_state.broadcast_update_to_view(UpdateTodoTmpl {
+			todo: TodoView::new(todo, TodoOperation::MarkAsUndone, TodoCan::Write),
+		});
+	}
+
+	let todo_view = TodoView::new(output.todo, TodoOperation::MarkAsUndone, TodoCan::Write);
+
+	let update = UpdateTodoTmpl { todo: todo_view };
+
+	app_state.broadcast_update_to_view(update.clone());
+
+	let status = extract_status_from_header(&headers);
 	if status == Some("done".to_string()) {
 		new_headers.insert("HX-Reswap", "delete".parse().unwrap());
 	}
 
-	(new_headers, update)
+	Ok((new_headers, update))
 }
 
-pub async fn delete_todo_ctrl(
+#[derive(Debug, Deserialize)]
+pub struct MoveTodoForm {
+	pub after_id: Option<String>,
+	pub before_id: Option<String>,
+}
+
+/// Called once a todo is dropped, the list is already in its new order.
+pub async fn move_todo_ctrl(
 	State(app_state): State<AppState>,
 	Path(id): Path<String>,
+	Form(MoveTodoForm {
+		after_id,
+		before_id,
+	}): Form<MoveTodoForm>,
 ) -> impl IntoResponse {
-	let mut new_headers = HeaderMap::new();
+	let move_todo_usecase = move_todo_usecase::MoveTodoUsecase::new(&app_state.todo_repo);
 
-	let todo = match &app_state.todo_repo.find_by_id(id).await {
-		Ok(todo) => todo.to_owned(),
-		Err(_) => return (StatusCode::UNPROCESSABLE_ENTITY, new_headers),
+	// the first and last items of the list send an empty neighbour
+	let params = MoveTodoParams {
+		after_id: after_id.filter(|id| !id.is_empty()),
+		before_id: before_id.filter(|id| !id.is_empty()),
+	};
+
+	let todo = match move_todo_usecase.exec(id, params, None).await {
+		Ok(todo) => todo,
+		Err(_) => return StatusCode::UNPROCESSABLE_ENTITY,
 	};
 
+	app_state.broadcast_update_to_view(UpdateTodoTmpl {
+		todo: TodoView::new(todo, TodoOperation::Move, TodoCan::Write),
+	});
+
+	StatusCode::OK
+}
+
+pub async fn delete_todo_ctrl(
+	State(app_state): State<AppState>,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	let mut new_headers = HeaderMap::new();
+
 	let delete_todo_usecase = delete_todo_usecase::DeleteTodoUsecase::new(&app_state.todo_repo);
 
-	match delete_todo_usecase.exec(todo.id.clone()).await {
-		Ok(_) => (),
+	let (todo, subtasks) = match delete_todo_usecase.exec(id, None).await {
+		Ok(output) => (output.todo, output.subtasks),
+		Err(TodoException::NotFound) => return (StatusCode::UNPROCESSABLE_ENTITY, new_headers),
 		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, new_headers),
 	};
 
+	// subtasks are deleted along with their parent, and the parent progress changes
+	if !subtasks.is_empty() || todo.parent_id.is_some() {
+		new_headers.insert("HX-Trigger", "watch-list-todos".parse().unwrap());
+	}
+
 	let update = UpdateTodoTmpl {
 		todo: TodoView::new(todo, TodoOperation::Delete, TodoCan::Write),
 	};
@@ -233,15 +557,127 @@ pub async fn delete_todo_ctrl(
 	(StatusCode::OK, new_headers)
 }
 
-pub async fn count_todos_ctrl(State(app_state): State<AppState>, headers: HeaderMap) -> String {
-	let status: Option<String> = extract_status_from_header(headers);
+pub async fn render_trash_ctrl(State(app_state): State<AppState>) -> Result<TrashTmpl, StatusCode> {
+	let get_trash_usecase = get_trash_usecase::GetTrashUsecase::new(&app_state.todo_repo);
+
+	let todos = match get_trash_usecase.exec().await {
+		Ok(todos) => todos,
+		Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+	};
+
+	Ok(TrashTmpl {
+		todos: todos
+			.into_iter()
+			.map(|todo| TodoView::new(todo, TodoOperation::Read, TodoCan::Write))
+			.collect(),
+		retention_days: app_state.trash_retention_days,
+	})
+}
+
+pub async fn restore_todo_ctrl(
+	State(app_state): State<AppState>,
+	Path(id): Path<String>,
+) -> StatusCode {
+	let restore_todo_usecase = restore_todo_usecase::RestoreTodoUsecase::new(&app_state.todo_repo);
+
+	let todo = match restore_todo_usecase.exec(id).await {
+		Ok(todo) => todo,
+		Err(_) => return StatusCode::UNPROCESSABLE_ENTITY,
+	};
+
+	app_state.broadcast_update_to_view(UpdateTodoTmpl {
+		todo: TodoView::new(todo, TodoOperation::Restore, TodoCan::Write),
+	});
+
+	StatusCode::OK
+}
+
+pub async fn clear_all_completed_todos_ctrl(
+	State(app_state): State<AppState>,
+	headers: HeaderMap,
+) -> impl IntoResponse {
+	let mut new_headers = HeaderMap::new();
+	let list: Option<String> = extract_list_from_header(&headers);
+
+	let clear_completed_todos_usecase =
+		clear_completed_todos_usecase::ClearCompletedTodosUsecase::new(&app_state.todo_repo);
+
+	let todos = match clear_completed_todos_usecase.exec(list.as_ref()).await {
+		Ok(todos) => todos,
+		Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, new_headers),
+	};
+
+	for todo in todos {
+		app_state.broadcast_update_to_view(UpdateTodoTmpl {
+			todo: TodoView::new(todo, TodoOperation::Delete, TodoCan::Write),
+		});
+	}
+
+	new_headers.insert("HX-Trigger", "watch-list-todos".parse().unwrap());
+
+	(StatusCode::OK, new_headers)
+}
+
+pub async fn count_todos_ctrl(
+	State(app_state): State<AppState>,
+	Query(query): Query<SearchTodosQuery>,
+	headers: HeaderMap,
+) -> Result<String, StatusCode> {
+	let status: Option<String> = extract_status_from_header(&headers);
+	let due: Option<String> = extract_due_from_header(&headers);
+	let priority: Option<String> = extract_priority_from_header(&headers);
+	let tag: Option<String> = extract_tag_from_header(&headers);
+	let list: Option<String> = extract_list_from_header(&headers);
 
 	let count_todos_usecase =
 		crate::usecase::count_todos_usecase::CountTodosUsecase::new(&app_state.todo_repo);
 
-	let count = count_todos_usecase.exec(status.as_ref()).await;
+	let count = count_todos_usecase
+		.exec(&TodoQueryParams {
+			status,
+			due,
+			priority,
+			tag,
+			list_id: list,
+			search: query.q,
+			..Default::default()
+		})
+		.await
+		.map_err(status_code)?;
+
+	Ok(count.to_string())
+}
 
-	count.to_string()
+#[derive(Debug, Deserialize)]
+pub struct CreateTodoListForm {
+	pub name: String,
+}
+
+pub async fn create_todo_list_ctrl(
+	State(app_state): State<AppState>,
+	Form(CreateTodoListForm { name }): Form<CreateTodoListForm>,
+) -> impl IntoResponse {
+	let mut new_headers = HeaderMap::new();
+
+	let create_todo_list_usecase =
+		create_todo_list_usecase::CreateTodoListUsecase::new(&app_state.todo_list_repo);
+
+	let list = match create_todo_list_usecase.exec(CreateTodoListParams { name }).await {
+		Ok(list) => list,
+		Err(_) => return (StatusCode::UNPROCESSABLE_ENTITY, new_headers),
+	};
+
+	new_headers.insert(
+		"HX-Redirect",
+		format!("/?list={}", list.id).parse().unwrap(),
+	);
+
+	(StatusCode::CREATED, new_headers)
+}
+
+fn split_tags(tags: Option<String>) -> Vec<String> {
+	tags.map(|tags| tags.split(',').map(|tag| tag.to_string()).collect())
+		.unwrap_or_default()
 }
 
 pub async fn todos_stream(
@@ -253,19 +689,23 @@ pub async fn todos_stream(
 
 	Sse::new(
 		stream
-			.map(
+			// a lagging receiver skips the missed updates instead of closing the stream
+			.filter_map(
 				|msg: Result<
 					UpdateTodoTmpl,
 					tokio_stream::wrappers::errors::BroadcastStreamRecvError,
-				>| {
-					let mut msg = msg.unwrap();
-
-					msg.todo.can = TodoCan::Read.to_string().to_uppercase();
-
-					Event::default().event("update_todo_view").data(msg.render().unwrap())
-				},
+				>| msg.ok(),
 			)
+			.map(|mut msg: UpdateTodoTmpl| {
+				msg.todo.can = TodoCan::Read.to_string().to_uppercase();
+
+				Event::default().event("update_todo_view").data(msg.render().unwrap())
+			})
 			.map(Ok),
 	)
 	.keep_alive(KeepAlive::new().interval(Duration::from_secs(600)).text("keep-alive-text"))
 }
+
+fn status_code(err: TodoException) -> StatusCode {
+	err.status()
+}
@@ -0,0 +1,71 @@
+use axum::{extract::State, http::StatusCode};
+
+use crate::{
+	domain::entity::todo::{Todo, TodoCan, TodoOperation, TodoView},
+	infra::{
+		api_response::{
+			ApiResponse, ApiResponseData, ApiResponseWithETag, ETag, ListInformations, TodoParams,
+		},
+		controller::{extract::PathParam, todos_views_ctrl::UpdateTodoTmpl},
+		server::AppState,
+	},
+	usecase::{get_trash_usecase, restore_todo_usecase},
+};
+
+#[utoipa::path(
+	tag = "Trash",
+	get,
+	path = "/api/trash",
+	responses(
+		(status = 200, description = "Deleted todo items retrieved successfully", body = ApiResponseListTodos),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_trash_ctrl(
+	State(app_state): State<AppState>,
+) -> ApiResponse<Vec<Todo>, ListInformations> {
+	let get_trash_usecase = get_trash_usecase::GetTrashUsecase::new(&app_state.todo_repo);
+
+	let todos = get_trash_usecase.exec().await?;
+	let total = todos.len() as i64;
+
+	Ok(ApiResponseData::success_with_data(
+		todos,
+		Some(ListInformations {
+			total,
+			next_cursor: None,
+		}),
+		StatusCode::OK,
+	))
+}
+
+#[utoipa::path(
+	tag = "Trash",
+	post,
+	path = "/api/todos/{id}/restore",
+	params(
+		("id" = String, Path, description = "Deleted todo item id"),
+	),
+	responses(
+		(status = 200, description = "Todo item restored successfully", body = ApiResponseTodo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 422, description = "Todo item not in the trash", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn restore_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResponseWithETag<Todo, TodoParams> {
+	let restore_todo_usecase = restore_todo_usecase::RestoreTodoUsecase::new(&app_state.todo_repo);
+
+	let todo = restore_todo_usecase.exec(id).await?;
+
+	app_state.broadcast_update_to_view(UpdateTodoTmpl {
+		todo: TodoView::new(todo.clone(), TodoOperation::Restore, TodoCan::Write),
+	});
+
+	Ok((
+		ETag(todo.version),
+		ApiResponseData::success_with_data(todo, None, StatusCode::OK),
+	))
+}
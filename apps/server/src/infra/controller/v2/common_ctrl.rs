@@ -0,0 +1,19 @@
+use axum::{extract::State, Json};
+
+use crate::infra::{
+	api_response::ApiResult, controller::common_ctrl, repository::todo_cached_repo::TodoCacheStats,
+	server::AppState,
+};
+
+#[utoipa::path(
+	tag = "Core",
+	get,
+	path = "/api/v2/cache",
+	responses(
+		(status = 200, description = "Hits and misses of the todo cache", body = TodoCacheStats),
+		(status = 404, description = "Todo cache disabled", body = ProblemDetails, content_type = "application/problem+json"),
+	)
+)]
+pub async fn cache_stats_ctrl(state: State<AppState>) -> ApiResult<Json<TodoCacheStats>> {
+	common_ctrl::cache_stats_ctrl(state).await
+}
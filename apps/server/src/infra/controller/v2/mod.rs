@@ -0,0 +1,8 @@
+//! Controllers of the `/api/v2` surface: the resources are sent as is, the lists as pages
+//! and every error, including the rejected bodies and queries, as a problem.
+
+pub mod common_ctrl;
+pub mod tag_ctrl;
+pub mod todo_ctrl;
+pub mod todo_list_ctrl;
+pub mod trash_ctrl;
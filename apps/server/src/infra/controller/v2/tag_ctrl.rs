@@ -0,0 +1,107 @@
+use axum::{extract::State, http::StatusCode, Json};
+
+use crate::{
+	domain::entity::tag::Tag,
+	infra::{
+		api_response::{ApiResult, Page},
+		controller::extract::{JsonBody, PathParam},
+		server::AppState,
+	},
+	usecase::{
+		create_tag_usecase::{self, CreateTagParams},
+		delete_tag_usecase, get_all_tags_usecase,
+		rename_tag_usecase::{self, RenameTagParams},
+	},
+};
+
+#[utoipa::path(
+	tag = "Tag",
+	post,
+	path = "/api/v2/tags",
+	request_body = CreateTagParams,
+	responses(
+		(status = 201, description = "Tag created successfully", body = Tag),
+		(status = 400, description = "Invalid body or empty tag name", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Tag already exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn create_tag_ctrl(
+	State(app_state): State<AppState>,
+	JsonBody(params): JsonBody<CreateTagParams>,
+) -> ApiResult<(StatusCode, Json<Tag>)> {
+	let create_tag_usecase = create_tag_usecase::CreateTagUsecase::new(&app_state.tag_repo);
+
+	let tag = create_tag_usecase.exec(params).await?;
+
+	Ok((StatusCode::CREATED, Json(tag)))
+}
+
+#[utoipa::path(
+	tag = "Tag",
+	get,
+	path = "/api/v2/tags",
+	responses(
+		(status = 200, description = "Tags retrieved successfully", body = TagPage),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_all_tags_ctrl(State(app_state): State<AppState>) -> ApiResult<Json<Page<Tag>>> {
+	let get_all_tags_usecase = get_all_tags_usecase::GetAllTagsUsecase::new(&app_state.tag_repo);
+
+	let tags = get_all_tags_usecase.exec().await?;
+
+	Ok(Json(Page::all(tags)))
+}
+
+#[utoipa::path(
+	tag = "Tag",
+	patch,
+	path = "/api/v2/tags/{id}",
+	params(
+		("id" = String, Path, description = "Tag id"),
+	),
+	request_body = RenameTagParams,
+	responses(
+		(status = 200, description = "Tag renamed successfully", body = Tag),
+		(status = 400, description = "Invalid body or empty tag name", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 404, description = "Tag not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Tag already exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn rename_tag_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	JsonBody(params): JsonBody<RenameTagParams>,
+) -> ApiResult<Json<Tag>> {
+	let rename_tag_usecase = rename_tag_usecase::RenameTagUsecase::new(&app_state.tag_repo);
+
+	let tag = rename_tag_usecase.exec(id, params).await?;
+
+	Ok(Json(tag))
+}
+
+#[utoipa::path(
+	tag = "Tag",
+	delete,
+	path = "/api/v2/tags/{id}",
+	params(
+		("id" = String, Path, description = "Tag id"),
+	),
+	responses(
+		(status = 204, description = "Tag deleted successfully"),
+		(status = 404, description = "Tag not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn delete_tag_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResult<StatusCode> {
+	let delete_tag_usecase = delete_tag_usecase::DeleteTagUsecase::new(&app_state.tag_repo);
+
+	delete_tag_usecase.exec(id).await?;
+
+	Ok(StatusCode::NO_CONTENT)
+}
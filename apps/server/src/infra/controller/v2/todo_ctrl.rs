@@ -0,0 +1,332 @@
+use axum::{
+	body::Bytes,
+	extract::State,
+	http::{HeaderMap, StatusCode},
+	Json,
+};
+
+use crate::{
+	domain::{
+		entity::todo::{Todo, TodoCan, TodoOperation, TodoTree, TodoView},
+		exception::TodoException,
+	},
+	infra::{
+		api_response::{ApiResult, BatchOperationResult, ETag, Page, TodoCount},
+		controller::{
+			extract::{JsonBody, PathParam, QueryParams},
+			helper::extract_if_match_from_header,
+			todo_ctrl::{self, CountTodosQuery, DeleteManyTodosQuery, GetAllTodosQuery},
+			todos_views_ctrl::UpdateTodoTmpl,
+		},
+		server::AppState,
+	},
+	usecase::{
+		batch_todos_usecase::{BatchOutcome, BatchTodosParams},
+		clear_completed_todos_usecase, count_todos_usecase, create_subtask_usecase,
+		create_todo_usecase::{self, CreateTodoParams},
+		delete_todo_usecase,
+		get_all_todos_usecase::{self, DEFAULT_PAGE_SIZE},
+		get_todo_tree_usecase,
+		move_todo_usecase::{self, MoveTodoParams},
+	},
+};
+
+#[utoipa::path(
+	tag = "Todo",
+	post,
+	path = "/api/v2/todos",
+	request_body = CreateTodoParams,
+	responses(
+		(status = 201, description = "Todo item created successfully", body = Todo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 400, description = "Invalid body or recurrence rule", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Todo already exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn create_todo_ctrl(
+	State(app_state): State<AppState>,
+	JsonBody(params): JsonBody<CreateTodoParams>,
+) -> ApiResult<(StatusCode, ETag, Json<Todo>)> {
+	let create_todo_usecase = create_todo_usecase::CreateTodoUsecase::new(&app_state.todo_repo);
+
+	let todo = create_todo_usecase.exec(params).await?;
+
+	todo_ctrl::broadcast_outcome(&app_state, BatchOutcome::Created(todo.clone()));
+
+	Ok((StatusCode::CREATED, ETag(todo.version), Json(todo)))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	post,
+	path = "/api/v2/todos/{id}/subtasks",
+	params(
+		("id" = String, Path, description = "Parent todo item id"),
+	),
+	request_body = CreateTodoParams,
+	responses(
+		(status = 201, description = "Subtask created successfully", body = Todo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 400, description = "Invalid body or recurrence rule", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 404, description = "Parent todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn create_subtask_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	JsonBody(params): JsonBody<CreateTodoParams>,
+) -> ApiResult<(StatusCode, ETag, Json<Todo>)> {
+	let create_subtask_usecase =
+		create_subtask_usecase::CreateSubtaskUsecase::new(&app_state.todo_repo);
+
+	let todo = create_subtask_usecase.exec(id, params).await?;
+
+	todo_ctrl::broadcast_outcome(&app_state, BatchOutcome::Created(todo.clone()));
+
+	Ok((StatusCode::CREATED, ETag(todo.version), Json(todo)))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	get,
+	path = "/api/v2/todos",
+	params(GetAllTodosQuery),
+	responses(
+		(status = 200, description = "Page of todo items retrieved successfully", body = TodoPage),
+		(status = 400, description = "Invalid todo status or query", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_all_todos_ctrl(
+	State(app_state): State<AppState>,
+	QueryParams(query): QueryParams<GetAllTodosQuery>,
+) -> ApiResult<Json<Page<Todo>>> {
+	let get_all_todos_usecase =
+		get_all_todos_usecase::GetAllTodosUsecase::new(&app_state.todo_repo);
+	let count_todos_usecase = count_todos_usecase::CountTodosUsecase::new(&app_state.todo_repo);
+
+	let params = query.params(None);
+
+	let total = count_todos_usecase.exec(&params).await?;
+	let page = get_all_todos_usecase.exec(&params, Some(DEFAULT_PAGE_SIZE)).await?;
+
+	Ok(Json(Page {
+		items: page.todos,
+		total,
+		next_cursor: page.next_cursor,
+	}))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	get,
+	path = "/api/v2/todos/count",
+	params(CountTodosQuery),
+	responses(
+		(status = 200, description = "Number of todo items matching the query", body = TodoCount),
+		(status = 400, description = "Invalid todo status or query", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn count_todos_ctrl(
+	State(app_state): State<AppState>,
+	QueryParams(query): QueryParams<CountTodosQuery>,
+) -> ApiResult<Json<TodoCount>> {
+	let count_todos_usecase = count_todos_usecase::CountTodosUsecase::new(&app_state.todo_repo);
+
+	let total = count_todos_usecase.exec(&query.params()).await?;
+
+	Ok(Json(TodoCount { total }))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	get,
+	path = "/api/v2/todos/{id}",
+	params(
+		("id" = String, Path, description = "Todo item id"),
+	),
+	responses(
+		(status = 200, description = "Todo item retrieved with its subtasks successfully", body = TodoTree, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 404, description = "Todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResult<(ETag, Json<TodoTree>)> {
+	let get_todo_tree_usecase =
+		get_todo_tree_usecase::GetTodoTreeUsecase::new(&app_state.todo_repo);
+
+	let tree = get_todo_tree_usecase.exec(id).await?;
+
+	Ok((ETag(tree.todo.version), Json(tree)))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	patch,
+	path = "/api/v2/todos/{id}",
+	params(
+		("id" = String, Path, description = "Todo item id"),
+		("If-Match" = Option<String>, Header, description = "ETag the todo item must match"),
+	),
+	request_body(content = UpdateTodoParams, description = "Changes of the todo item, or a `application/merge-patch+json` or `application/json-patch+json` patch of its `description` and `done` fields"),
+	responses(
+		(status = 200, description = "Todo item updated successfully", body = Todo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 400, description = "Invalid body, recurrence rule or patch", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 404, description = "Todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Todo item was modified meanwhile", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 412, description = "Todo item version does not match If-Match", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 415, description = "Unsupported content type", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn update_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	headers: HeaderMap,
+	body: Bytes,
+) -> ApiResult<(ETag, Json<Todo>)> {
+	let todo = todo_ctrl::update_todo(&app_state, id, &headers, body).await?;
+
+	Ok((ETag(todo.version), Json(todo)))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	delete,
+	path = "/api/v2/todos/{id}",
+	params(
+		("id" = String, Path, description = "Todo item id"),
+		("If-Match" = Option<String>, Header, description = "ETag the todo item must match"),
+	),
+	responses(
+		(status = 204, description = "Todo item and its subtasks moved to the trash successfully"),
+		(status = 404, description = "Todo item not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 412, description = "Todo item version does not match If-Match", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn delete_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	headers: HeaderMap,
+) -> ApiResult<StatusCode> {
+	let delete_todo_usecase = delete_todo_usecase::DeleteTodoUsecase::new(&app_state.todo_repo);
+
+	let if_match = extract_if_match_from_header(&headers);
+	let output = delete_todo_usecase.exec(id, if_match.as_deref()).await?;
+
+	todo_ctrl::broadcast_outcome(&app_state, BatchOutcome::Deleted(output));
+
+	Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	delete,
+	path = "/api/v2/todos",
+	params(DeleteManyTodosQuery),
+	responses(
+		(status = 200, description = "Completed todo items moved to the trash successfully", body = TodoPage),
+		(status = 400, description = "Invalid todo status", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn delete_many_todos_ctrl(
+	State(app_state): State<AppState>,
+	QueryParams(query): QueryParams<DeleteManyTodosQuery>,
+) -> ApiResult<Json<Page<Todo>>> {
+	if query.status.as_deref() != Some("done") {
+		return Err(TodoException::InvalidStatus.into());
+	}
+
+	let clear_completed_todos_usecase =
+		clear_completed_todos_usecase::ClearCompletedTodosUsecase::new(&app_state.todo_repo);
+
+	let todos = clear_completed_todos_usecase.exec(None).await?;
+
+	for todo in todos.iter() {
+		app_state.broadcast_update_to_view(UpdateTodoTmpl {
+			todo: TodoView::new(todo.clone(), TodoOperation::Delete, TodoCan::Read),
+		});
+	}
+
+	Ok(Json(Page::all(todos)))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	post,
+	path = "/api/v2/todos/{id}/move",
+	params(
+		("id" = String, Path, description = "Todo item id"),
+		("If-Match" = Option<String>, Header, description = "ETag the todo item must match"),
+	),
+	request_body = MoveTodoParams,
+	responses(
+		(status = 200, description = "Todo item moved successfully", body = Todo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 400, description = "Invalid body, or neighbours are missing, not in order or not siblings of the todo", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 404, description = "Todo item or neighbour not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Todo item was modified meanwhile", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 412, description = "Todo item version does not match If-Match", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn move_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	headers: HeaderMap,
+	JsonBody(params): JsonBody<MoveTodoParams>,
+) -> ApiResult<(ETag, Json<Todo>)> {
+	let move_todo_usecase = move_todo_usecase::MoveTodoUsecase::new(&app_state.todo_repo);
+
+	let if_match = extract_if_match_from_header(&headers);
+	let todo = move_todo_usecase.exec(id, params, if_match.as_deref()).await?;
+
+	app_state.broadcast_update_to_view(UpdateTodoTmpl {
+		todo: TodoView::new(todo.clone(), TodoOperation::Move, TodoCan::Write),
+	});
+
+	Ok((ETag(todo.version), Json(todo)))
+}
+
+#[utoipa::path(
+	tag = "Todo",
+	post,
+	path = "/api/v2/todos/batch",
+	request_body = BatchTodosParams,
+	responses(
+		(status = 200, description = "Operations applied, each with its own status in best effort mode", body = [BatchOperationResult]),
+		(status = 400, description = "Invalid body, empty or too large batch, or invalid operation of an atomic batch", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 404, description = "Todo item of an atomic batch not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 409, description = "Todo item of an atomic batch was modified meanwhile", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 412, description = "Todo item of an atomic batch is not at the given version", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn batch_todos_ctrl(
+	State(app_state): State<AppState>,
+	JsonBody(params): JsonBody<BatchTodosParams>,
+) -> ApiResult<Json<Vec<BatchOperationResult>>> {
+	let results = todo_ctrl::batch_todos(&app_state, params)
+		.await?
+		.into_iter()
+		.map(|result| match result.error {
+			Some(error) => {
+				let error = error.into_not_found();
+
+				BatchOperationResult {
+					status: error.status,
+					error: Some(error),
+					..result
+				}
+			},
+			None => result,
+		})
+		.collect();
+
+	Ok(Json(results))
+}
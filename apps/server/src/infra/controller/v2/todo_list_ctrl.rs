@@ -0,0 +1,219 @@
+use axum::{extract::State, http::StatusCode, Json};
+
+use crate::{
+	domain::entity::{todo::Todo, todo_list::TodoList},
+	infra::{
+		api_response::{ApiResult, ETag, Page},
+		controller::{
+			extract::{JsonBody, PathParam, QueryParams},
+			todo_ctrl::{self, GetAllTodosQuery},
+		},
+		server::AppState,
+	},
+	usecase::{
+		batch_todos_usecase::BatchOutcome,
+		count_todos_usecase,
+		create_todo_list_usecase::{self, CreateTodoListParams},
+		create_todo_usecase::{self, CreateTodoParams},
+		delete_todo_list_usecase, get_all_todo_lists_usecase,
+		get_all_todos_usecase::{self, DEFAULT_PAGE_SIZE},
+		get_todo_list_usecase,
+		rename_todo_list_usecase::{self, RenameTodoListParams},
+	},
+};
+
+#[utoipa::path(
+	tag = "List",
+	post,
+	path = "/api/v2/lists",
+	request_body = CreateTodoListParams,
+	responses(
+		(status = 201, description = "List created successfully", body = TodoList),
+		(status = 400, description = "Invalid body or empty list name", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn create_todo_list_ctrl(
+	State(app_state): State<AppState>,
+	JsonBody(params): JsonBody<CreateTodoListParams>,
+) -> ApiResult<(StatusCode, Json<TodoList>)> {
+	let create_todo_list_usecase =
+		create_todo_list_usecase::CreateTodoListUsecase::new(&app_state.todo_list_repo);
+
+	let list = create_todo_list_usecase.exec(params).await?;
+
+	Ok((StatusCode::CREATED, Json(list)))
+}
+
+#[utoipa::path(
+	tag = "List",
+	get,
+	path = "/api/v2/lists",
+	responses(
+		(status = 200, description = "Lists retrieved successfully", body = TodoListPage),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_all_todo_lists_ctrl(
+	State(app_state): State<AppState>,
+) -> ApiResult<Json<Page<TodoList>>> {
+	let get_all_todo_lists_usecase =
+		get_all_todo_lists_usecase::GetAllTodoListsUsecase::new(&app_state.todo_list_repo);
+
+	let lists = get_all_todo_lists_usecase.exec().await?;
+
+	Ok(Json(Page::all(lists)))
+}
+
+#[utoipa::path(
+	tag = "List",
+	get,
+	path = "/api/v2/lists/{id}",
+	params(
+		("id" = String, Path, description = "List id"),
+	),
+	responses(
+		(status = 200, description = "List retrieved successfully", body = TodoList),
+		(status = 404, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_todo_list_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResult<Json<TodoList>> {
+	let get_todo_list_usecase =
+		get_todo_list_usecase::GetTodoListUsecase::new(&app_state.todo_list_repo);
+
+	let list = get_todo_list_usecase.exec(id).await?;
+
+	Ok(Json(list))
+}
+
+#[utoipa::path(
+	tag = "List",
+	patch,
+	path = "/api/v2/lists/{id}",
+	params(
+		("id" = String, Path, description = "List id"),
+	),
+	request_body = RenameTodoListParams,
+	responses(
+		(status = 200, description = "List renamed successfully", body = TodoList),
+		(status = 400, description = "Invalid body or empty list name", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 404, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn rename_todo_list_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	JsonBody(params): JsonBody<RenameTodoListParams>,
+) -> ApiResult<Json<TodoList>> {
+	let rename_todo_list_usecase =
+		rename_todo_list_usecase::RenameTodoListUsecase::new(&app_state.todo_list_repo);
+
+	let list = rename_todo_list_usecase.exec(id, params).await?;
+
+	Ok(Json(list))
+}
+
+#[utoipa::path(
+	tag = "List",
+	delete,
+	path = "/api/v2/lists/{id}",
+	params(
+		("id" = String, Path, description = "List id"),
+	),
+	responses(
+		(status = 204, description = "List and its todo items deleted successfully"),
+		(status = 404, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn delete_todo_list_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResult<StatusCode> {
+	let delete_todo_list_usecase =
+		delete_todo_list_usecase::DeleteTodoListUsecase::new(&app_state.todo_list_repo);
+
+	delete_todo_list_usecase.exec(id).await?;
+
+	Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+	tag = "List",
+	get,
+	path = "/api/v2/lists/{id}/todos",
+	params(
+		("id" = String, Path, description = "List id"),
+		GetAllTodosQuery,
+	),
+	responses(
+		(status = 200, description = "Page of todo items of the list retrieved successfully", body = TodoPage),
+		(status = 400, description = "Invalid todo status or query", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 404, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_list_todos_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	QueryParams(query): QueryParams<GetAllTodosQuery>,
+) -> ApiResult<Json<Page<Todo>>> {
+	let get_todo_list_usecase =
+		get_todo_list_usecase::GetTodoListUsecase::new(&app_state.todo_list_repo);
+	let get_all_todos_usecase =
+		get_all_todos_usecase::GetAllTodosUsecase::new(&app_state.todo_repo);
+	let count_todos_usecase = count_todos_usecase::CountTodosUsecase::new(&app_state.todo_repo);
+
+	let list = get_todo_list_usecase.exec(id).await?;
+
+	let params = query.params(Some(list.id));
+
+	let total = count_todos_usecase.exec(&params).await?;
+	let page = get_all_todos_usecase.exec(&params, Some(DEFAULT_PAGE_SIZE)).await?;
+
+	Ok(Json(Page {
+		items: page.todos,
+		total,
+		next_cursor: page.next_cursor,
+	}))
+}
+
+#[utoipa::path(
+	tag = "List",
+	post,
+	path = "/api/v2/lists/{id}/todos",
+	params(
+		("id" = String, Path, description = "List id"),
+	),
+	request_body = CreateTodoParams,
+	responses(
+		(status = 201, description = "Todo item created in the list successfully", body = Todo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 400, description = "Invalid body or recurrence rule", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 404, description = "List not exists", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn create_list_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+	JsonBody(mut params): JsonBody<CreateTodoParams>,
+) -> ApiResult<(StatusCode, ETag, Json<Todo>)> {
+	let get_todo_list_usecase =
+		get_todo_list_usecase::GetTodoListUsecase::new(&app_state.todo_list_repo);
+	let create_todo_usecase = create_todo_usecase::CreateTodoUsecase::new(&app_state.todo_repo);
+
+	let list = get_todo_list_usecase.exec(id).await?;
+
+	params.list_id = Some(list.id);
+
+	let todo = create_todo_usecase.exec(params).await?;
+
+	todo_ctrl::broadcast_outcome(&app_state, BatchOutcome::Created(todo.clone()));
+
+	Ok((StatusCode::CREATED, ETag(todo.version), Json(todo)))
+}
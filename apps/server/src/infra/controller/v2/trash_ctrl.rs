@@ -0,0 +1,56 @@
+use axum::{extract::State, Json};
+
+use crate::{
+	domain::entity::todo::{Todo, TodoCan, TodoOperation, TodoView},
+	infra::{
+		api_response::{ApiResult, ETag, Page},
+		controller::{extract::PathParam, todos_views_ctrl::UpdateTodoTmpl},
+		server::AppState,
+	},
+	usecase::{get_trash_usecase, restore_todo_usecase},
+};
+
+#[utoipa::path(
+	tag = "Trash",
+	get,
+	path = "/api/v2/trash",
+	responses(
+		(status = 200, description = "Deleted todo items retrieved successfully", body = TodoPage),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn get_trash_ctrl(State(app_state): State<AppState>) -> ApiResult<Json<Page<Todo>>> {
+	let get_trash_usecase = get_trash_usecase::GetTrashUsecase::new(&app_state.todo_repo);
+
+	let todos = get_trash_usecase.exec().await?;
+
+	Ok(Json(Page::all(todos)))
+}
+
+#[utoipa::path(
+	tag = "Trash",
+	post,
+	path = "/api/v2/todos/{id}/restore",
+	params(
+		("id" = String, Path, description = "Deleted todo item id"),
+	),
+	responses(
+		(status = 200, description = "Todo item restored successfully", body = Todo, headers(("ETag" = String, description = "Version of the todo item"))),
+		(status = 404, description = "Todo item not in the trash", body = ProblemDetails, content_type = "application/problem+json"),
+		(status = 500, description = "Internal Server Error", body = ProblemDetails, content_type = "application/problem+json")
+	)
+)]
+pub async fn restore_todo_ctrl(
+	State(app_state): State<AppState>,
+	PathParam(id): PathParam<String>,
+) -> ApiResult<(ETag, Json<Todo>)> {
+	let restore_todo_usecase = restore_todo_usecase::RestoreTodoUsecase::new(&app_state.todo_repo);
+
+	let todo = restore_todo_usecase.exec(id).await?;
+
+	app_state.broadcast_update_to_view(UpdateTodoTmpl {
+		todo: TodoView::new(todo.clone(), TodoOperation::Restore, TodoCan::Write),
+	});
+
+	Ok((ETag(todo.version), Json(todo)))
+}
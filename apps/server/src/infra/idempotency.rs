@@ -0,0 +1,130 @@
+use axum::{
+	body::{to_bytes, Body},
+	extract::{Request, State},
+	http::{request::Parts, HeaderName, HeaderValue, Method, StatusCode},
+	middleware::Next,
+	response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+	domain::entity::idempotency_key::StoredResponse,
+	usecase::idempotent_request_usecase::{IdempotentRequest, IdempotentRequestUsecase},
+};
+
+use super::{api_response::ApiResponseError, server::AppState};
+
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Set on the responses replayed to the retries of a request.
+const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// Largest request body fingerprinted, as for the JSON bodies extracted by axum.
+const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// Handles the `POST` and `PATCH` requests sent with an `Idempotency-Key` once: their
+/// retries get the response to the first request, until the key expires.
+///
+/// Server errors are not stored, a retry then handles the request again.
+pub async fn idempotent_request(
+	State(app_state): State<AppState>,
+	request: Request,
+	next: Next,
+) -> Response {
+	if !matches!(*request.method(), Method::POST | Method::PATCH) {
+		return next.run(request).await;
+	}
+	let Some(key) = request.headers().get(IDEMPOTENCY_KEY) else {
+		return next.run(request).await;
+	};
+	let key = String::from_utf8_lossy(key.as_bytes()).into_owned();
+
+	let (parts, body) = request.into_parts();
+	let Ok(body) = to_bytes(body, MAX_BODY_SIZE).await else {
+		return ApiResponseError::new(
+			StatusCode::PAYLOAD_TOO_LARGE,
+			"payload-too-large",
+			"Request body is too large",
+		)
+		.into_response();
+	};
+
+	let idempotent_request_usecase = IdempotentRequestUsecase::new(&app_state.idempotency_repo);
+
+	match idempotent_request_usecase
+		.exec(
+			key.clone(),
+			fingerprint(&parts, &body),
+			app_state.idempotency_ttl,
+		)
+		.await
+	{
+		Ok(IdempotentRequest::New) => (),
+		Ok(IdempotentRequest::Replay(response)) => return replay(response),
+		Err(err) => return ApiResponseError::from(err).into_response(),
+	}
+
+	let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+	if response.status().is_server_error() {
+		release(&idempotent_request_usecase, key).await;
+		return response;
+	}
+
+	let (parts, body) = response.into_parts();
+	let Ok(body) = to_bytes(body, usize::MAX).await else {
+		release(&idempotent_request_usecase, key).await;
+		return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+	};
+
+	let stored = StoredResponse {
+		status: parts.status.as_u16(),
+		headers: parts
+			.headers
+			.iter()
+			.filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+			.collect(),
+		body: body.to_vec(),
+	};
+
+	if let Err(err) = idempotent_request_usecase.complete(key, stored).await {
+		tracing::error!("Error storing the idempotent response: {:?}", err);
+	}
+
+	Response::from_parts(parts, Body::from(body))
+}
+
+async fn release(idempotent_request_usecase: &IdempotentRequestUsecase<'_>, key: String) {
+	if let Err(err) = idempotent_request_usecase.release(key).await {
+		tracing::error!("Error releasing the idempotency key: {:?}", err);
+	}
+}
+
+/// Hash of the method, path and body, telling apart the requests sharing a key.
+fn fingerprint(parts: &Parts, body: &[u8]) -> String {
+	let mut hasher = Sha256::new();
+
+	hasher.update(parts.method.as_str());
+	hasher.update(b" ");
+	hasher.update(parts.uri.path());
+	hasher.update(b"\n");
+	hasher.update(body);
+
+	format!("{:x}", hasher.finalize())
+}
+
+fn replay(stored: StoredResponse) -> Response {
+	let mut response = Response::new(Body::from(stored.body));
+
+	*response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+	for (name, value) in stored.headers {
+		if let (Ok(name), Ok(value)) = (name.parse::<HeaderName>(), value.parse::<HeaderValue>()) {
+			response.headers_mut().append(name, value);
+		}
+	}
+	response
+		.headers_mut()
+		.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+
+	response
+}
@@ -0,0 +1,7 @@
+use sqlx::migrate::Migrator;
+
+/// Schema of the Postgres database, applied by the server on boot and by `bin/migrate`.
+pub static PG_MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Schema of the SQLite database, applied by the server on boot and by `bin/migrate`.
+pub static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
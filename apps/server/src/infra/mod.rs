@@ -1,9 +1,13 @@
 pub mod api_doc;
 pub mod api_response;
+pub mod api_version;
 pub mod app_error;
 pub mod controller;
+pub mod idempotency;
+pub mod migrations;
 pub mod pg;
 pub mod repository;
 pub mod routes;
 pub mod server;
+pub mod sqlite;
 pub mod tracing;
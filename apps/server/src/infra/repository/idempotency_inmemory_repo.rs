@@ -0,0 +1,71 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use axum::async_trait;
+
+use crate::domain::{
+	entity::idempotency_key::{IdempotencyKey, StoredResponse},
+	repository::idempotency_repository::{
+		ClaimKeyError, CompleteKeyError, IdempotencyRepository, PurgeKeysError, ReleaseKeyError,
+	},
+};
+
+#[derive(Default)]
+pub struct IdempotencyInMemoryRepository {
+	keys: Mutex<HashMap<String, IdempotencyKey>>,
+}
+
+impl IdempotencyInMemoryRepository {
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+#[async_trait]
+impl IdempotencyRepository for IdempotencyInMemoryRepository {
+	async fn claim_key(
+		&self,
+		key: IdempotencyKey,
+	) -> Result<Option<IdempotencyKey>, ClaimKeyError> {
+		let mut keys = self.keys.lock().unwrap();
+
+		match keys.get(&key.key) {
+			Some(existing) if existing.expires_at > chrono::Utc::now() => {
+				Ok(Some(existing.clone()))
+			},
+			_ => {
+				keys.insert(key.key.clone(), key);
+				Ok(None)
+			},
+		}
+	}
+
+	async fn complete_key(
+		&self,
+		key: String,
+		response: StoredResponse,
+	) -> Result<(), CompleteKeyError> {
+		if let Some(key) = self.keys.lock().unwrap().get_mut(&key) {
+			key.response = Some(response);
+		}
+
+		Ok(())
+	}
+
+	async fn release_key(&self, key: String) -> Result<(), ReleaseKeyError> {
+		self.keys.lock().unwrap().remove(&key);
+
+		Ok(())
+	}
+
+	async fn purge_expired_keys(
+		&self,
+		now: chrono::DateTime<chrono::Utc>,
+	) -> Result<u64, PurgeKeysError> {
+		let mut keys = self.keys.lock().unwrap();
+		let count = keys.len();
+
+		keys.retain(|_, key| key.expires_at > now);
+
+		Ok((count - keys.len()) as u64)
+	}
+}
@@ -0,0 +1,154 @@
+use axum::async_trait;
+use sqlx::types::Json;
+use tracing::instrument;
+
+use crate::domain::{
+	entity::idempotency_key::{IdempotencyKey, StoredResponse},
+	repository::idempotency_repository::{
+		ClaimKeyError, CompleteKeyError, IdempotencyRepository, PurgeKeysError, ReleaseKeyError,
+	},
+};
+
+#[derive(Debug)]
+pub struct IdempotencyPgRepository<'a> {
+	pool: &'a sqlx::Pool<sqlx::Postgres>,
+}
+
+impl<'a> IdempotencyPgRepository<'a> {
+	pub fn new(pool: &'a sqlx::Pool<sqlx::Postgres>) -> Self {
+		Self { pool }
+	}
+}
+
+#[derive(sqlx::FromRow)]
+struct IdempotencyKeyRow {
+	key: String,
+	fingerprint: String,
+	status: Option<i32>,
+	headers: Option<Json<Vec<(String, String)>>>,
+	body: Option<Vec<u8>>,
+	expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<IdempotencyKeyRow> for IdempotencyKey {
+	fn from(row: IdempotencyKeyRow) -> Self {
+		let response = match (row.status, row.headers, row.body) {
+			(Some(status), Some(Json(headers)), Some(body)) => Some(StoredResponse {
+				status: status as u16,
+				headers,
+				body,
+			}),
+			_ => None,
+		};
+
+		Self {
+			key: row.key,
+			fingerprint: row.fingerprint,
+			response,
+			expires_at: row.expires_at,
+		}
+	}
+}
+
+#[async_trait]
+impl<'a> IdempotencyRepository for IdempotencyPgRepository<'a> {
+	#[instrument(name = "sqlx::claim_key")]
+	async fn claim_key(
+		&self,
+		key: IdempotencyKey,
+	) -> Result<Option<IdempotencyKey>, ClaimKeyError> {
+		// the key may be released between the insert and the select, then claimed again
+		loop {
+			let claimed = sqlx::query(
+				"INSERT INTO idempotency_keys (key, fingerprint, expires_at) VALUES ($1, $2, $3) \
+				 ON CONFLICT (key) DO UPDATE SET fingerprint = excluded.fingerprint, \
+				 status = NULL, headers = NULL, body = NULL, expires_at = excluded.expires_at \
+				 WHERE idempotency_keys.expires_at <= $4",
+			)
+			.bind(&key.key)
+			.bind(&key.fingerprint)
+			.bind(key.expires_at)
+			.bind(chrono::Utc::now())
+			.execute(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error claiming idempotency key: {:?}", err);
+				ClaimKeyError::DBInternalError
+			})?
+			.rows_affected()
+				== 1;
+
+			if claimed {
+				return Ok(None);
+			}
+
+			let existing = sqlx::query_as::<_, IdempotencyKeyRow>(
+				"SELECT * FROM idempotency_keys WHERE key = $1",
+			)
+			.bind(&key.key)
+			.fetch_optional(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error finding idempotency key: {:?}", err);
+				ClaimKeyError::DBInternalError
+			})?;
+
+			if let Some(existing) = existing {
+				return Ok(Some(existing.into()));
+			}
+		}
+	}
+
+	#[instrument(name = "sqlx::complete_key", skip(response))]
+	async fn complete_key(
+		&self,
+		key: String,
+		response: StoredResponse,
+	) -> Result<(), CompleteKeyError> {
+		sqlx::query(
+			"UPDATE idempotency_keys SET status = $1, headers = $2, body = $3 WHERE key = $4",
+		)
+		.bind(response.status as i32)
+		.bind(Json(response.headers))
+		.bind(response.body)
+		.bind(key)
+		.execute(self.pool)
+		.await
+		.map_err(|err| {
+			tracing::error!("Error completing idempotency key: {:?}", err);
+			CompleteKeyError::DBInternalError
+		})?;
+
+		Ok(())
+	}
+
+	#[instrument(name = "sqlx::release_key")]
+	async fn release_key(&self, key: String) -> Result<(), ReleaseKeyError> {
+		sqlx::query("DELETE FROM idempotency_keys WHERE key = $1")
+			.bind(key)
+			.execute(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error releasing idempotency key: {:?}", err);
+				ReleaseKeyError::DBInternalError
+			})?;
+
+		Ok(())
+	}
+
+	#[instrument(name = "sqlx::purge_expired_keys")]
+	async fn purge_expired_keys(
+		&self,
+		now: chrono::DateTime<chrono::Utc>,
+	) -> Result<u64, PurgeKeysError> {
+		sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= $1")
+			.bind(now)
+			.execute(self.pool)
+			.await
+			.map(|result| result.rows_affected())
+			.map_err(|err| {
+				tracing::error!("Error purging idempotency keys: {:?}", err);
+				PurgeKeysError::DBInternalError
+			})
+	}
+}
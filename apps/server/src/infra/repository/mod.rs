@@ -1,2 +1,16 @@
+pub mod idempotency_inmemory_repo;
+pub mod idempotency_pg_repo;
+pub mod idempotency_sqlite_repo;
+pub mod tag_file_repo;
+pub mod tag_inmemory_repo;
+pub mod tag_pg_repo;
+pub mod tag_sqlite_repo;
+pub mod todo_cached_repo;
+pub mod todo_file_repo;
 pub mod todo_inmemory_repo;
+pub mod todo_list_file_repo;
+pub mod todo_list_inmemory_repo;
+pub mod todo_list_pg_repo;
+pub mod todo_list_sqlite_repo;
 pub mod todo_pg_repo;
+pub mod todo_sqlite_repo;
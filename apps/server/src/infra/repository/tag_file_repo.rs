@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use tokio::sync::Mutex;
+
+use crate::domain::{
+	entity::tag::Tag,
+	repository::tag_repository::{
+		CreateTagError, DeleteTagError, FindManyTagError, FindTagError, TagRepository,
+		UpdateTagError,
+	},
+};
+
+use super::{
+	tag_inmemory_repo::TagInMemoryRepository,
+	todo_file_repo::{todo_changes, todo_states, Log, TodoFileRepository, TodoRecord},
+	todo_inmemory_repo::TodoInMemoryRepository,
+};
+
+/// Tags of a `TodoFileRepository`, persisted to its log along with the todos they rename
+/// or untag.
+pub struct TagFileRepository {
+	memory: TodoInMemoryRepository,
+	tags: TagInMemoryRepository,
+	log: Arc<Mutex<Log>>,
+}
+
+impl TagFileRepository {
+	pub fn new(todo_repo: &TodoFileRepository) -> TagFileRepository {
+		Self {
+			memory: todo_repo.memory.clone(),
+			tags: TagInMemoryRepository::new(&todo_repo.memory),
+			log: todo_repo.log.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl TagRepository for TagFileRepository {
+	async fn create_tag(&self, create_tag: Tag) -> Result<Tag, CreateTagError> {
+		let mut log = self.log.lock().await;
+
+		let tag = self.tags.create_tag(create_tag).await?;
+
+		log.append(&self.memory, &[TodoRecord::CreateTag { tag: tag.clone() }])
+			.map_err(|err| {
+				tracing::error!("Error appending to the todos log: {:?}", err);
+				CreateTagError::DBInternalError
+			})?;
+
+		Ok(tag)
+	}
+
+	async fn find_tag_by_id(&self, id: String) -> Result<Tag, FindTagError> {
+		self.tags.find_tag_by_id(id).await
+	}
+
+	async fn find_many_tags(&self) -> Result<Vec<Tag>, FindManyTagError> {
+		self.tags.find_many_tags().await
+	}
+
+	async fn update_tag(&self, update_tag: Tag) -> Result<Tag, UpdateTagError> {
+		let mut log = self.log.lock().await;
+		let before = todo_states(&self.memory);
+
+		let tag = self.tags.update_tag(update_tag).await?;
+
+		let mut records = vec![TodoRecord::UpdateTag { tag: tag.clone() }];
+		records.extend(todo_changes(&self.memory, before));
+
+		log.append(&self.memory, &records).map_err(|err| {
+			tracing::error!("Error appending to the todos log: {:?}", err);
+			UpdateTagError::DBInternalError
+		})?;
+
+		Ok(tag)
+	}
+
+	async fn delete_tag(&self, id: String) -> Result<(), DeleteTagError> {
+		let mut log = self.log.lock().await;
+		let before = todo_states(&self.memory);
+
+		self.tags.delete_tag(id.clone()).await?;
+
+		let mut records = vec![TodoRecord::DeleteTag { id }];
+		records.extend(todo_changes(&self.memory, before));
+
+		log.append(&self.memory, &records).map_err(|err| {
+			tracing::error!("Error appending to the todos log: {:?}", err);
+			DeleteTagError::DBInternalError
+		})
+	}
+}
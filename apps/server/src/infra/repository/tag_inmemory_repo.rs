@@ -0,0 +1,104 @@
+use std::sync::{Arc, Mutex};
+
+use axum::async_trait;
+
+use crate::domain::{
+	entity::{tag::Tag, todo::Todo},
+	repository::tag_repository::{
+		CreateTagError, DeleteTagError, FindManyTagError, FindTagError, TagRepository,
+		UpdateTagError,
+	},
+};
+
+use super::todo_inmemory_repo::TodoInMemoryRepository;
+
+pub struct TagInMemoryRepository {
+	pub tags: Arc<Mutex<Vec<Tag>>>,
+	pub todos: Arc<Mutex<Vec<Todo>>>,
+	/// Write lock of the todos, a rolled back unit of work would otherwise undo the
+	/// renames and deletes
+	write_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl TagInMemoryRepository {
+	pub fn new(todo_repo: &TodoInMemoryRepository) -> TagInMemoryRepository {
+		Self {
+			tags: todo_repo.tags.clone(),
+			todos: todo_repo.todos.clone(),
+			write_lock: todo_repo.write_lock.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl TagRepository for TagInMemoryRepository {
+	async fn create_tag(&self, create_tag: Tag) -> Result<Tag, CreateTagError> {
+		let mut tags = self.tags.lock().unwrap();
+
+		if tags.iter().any(|tag| tag.name == create_tag.name) {
+			return Err(CreateTagError::AlreadyExists);
+		}
+
+		tags.push(create_tag.clone());
+
+		Ok(create_tag)
+	}
+
+	async fn find_tag_by_id(&self, id: String) -> Result<Tag, FindTagError> {
+		let tags = self.tags.lock().unwrap();
+
+		let tag = tags.iter().find(|tag: &&Tag| tag.id == id).ok_or(FindTagError::NotFound)?;
+
+		Ok(tag.clone())
+	}
+
+	async fn find_many_tags(&self) -> Result<Vec<Tag>, FindManyTagError> {
+		let mut tags: Vec<Tag> = self.tags.lock().unwrap().clone();
+
+		tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+		Ok(tags)
+	}
+
+	async fn update_tag(&self, update_tag: Tag) -> Result<Tag, UpdateTagError> {
+		let _write = self.write_lock.lock().await;
+		let mut todos = self.todos.lock().unwrap();
+		let mut tags = self.tags.lock().unwrap();
+
+		if tags.iter().any(|tag| tag.name == update_tag.name && tag.id != update_tag.id) {
+			return Err(UpdateTagError::AlreadyExists);
+		}
+
+		let index = tags
+			.iter()
+			.position(|tag: &Tag| tag.id == update_tag.id)
+			.ok_or(UpdateTagError::NotFound)?;
+
+		let previous_name = std::mem::replace(&mut tags[index], update_tag.clone()).name;
+
+		for todo in todos.iter_mut().filter(|todo| todo.tags.contains(&previous_name)) {
+			todo.tags.retain(|name| *name != previous_name);
+			todo.tags.push(update_tag.name.clone());
+			todo.tags.sort();
+		}
+
+		Ok(update_tag)
+	}
+
+	async fn delete_tag(&self, id: String) -> Result<(), DeleteTagError> {
+		let _write = self.write_lock.lock().await;
+		let mut todos = self.todos.lock().unwrap();
+		let mut tags = self.tags.lock().unwrap();
+
+		let index =
+			tags.iter().position(|tag: &Tag| tag.id == id).ok_or(DeleteTagError::NotFound)?;
+
+		let deleted = tags.remove(index);
+
+		for todo in todos.iter_mut() {
+			todo.tags.retain(|name| *name != deleted.name);
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,105 @@
+use axum::async_trait;
+use tracing::instrument;
+
+use crate::domain::{
+	entity::tag::Tag,
+	repository::tag_repository::{
+		CreateTagError, DeleteTagError, FindManyTagError, FindTagError, TagRepository,
+		UpdateTagError,
+	},
+};
+
+#[derive(Debug)]
+pub struct TagPgRepository<'a> {
+	pool: &'a sqlx::Pool<sqlx::Postgres>,
+}
+
+impl<'a> TagPgRepository<'a> {
+	pub fn new(pool: &'a sqlx::Pool<sqlx::Postgres>) -> Self {
+		Self { pool }
+	}
+}
+
+fn is_unique_violation(err: &sqlx::Error) -> bool {
+	err.as_database_error().map(|err| err.is_unique_violation()).unwrap_or(false)
+}
+
+#[async_trait]
+impl<'a> TagRepository for TagPgRepository<'a> {
+	#[instrument(name = "sqlx::create_tag")]
+	async fn create_tag(&self, tag: Tag) -> Result<Tag, CreateTagError> {
+		sqlx::query_as::<_, Tag>(
+			"INSERT INTO tags (id, name, created_at) VALUES ($1, $2, $3) RETURNING *",
+		)
+		.bind(tag.id)
+		.bind(tag.name)
+		.bind(tag.created_at)
+		.fetch_one(self.pool)
+		.await
+		.map_err(|err| match is_unique_violation(&err) {
+			true => CreateTagError::AlreadyExists,
+			false => {
+				tracing::error!("Error creating tag: {:?}", err);
+				CreateTagError::DBInternalError
+			},
+		})
+	}
+
+	#[instrument(name = "sqlx::find_tag_by_id")]
+	async fn find_tag_by_id(&self, id: String) -> Result<Tag, FindTagError> {
+		sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE id = $1")
+			.bind(id)
+			.fetch_optional(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error finding tag: {:?}", err);
+				FindTagError::DBInternalError
+			})?
+			.ok_or(FindTagError::NotFound)
+	}
+
+	#[instrument(name = "sqlx::find_many_tags")]
+	async fn find_many_tags(&self) -> Result<Vec<Tag>, FindManyTagError> {
+		sqlx::query_as::<_, Tag>("SELECT * FROM tags ORDER BY name")
+			.fetch_all(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error finding tags: {:?}", err);
+				FindManyTagError::DBInternalError
+			})
+	}
+
+	#[instrument(name = "sqlx::update_tag")]
+	async fn update_tag(&self, tag: Tag) -> Result<Tag, UpdateTagError> {
+		sqlx::query_as::<_, Tag>("UPDATE tags SET name = $1 WHERE id = $2 RETURNING *")
+			.bind(tag.name)
+			.bind(tag.id)
+			.fetch_optional(self.pool)
+			.await
+			.map_err(|err| match is_unique_violation(&err) {
+				true => UpdateTagError::AlreadyExists,
+				false => {
+					tracing::error!("Error updating tag: {:?}", err);
+					UpdateTagError::DBInternalError
+				},
+			})?
+			.ok_or(UpdateTagError::NotFound)
+	}
+
+	#[instrument(name = "sqlx::delete_tag")]
+	async fn delete_tag(&self, id: String) -> Result<(), DeleteTagError> {
+		let result = sqlx::query("DELETE FROM tags WHERE id = $1")
+			.bind(id)
+			.execute(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error deleting tag: {:?}", err);
+				DeleteTagError::DBInternalError
+			})?;
+
+		match result.rows_affected() {
+			0 => Err(DeleteTagError::NotFound),
+			_ => Ok(()),
+		}
+	}
+}
@@ -0,0 +1,374 @@
+use std::{
+	collections::HashMap,
+	future::Future,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex,
+	},
+};
+
+use axum::async_trait;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::{
+	entity::{tag::Tag, todo::Todo, todo_list::TodoList, todo_query::TodoQuery},
+	repository::{
+		tag_repository::{
+			CreateTagError, DeleteTagError, DynTagRepository, FindManyTagError, FindTagError,
+			TagRepository, UpdateTagError,
+		},
+		todo_list_repository::{
+			CreateTodoListError, DeleteTodoListError, DynTodoListRepository, FindManyTodoListError,
+			FindTodoListError, TodoListRepository, UpdateTodoListError,
+		},
+		todo_repository::{
+			CountTodoError, CreateTodoError, DeleteError, DeleteManyError, DynTodoRepository,
+			FindManyTodoError, FindTodoError, PurgeError, RestoreError, TodoRepository,
+			TodoUnitOfWork, UnitOfWorkError, UpdateError,
+		},
+	},
+};
+
+#[derive(Serialize, ToSchema, Debug)]
+pub struct TodoCacheStats {
+	/// Lookups answered from the cache
+	pub hits: u64,
+	/// Lookups sent to the repository
+	pub misses: u64,
+	/// Queries currently cached
+	pub entries: usize,
+}
+
+#[derive(Default)]
+struct Entries {
+	/// Bumped on every write, so a result read before it is not cached after it
+	generation: u64,
+	todos: HashMap<TodoQuery, Vec<Todo>>,
+	counts: HashMap<TodoQuery, i64>,
+}
+
+/// Results of the todo queries, dropped as a whole on any write since a single todo
+/// change may affect every query.
+pub struct TodoCache {
+	/// Entries kept before the cache is emptied to make room
+	capacity: usize,
+	entries: Mutex<Entries>,
+	hits: AtomicU64,
+	misses: AtomicU64,
+}
+
+impl TodoCache {
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			capacity,
+			entries: Mutex::new(Entries::default()),
+			hits: AtomicU64::new(0),
+			misses: AtomicU64::new(0),
+		}
+	}
+
+	pub fn stats(&self) -> TodoCacheStats {
+		let entries = self.entries.lock().unwrap();
+
+		TodoCacheStats {
+			hits: self.hits.load(Ordering::Relaxed),
+			misses: self.misses.load(Ordering::Relaxed),
+			entries: entries.todos.len() + entries.counts.len(),
+		}
+	}
+
+	pub fn invalidate(&self) {
+		let mut entries = self.entries.lock().unwrap();
+
+		entries.generation += 1;
+		entries.todos.clear();
+		entries.counts.clear();
+	}
+
+	/// Returns the cached result of the query, or loads and caches it.
+	async fn get_or_load<T, E>(
+		&self,
+		select: fn(&mut Entries) -> &mut HashMap<TodoQuery, T>,
+		query: TodoQuery,
+		load: impl Future<Output = Result<T, E>>,
+	) -> Result<T, E>
+	where
+		T: Clone,
+	{
+		let generation = {
+			let mut entries = self.entries.lock().unwrap();
+
+			if let Some(value) = select(&mut entries).get(&query) {
+				self.hits.fetch_add(1, Ordering::Relaxed);
+				return Ok(value.clone());
+			}
+
+			entries.generation
+		};
+
+		self.misses.fetch_add(1, Ordering::Relaxed);
+		let value = load.await?;
+
+		let mut entries = self.entries.lock().unwrap();
+		if entries.generation == generation {
+			if entries.todos.len() + entries.counts.len() >= self.capacity {
+				entries.todos.clear();
+				entries.counts.clear();
+			}
+
+			select(&mut entries).insert(query, value.clone());
+		}
+
+		Ok(value)
+	}
+}
+
+/// Caches the todo lists and counts of any repository.
+pub struct TodoCachedRepository {
+	inner: DynTodoRepository,
+	cache: Arc<TodoCache>,
+}
+
+impl TodoCachedRepository {
+	pub fn new(inner: DynTodoRepository, cache: Arc<TodoCache>) -> Self {
+		Self { inner, cache }
+	}
+}
+
+#[async_trait]
+impl TodoRepository for TodoCachedRepository {
+	async fn create_todo(&self, todo: Todo) -> Result<Todo, CreateTodoError> {
+		let result = self.inner.create_todo(todo).await;
+		self.cache.invalidate();
+		result
+	}
+
+	async fn find_by_id(&self, id: String) -> Result<Todo, FindTodoError> {
+		self.inner.find_by_id(id).await
+	}
+
+	async fn find_many_todos(&self, query: &TodoQuery) -> Result<Vec<Todo>, FindManyTodoError> {
+		// the due filters compare with the current time, their result changes without writes
+		if query.due.is_some() {
+			return self.inner.find_many_todos(query).await;
+		}
+
+		self.cache
+			.get_or_load(
+				|entries| &mut entries.todos,
+				query.clone(),
+				self.inner.find_many_todos(query),
+			)
+			.await
+	}
+
+	async fn find_descendants(&self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.inner.find_descendants(id).await
+	}
+
+	async fn update(&self, todo: Todo) -> Result<Todo, UpdateError> {
+		let result = self.inner.update(todo).await;
+		self.cache.invalidate();
+		result
+	}
+
+	async fn delete(&self, id: String) -> Result<(), DeleteError> {
+		let result = self.inner.delete(id).await;
+		self.cache.invalidate();
+		result
+	}
+
+	async fn delete_where_done(
+		&self,
+		list_id: Option<&String>,
+	) -> Result<Vec<Todo>, DeleteManyError> {
+		let result = self.inner.delete_where_done(list_id).await;
+		self.cache.invalidate();
+		result
+	}
+
+	async fn find_deleted(&self) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.inner.find_deleted().await
+	}
+
+	async fn restore(&self, id: String) -> Result<Todo, RestoreError> {
+		let result = self.inner.restore(id).await;
+		self.cache.invalidate();
+		result
+	}
+
+	async fn purge_deleted(
+		&self,
+		deleted_before: chrono::DateTime<chrono::Utc>,
+	) -> Result<u64, PurgeError> {
+		let result = self.inner.purge_deleted(deleted_before).await;
+		self.cache.invalidate();
+		result
+	}
+
+	async fn count(&self, query: &TodoQuery) -> Result<i64, CountTodoError> {
+		if query.due.is_some() {
+			return self.inner.count(query).await;
+		}
+
+		// the count ignores the sort and page, queries differing only by them share it
+		let key = TodoQuery {
+			sort: Default::default(),
+			cursor: None,
+			limit: None,
+			..query.clone()
+		};
+
+		self.cache
+			.get_or_load(|entries| &mut entries.counts, key, self.inner.count(query))
+			.await
+	}
+
+	async fn begin(&self) -> Result<Box<dyn TodoUnitOfWork>, UnitOfWorkError> {
+		Ok(Box::new(TodoCachedUnitOfWork {
+			inner: Some(self.inner.begin().await?),
+			cache: self.cache.clone(),
+		}))
+	}
+}
+
+/// Unit of work of the wrapped repository, invalidating the cache once committed or
+/// rolled back, as the reads meanwhile may have cached its uncommitted writes.
+struct TodoCachedUnitOfWork {
+	/// `None` once committed
+	inner: Option<Box<dyn TodoUnitOfWork>>,
+	cache: Arc<TodoCache>,
+}
+
+impl TodoCachedUnitOfWork {
+	fn inner(&mut self) -> &mut Box<dyn TodoUnitOfWork> {
+		self.inner.as_mut().expect("unit of work already committed")
+	}
+}
+
+#[async_trait]
+impl TodoUnitOfWork for TodoCachedUnitOfWork {
+	async fn create_todo(&mut self, todo: Todo) -> Result<Todo, CreateTodoError> {
+		self.inner().create_todo(todo).await
+	}
+
+	async fn find_by_id(&mut self, id: String) -> Result<Todo, FindTodoError> {
+		self.inner().find_by_id(id).await
+	}
+
+	async fn find_descendants(&mut self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.inner().find_descendants(id).await
+	}
+
+	async fn find_siblings(
+		&mut self,
+		list_id: Option<String>,
+		parent_id: Option<String>,
+	) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.inner().find_siblings(list_id, parent_id).await
+	}
+
+	async fn update(&mut self, todo: Todo) -> Result<Todo, UpdateError> {
+		self.inner().update(todo).await
+	}
+
+	async fn delete(&mut self, id: String) -> Result<(), DeleteError> {
+		self.inner().delete(id).await
+	}
+
+	async fn commit(mut self: Box<Self>) -> Result<(), UnitOfWorkError> {
+		let inner = self.inner.take().expect("unit of work already committed");
+
+		let result = inner.commit().await;
+		self.cache.invalidate();
+		result
+	}
+}
+
+impl Drop for TodoCachedUnitOfWork {
+	fn drop(&mut self) {
+		// invalidated after the rollback, so no read caches the discarded writes again
+		if let Some(inner) = self.inner.take() {
+			drop(inner);
+			self.cache.invalidate();
+		}
+	}
+}
+
+/// Invalidates the todo cache when a tag is renamed or deleted, as the cached todos
+/// carry the tag names.
+pub struct TagCacheInvalidatingRepository {
+	inner: DynTagRepository,
+	cache: Arc<TodoCache>,
+}
+
+impl TagCacheInvalidatingRepository {
+	pub fn new(inner: DynTagRepository, cache: Arc<TodoCache>) -> Self {
+		Self { inner, cache }
+	}
+}
+
+#[async_trait]
+impl TagRepository for TagCacheInvalidatingRepository {
+	async fn create_tag(&self, tag: Tag) -> Result<Tag, CreateTagError> {
+		self.inner.create_tag(tag).await
+	}
+
+	async fn find_tag_by_id(&self, id: String) -> Result<Tag, FindTagError> {
+		self.inner.find_tag_by_id(id).await
+	}
+
+	async fn find_many_tags(&self) -> Result<Vec<Tag>, FindManyTagError> {
+		self.inner.find_many_tags().await
+	}
+
+	async fn update_tag(&self, tag: Tag) -> Result<Tag, UpdateTagError> {
+		let result = self.inner.update_tag(tag).await;
+		self.cache.invalidate();
+		result
+	}
+
+	async fn delete_tag(&self, id: String) -> Result<(), DeleteTagError> {
+		let result = self.inner.delete_tag(id).await;
+		self.cache.invalidate();
+		result
+	}
+}
+
+/// Invalidates the todo cache when a list is deleted, along with its todos.
+pub struct TodoListCacheInvalidatingRepository {
+	inner: DynTodoListRepository,
+	cache: Arc<TodoCache>,
+}
+
+impl TodoListCacheInvalidatingRepository {
+	pub fn new(inner: DynTodoListRepository, cache: Arc<TodoCache>) -> Self {
+		Self { inner, cache }
+	}
+}
+
+#[async_trait]
+impl TodoListRepository for TodoListCacheInvalidatingRepository {
+	async fn create_list(&self, list: TodoList) -> Result<TodoList, CreateTodoListError> {
+		self.inner.create_list(list).await
+	}
+
+	async fn find_list_by_id(&self, id: String) -> Result<TodoList, FindTodoListError> {
+		self.inner.find_list_by_id(id).await
+	}
+
+	async fn find_many_lists(&self) -> Result<Vec<TodoList>, FindManyTodoListError> {
+		self.inner.find_many_lists().await
+	}
+
+	async fn update_list(&self, list: TodoList) -> Result<TodoList, UpdateTodoListError> {
+		self.inner.update_list(list).await
+	}
+
+	async fn delete_list(&self, id: String) -> Result<(), DeleteTodoListError> {
+		let result = self.inner.delete_list(id).await;
+		self.cache.invalidate();
+		result
+	}
+}
@@ -0,0 +1,529 @@
+use std::{
+	collections::{HashMap, HashSet},
+	ffi::OsString,
+	fs::{File, OpenOptions},
+	io::{self, BufRead, BufReader, BufWriter, Write},
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use axum::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::domain::{
+	entity::{tag::Tag, todo::Todo, todo_list::TodoList, todo_query::TodoQuery},
+	repository::todo_repository::{
+		CountTodoError, CreateTodoError, DeleteError, DeleteManyError, FindManyTodoError,
+		FindTodoError, PurgeError, RestoreError, TodoRepository, TodoUnitOfWork, UnitOfWorkError,
+		UpdateError,
+	},
+};
+
+use super::todo_inmemory_repo::TodoInMemoryRepository;
+
+/// Change to a todo, tag or list, stored as one JSON object per line of the log.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub(super) enum TodoRecord {
+	Create { todo: Todo },
+	Update { todo: Todo },
+	Delete { id: String },
+	CreateTag { tag: Tag },
+	UpdateTag { tag: Tag },
+	DeleteTag { id: String },
+	CreateList { list: TodoList },
+	UpdateList { list: TodoList },
+	DeleteList { id: String },
+}
+
+pub(super) struct Log {
+	file: File,
+	/// Records in the file, compacting is worth it once they outnumber the todos, tags
+	/// and lists
+	records: usize,
+	/// Tags in the file, the ones the todos add on the fly are logged along their todo
+	tag_ids: HashSet<String>,
+}
+
+impl Log {
+	/// Appends the records, preceded by the tags of `memory` not logged yet.
+	pub(super) fn append(
+		&mut self,
+		memory: &TodoInMemoryRepository,
+		records: &[TodoRecord],
+	) -> io::Result<()> {
+		let mut tag_ids = self.tag_ids.clone();
+		for record in records {
+			match record {
+				TodoRecord::CreateTag { tag } => tag_ids.insert(tag.id.clone()),
+				TodoRecord::DeleteTag { id } => tag_ids.remove(id),
+				_ => false,
+			};
+		}
+
+		let new_tags: Vec<TodoRecord> = memory
+			.tags
+			.lock()
+			.unwrap()
+			.iter()
+			.filter(|tag| !tag_ids.contains(&tag.id))
+			.map(|tag| TodoRecord::CreateTag { tag: tag.clone() })
+			.collect();
+		for record in &new_tags {
+			if let TodoRecord::CreateTag { tag } = record {
+				tag_ids.insert(tag.id.clone());
+			}
+		}
+
+		let mut lines = String::new();
+		for record in new_tags.iter().chain(records) {
+			lines.push_str(&serde_json::to_string(record)?);
+			lines.push('\n');
+		}
+
+		self.file.write_all(lines.as_bytes())?;
+		self.file.sync_data()?;
+		self.records += new_tags.len() + records.len();
+		self.tag_ids = tag_ids;
+
+		Ok(())
+	}
+}
+
+/// Todos, tags and lists left by the records of a log.
+#[derive(Default)]
+struct Snapshot {
+	todos: Vec<Todo>,
+	tags: Vec<Tag>,
+	lists: Vec<TodoList>,
+}
+
+impl Snapshot {
+	fn of(memory: &TodoInMemoryRepository) -> Self {
+		Self {
+			todos: memory.todos.lock().unwrap().clone(),
+			tags: memory.tags.lock().unwrap().clone(),
+			lists: memory.lists.lock().unwrap().clone(),
+		}
+	}
+
+	fn len(&self) -> usize {
+		self.todos.len() + self.tags.len() + self.lists.len()
+	}
+}
+
+/// Keeps the todos in memory and persists every change to an append-only JSON-lines
+/// log, replayed when the repository is opened. The tags and lists go through
+/// `TagFileRepository` and `TodoListFileRepository`, which share the log.
+pub struct TodoFileRepository {
+	/// Answers the queries, shared with the in-memory tag and list repositories
+	pub memory: TodoInMemoryRepository,
+	path: PathBuf,
+	pub(super) log: Arc<Mutex<Log>>,
+}
+
+impl TodoFileRepository {
+	/// Replays the log at `path`, created when missing, then compacts it.
+	///
+	/// A last line that does not parse is a write cut short by a crash and is dropped,
+	/// any other one fails the opening.
+	pub fn open(path: impl Into<PathBuf>) -> io::Result<TodoFileRepository> {
+		let path = path.into();
+
+		let snapshot = match File::open(&path) {
+			Ok(file) => replay(BufReader::new(file))?,
+			Err(err) if err.kind() == io::ErrorKind::NotFound => Snapshot::default(),
+			Err(err) => return Err(err),
+		};
+
+		let memory =
+			TodoInMemoryRepository::with_data(snapshot.todos, snapshot.tags, snapshot.lists);
+		// the tags of the todos missing from an older log are now registered
+		let snapshot = Snapshot::of(&memory);
+
+		let file = write_snapshot(&path, &snapshot)?;
+
+		Ok(Self {
+			memory,
+			path,
+			log: Arc::new(Mutex::new(Log {
+				file,
+				records: snapshot.len(),
+				tag_ids: snapshot.tags.iter().map(|tag| tag.id.clone()).collect(),
+			})),
+		})
+	}
+
+	/// Applies `change` to the todos in memory and appends the records it returns, the
+	/// todos being rolled back when the append fails.
+	async fn write<T, E>(
+		&self,
+		internal_error: impl Fn() -> E,
+		change: impl FnOnce(&TodoInMemoryRepository) -> Result<(T, Vec<TodoRecord>), E>,
+	) -> Result<T, E> {
+		let mut log = self.log.lock().await;
+		let uow = self.memory.begin().await.map_err(|_| internal_error())?;
+
+		let (value, records) = change(&self.memory)?;
+
+		log.append(&self.memory, &records).map_err(|err| {
+			tracing::error!("Error appending to the todos log: {:?}", err);
+			internal_error()
+		})?;
+		uow.commit().await.map_err(|_| internal_error())?;
+
+		Ok(value)
+	}
+
+	/// Rewrites the log with a single record per todo, tag and list, returns how many
+	/// records were dropped.
+	pub async fn compact(&self) -> io::Result<usize> {
+		let mut log = self.log.lock().await;
+
+		let snapshot = Snapshot::of(&self.memory);
+		if log.records <= snapshot.len() {
+			return Ok(0);
+		}
+
+		let dropped = log.records - snapshot.len();
+		log.file = write_snapshot(&self.path, &snapshot)?;
+		log.records = snapshot.len();
+		log.tag_ids = snapshot.tags.iter().map(|tag| tag.id.clone()).collect();
+
+		Ok(dropped)
+	}
+}
+
+#[async_trait]
+impl TodoRepository for TodoFileRepository {
+	async fn create_todo(&self, create_todo: Todo) -> Result<Todo, CreateTodoError> {
+		self.write(
+			|| CreateTodoError::DBInternalError,
+			|memory| {
+				let todo = memory.insert(create_todo);
+
+				Ok((todo.clone(), vec![TodoRecord::Create { todo }]))
+			},
+		)
+		.await
+	}
+
+	async fn find_by_id(&self, id: String) -> Result<Todo, FindTodoError> {
+		self.memory.find_by_id(id).await
+	}
+
+	async fn find_many_todos(&self, query: &TodoQuery) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.memory.find_many_todos(query).await
+	}
+
+	async fn find_descendants(&self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.memory.find_descendants(id).await
+	}
+
+	async fn update(&self, update_todo: Todo) -> Result<Todo, UpdateError> {
+		self.write(
+			|| UpdateError::DBInternalError,
+			|memory| {
+				let todo = memory.replace(update_todo)?;
+
+				Ok((todo.clone(), vec![TodoRecord::Update { todo }]))
+			},
+		)
+		.await
+	}
+
+	async fn delete(&self, id: String) -> Result<(), DeleteError> {
+		self.write(
+			|| DeleteError::DBInternalError,
+			|memory| {
+				let before = todo_states(memory);
+				memory.trash_todo(id)?;
+
+				Ok(((), todo_changes(memory, before)))
+			},
+		)
+		.await
+	}
+
+	async fn delete_where_done(
+		&self,
+		list_id: Option<&String>,
+	) -> Result<Vec<Todo>, DeleteManyError> {
+		self.write(
+			|| DeleteManyError::DBInternalError,
+			|memory| {
+				let before = todo_states(memory);
+				let todos = memory.trash_done(list_id);
+
+				Ok((todos, todo_changes(memory, before)))
+			},
+		)
+		.await
+	}
+
+	async fn find_deleted(&self) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.memory.find_deleted().await
+	}
+
+	async fn restore(&self, id: String) -> Result<Todo, RestoreError> {
+		self.write(
+			|| RestoreError::DBInternalError,
+			|memory| {
+				let before = todo_states(memory);
+				let todo = memory.restore_todo(id)?;
+
+				Ok((todo, todo_changes(memory, before)))
+			},
+		)
+		.await
+	}
+
+	async fn purge_deleted(
+		&self,
+		deleted_before: chrono::DateTime<chrono::Utc>,
+	) -> Result<u64, PurgeError> {
+		self.write(
+			|| PurgeError::DBInternalError,
+			|memory| {
+				let before = todo_states(memory);
+				let count = memory.purge(deleted_before);
+
+				Ok((count, todo_changes(memory, before)))
+			},
+		)
+		.await
+	}
+
+	async fn count(&self, query: &TodoQuery) -> Result<i64, CountTodoError> {
+		self.memory.count(query).await
+	}
+
+	async fn begin(&self) -> Result<Box<dyn TodoUnitOfWork>, UnitOfWorkError> {
+		let log = self.log.clone().lock_owned().await;
+
+		Ok(Box::new(TodoFileUnitOfWork {
+			log,
+			memory: self.memory.clone(),
+			inner: self.memory.begin().await?,
+			records: vec![],
+		}))
+	}
+}
+
+/// Unit of work of the in-memory repository, its records are appended on commit.
+struct TodoFileUnitOfWork {
+	log: OwnedMutexGuard<Log>,
+	memory: TodoInMemoryRepository,
+	inner: Box<dyn TodoUnitOfWork>,
+	records: Vec<TodoRecord>,
+}
+
+#[async_trait]
+impl TodoUnitOfWork for TodoFileUnitOfWork {
+	async fn create_todo(&mut self, todo: Todo) -> Result<Todo, CreateTodoError> {
+		let todo = self.inner.create_todo(todo).await?;
+
+		self.records.push(TodoRecord::Create { todo: todo.clone() });
+
+		Ok(todo)
+	}
+
+	async fn find_by_id(&mut self, id: String) -> Result<Todo, FindTodoError> {
+		self.inner.find_by_id(id).await
+	}
+
+	async fn find_descendants(&mut self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.inner.find_descendants(id).await
+	}
+
+	async fn find_siblings(
+		&mut self,
+		list_id: Option<String>,
+		parent_id: Option<String>,
+	) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.inner.find_siblings(list_id, parent_id).await
+	}
+
+	async fn update(&mut self, todo: Todo) -> Result<Todo, UpdateError> {
+		let todo = self.inner.update(todo).await?;
+
+		self.records.push(TodoRecord::Update { todo: todo.clone() });
+
+		Ok(todo)
+	}
+
+	async fn delete(&mut self, id: String) -> Result<(), DeleteError> {
+		let before = todo_states(&self.memory);
+
+		self.inner.delete(id).await?;
+
+		self.records.extend(todo_changes(&self.memory, before));
+
+		Ok(())
+	}
+
+	/// Appends the records before committing, a failed append rolls the todos back.
+	async fn commit(self: Box<Self>) -> Result<(), UnitOfWorkError> {
+		let TodoFileUnitOfWork {
+			mut log,
+			memory,
+			inner,
+			records,
+		} = *self;
+
+		log.append(&memory, &records).map_err(|err| {
+			tracing::error!("Error appending to the todos log: {:?}", err);
+			UnitOfWorkError::DBInternalError
+		})?;
+
+		inner.commit().await
+	}
+}
+
+/// Trash date and tags of a todo, the fields changed along with other todos or with
+/// tags.
+type TodoState = (Option<chrono::DateTime<chrono::Utc>>, Vec<String>);
+
+/// State of every todo, to find out what a change touched.
+pub(super) fn todo_states(memory: &TodoInMemoryRepository) -> HashMap<String, TodoState> {
+	memory
+		.todos
+		.lock()
+		.unwrap()
+		.iter()
+		.map(|todo| (todo.id.clone(), (todo.deleted_at, todo.tags.clone())))
+		.collect()
+}
+
+/// Records for the todos moved to or out of the trash, retagged, or removed, since
+/// `before`.
+pub(super) fn todo_changes(
+	memory: &TodoInMemoryRepository,
+	mut before: HashMap<String, TodoState>,
+) -> Vec<TodoRecord> {
+	let mut records: Vec<TodoRecord> = vec![];
+
+	for todo in memory.todos.lock().unwrap().iter() {
+		match before.remove(&todo.id) {
+			Some((deleted_at, tags)) if deleted_at == todo.deleted_at && tags == todo.tags => {},
+			Some(_) => records.push(TodoRecord::Update { todo: todo.clone() }),
+			None => records.push(TodoRecord::Create { todo: todo.clone() }),
+		}
+	}
+	records.extend(before.into_keys().map(|id| TodoRecord::Delete { id }));
+
+	records
+}
+
+/// Values recorded in a log by id, removed ones included.
+struct Entries<T> {
+	values: Vec<T>,
+	indexes: HashMap<String, usize>,
+}
+
+impl<T> Entries<T> {
+	fn new() -> Self {
+		Self {
+			values: vec![],
+			indexes: HashMap::new(),
+		}
+	}
+
+	fn upsert(&mut self, id: &str, value: T) {
+		match self.indexes.get(id) {
+			Some(&index) => self.values[index] = value,
+			None => {
+				self.indexes.insert(id.to_string(), self.values.len());
+				self.values.push(value);
+			},
+		}
+	}
+
+	fn remove(&mut self, id: &str, id_of: impl Fn(&T) -> &str) {
+		if let Some(index) = self.indexes.remove(id) {
+			self.values.swap_remove(index);
+			if let Some(moved) = self.values.get(index) {
+				self.indexes.insert(id_of(moved).to_string(), index);
+			}
+		}
+	}
+}
+
+/// Applies the records of the log in order, returns what they leave.
+fn replay(reader: impl BufRead) -> io::Result<Snapshot> {
+	let lines: Vec<String> = reader.lines().collect::<io::Result<_>>()?;
+
+	let mut todos: Entries<Todo> = Entries::new();
+	let mut tags: Entries<Tag> = Entries::new();
+	let mut lists: Entries<TodoList> = Entries::new();
+
+	for (number, line) in lines.iter().enumerate() {
+		if line.trim().is_empty() {
+			continue;
+		}
+
+		let record: TodoRecord = match serde_json::from_str(line) {
+			Ok(record) => record,
+			Err(err) if number + 1 == lines.len() => {
+				tracing::warn!(
+					"Dropping the truncated last record of the todos log: {}",
+					err
+				);
+				break;
+			},
+			Err(err) => {
+				return Err(io::Error::new(
+					io::ErrorKind::InvalidData,
+					format!("invalid todos log record on line {}: {}", number + 1, err),
+				))
+			},
+		};
+
+		match record {
+			TodoRecord::Create { todo } | TodoRecord::Update { todo } => {
+				todos.upsert(&todo.id.clone(), todo)
+			},
+			TodoRecord::Delete { id } => todos.remove(&id, |todo| &todo.id),
+			TodoRecord::CreateTag { tag } | TodoRecord::UpdateTag { tag } => {
+				tags.upsert(&tag.id.clone(), tag)
+			},
+			TodoRecord::DeleteTag { id } => tags.remove(&id, |tag| &tag.id),
+			TodoRecord::CreateList { list } | TodoRecord::UpdateList { list } => {
+				lists.upsert(&list.id.clone(), list)
+			},
+			TodoRecord::DeleteList { id } => lists.remove(&id, |list| &list.id),
+		}
+	}
+
+	Ok(Snapshot {
+		todos: todos.values,
+		tags: tags.values,
+		lists: lists.values,
+	})
+}
+
+/// Writes the snapshot to a temporary file swapped with the log once synced, so a crash
+/// leaves either log whole. Returns the new log opened for appending.
+fn write_snapshot(path: &Path, snapshot: &Snapshot) -> io::Result<File> {
+	let mut tmp_path = OsString::from(path);
+	tmp_path.push(".tmp");
+
+	let records = snapshot
+		.tags
+		.iter()
+		.map(|tag| TodoRecord::CreateTag { tag: tag.clone() })
+		.chain(snapshot.lists.iter().map(|list| TodoRecord::CreateList { list: list.clone() }))
+		.chain(snapshot.todos.iter().map(|todo| TodoRecord::Create { todo: todo.clone() }));
+
+	let mut writer = BufWriter::new(File::create(&tmp_path)?);
+	for record in records {
+		serde_json::to_writer(&mut writer, &record)?;
+		writer.write_all(b"\n")?;
+	}
+	writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+
+	std::fs::rename(&tmp_path, path)?;
+
+	OpenOptions::new().append(true).open(path)
+}
@@ -1,48 +1,216 @@
-use std::{cmp::Reverse, sync::Mutex};
+use std::{
+	cmp::{Ordering, Reverse},
+	sync::{Arc, Mutex},
+};
 
 use axum::async_trait;
 use chrono::{Duration, NaiveDate};
 use rand::Rng;
 use random_word::Lang;
+use tokio::sync::OwnedMutexGuard;
 
 use crate::domain::{
-	entity::todo::Todo,
+	entity::{
+		tag::Tag,
+		todo::{Todo, TodoOrder},
+		todo_list::TodoList,
+		todo_query::{SortDirection, TodoQuery, TodoSort},
+	},
 	repository::todo_repository::{
-		CountTodoError, CreateTodoError, DeleteError, FindManyTodoError, FindTodoError,
-		TodoRepository, UpdateError,
+		CountTodoError, CreateTodoError, DeleteError, DeleteManyError, FindManyTodoError,
+		FindTodoError, PurgeError, RestoreError, TodoRepository, TodoUnitOfWork, UnitOfWorkError,
+		UpdateError,
 	},
 };
 
+#[derive(Clone)]
 pub struct TodoInMemoryRepository {
-	pub todos: Mutex<Vec<Todo>>,
+	pub todos: Arc<Mutex<Vec<Todo>>>,
+	/// Shared with `TagInMemoryRepository` so tags used by todos are listed as tags
+	pub tags: Arc<Mutex<Vec<Tag>>>,
+	/// Shared with `TodoListInMemoryRepository`
+	pub lists: Arc<Mutex<Vec<TodoList>>>,
+	/// Held by every write and unit of work, which then run one at a time, including the
+	/// tag and list writes changing todos
+	pub(super) write_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl TodoInMemoryRepository {
 	pub fn new() -> TodoInMemoryRepository {
-		Self {
-			todos: Mutex::new(
-				(1..501)
-					.map(|n| {
-						let created_date = random_date_in_range(
-							&mut rand::thread_rng(),
-							NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
-							NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
-						);
-
-						let mut todo = Todo::new(random_word::gen(Lang::En).to_string());
-
-						todo.done = n % 3 == 0;
-						todo.created_at = created_date;
-						todo.updated_at = created_date;
-						todo.done_at = match n % 3 == 0 {
-							true => Some(chrono::Utc::now()),
-							false => None,
-						};
-
-						todo
-					})
-					.collect(),
-			),
+		Self::with_todos(
+			(1..501)
+				.map(|n| {
+					let created_date = random_date_in_range(
+						&mut rand::thread_rng(),
+						NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+						NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+					);
+
+					let mut todo = Todo::new(random_word::gen(Lang::En).to_string());
+
+					todo.done = n % 3 == 0;
+					todo.created_at = created_date;
+					todo.updated_at = created_date;
+					todo.position = Todo::default_position(created_date);
+					todo.done_at = match n % 3 == 0 {
+						true => Some(chrono::Utc::now()),
+						false => None,
+					};
+
+					todo
+				})
+				.collect(),
+		)
+	}
+
+	pub fn with_todos(todos: Vec<Todo>) -> TodoInMemoryRepository {
+		Self::with_data(todos, vec![], vec![])
+	}
+
+	/// The tags used by the todos and missing from `tags` are added to them.
+	pub fn with_data(
+		todos: Vec<Todo>,
+		tags: Vec<Tag>,
+		lists: Vec<TodoList>,
+	) -> TodoInMemoryRepository {
+		let repo = Self {
+			todos: Arc::new(Mutex::new(vec![])),
+			tags: Arc::new(Mutex::new(tags)),
+			lists: Arc::new(Mutex::new(lists)),
+			write_lock: Arc::new(tokio::sync::Mutex::new(())),
+		};
+
+		for todo in &todos {
+			repo.register_tags(&todo.tags);
+		}
+		*repo.todos.lock().unwrap() = todos;
+
+		repo
+	}
+
+	pub(super) fn insert(&self, todo: Todo) -> Todo {
+		self.register_tags(&todo.tags);
+
+		self.todos.lock().unwrap().push(todo.clone());
+
+		todo
+	}
+
+	pub(super) fn replace(&self, mut update_todo: Todo) -> Result<Todo, UpdateError> {
+		let mut todos = self.todos.lock().unwrap();
+
+		let index = todos
+			.iter()
+			.position(|todo: &Todo| todo.id == update_todo.id)
+			.ok_or(UpdateError::NotFound)?;
+
+		if todos[index].version != update_todo.version {
+			return Err(UpdateError::Conflict);
+		}
+
+		self.register_tags(&update_todo.tags);
+
+		update_todo.version += 1;
+		todos[index] = update_todo.clone();
+
+		Ok(update_todo)
+	}
+
+	pub(super) fn trash_todo(&self, id: String) -> Result<(), DeleteError> {
+		let mut todos = self.todos.lock().unwrap();
+
+		if !todos.iter().any(|todo: &Todo| todo.id == id && todo.deleted_at.is_none()) {
+			return Err(DeleteError::NotFound);
+		}
+
+		trash(&mut todos, &[id], chrono::Utc::now());
+
+		Ok(())
+	}
+
+	/// Trashes the done todos, of the list when given, along with their subtasks.
+	pub(super) fn trash_done(&self, list_id: Option<&String>) -> Vec<Todo> {
+		let mut todos = self.todos.lock().unwrap();
+
+		let ids: Vec<String> = todos
+			.iter()
+			.filter(|todo| {
+				todo.deleted_at.is_none()
+					&& todo.done && list_id
+					.map_or(true, |list_id| todo.list_id.as_ref() == Some(list_id))
+			})
+			.map(|todo| todo.id.clone())
+			.collect();
+
+		trash(&mut todos, &ids, chrono::Utc::now())
+	}
+
+	/// Restores the todo along with the subtasks trashed with it and its parents.
+	pub(super) fn restore_todo(&self, id: String) -> Result<Todo, RestoreError> {
+		let mut todos = self.todos.lock().unwrap();
+
+		let todo = todos
+			.iter()
+			.find(|todo: &&Todo| todo.id == id && todo.deleted_at.is_some())
+			.ok_or(RestoreError::NotFound)?;
+		let deleted_at = todo.deleted_at;
+
+		let mut restored_ids: Vec<String> = descendants_of(&todos, &id)
+			.into_iter()
+			.filter(|todo| todo.deleted_at == deleted_at)
+			.map(|todo| todo.id.clone())
+			.collect();
+		restored_ids.push(id.clone());
+
+		let mut parent_id = todo.parent_id.clone();
+		while let Some(id) = parent_id {
+			let parent = todos.iter().find(|todo| todo.id == id);
+
+			parent_id = parent.and_then(|parent| parent.parent_id.clone());
+			restored_ids.push(id);
+		}
+
+		for todo in todos
+			.iter_mut()
+			.filter(|todo| todo.deleted_at.is_some() && restored_ids.contains(&todo.id))
+		{
+			todo.deleted_at = None;
+			todo.version += 1;
+		}
+
+		Ok(todos.iter().find(|todo| todo.id == id).cloned().unwrap())
+	}
+
+	/// Removes the todos trashed before the date along with their subtasks.
+	pub(super) fn purge(&self, deleted_before: chrono::DateTime<chrono::Utc>) -> u64 {
+		let mut todos = self.todos.lock().unwrap();
+
+		let purged_ids: Vec<String> = todos
+			.iter()
+			.filter(|todo| todo.deleted_at.map_or(false, |deleted_at| deleted_at < deleted_before))
+			.map(|todo| todo.id.clone())
+			.collect();
+
+		// subtasks go along with their parent, as with `on delete cascade`
+		let mut removed_ids: Vec<String> = vec![];
+		for id in &purged_ids {
+			removed_ids.extend(descendants_of(&todos, id).into_iter().map(|todo| todo.id.clone()));
+			removed_ids.push(id.clone());
+		}
+
+		let count = todos.len();
+		todos.retain(|todo| !removed_ids.contains(&todo.id));
+
+		(count - todos.len()) as u64
+	}
+
+	fn register_tags(&self, names: &[String]) {
+		let mut tags = self.tags.lock().unwrap();
+
+		for name in names {
+			if !tags.iter().any(|tag| tag.name == *name) {
+				tags.push(Tag::new(name.clone()));
+			}
 		}
 	}
 }
@@ -56,70 +224,348 @@ impl Default for TodoInMemoryRepository {
 #[async_trait]
 impl TodoRepository for TodoInMemoryRepository {
 	async fn create_todo(&self, create_todo: Todo) -> Result<Todo, CreateTodoError> {
-		let mut todos = self.todos.lock().unwrap();
-
-		todos.push(create_todo.clone());
+		let _write = self.write_lock.lock().await;
 
-		Ok(create_todo)
+		Ok(self.insert(create_todo))
 	}
 
 	async fn find_by_id(&self, id: String) -> Result<Todo, FindTodoError> {
 		let todos = self.todos.lock().unwrap();
 
-		let todo =
-			todos.iter().find(|todo: &&Todo| todo.id == id).ok_or(FindTodoError::NotFound)?;
+		let todo = todos
+			.iter()
+			.find(|todo: &&Todo| todo.id == id && todo.deleted_at.is_none())
+			.ok_or(FindTodoError::NotFound)?;
 
 		Ok(todo.clone())
 	}
 
-	async fn find_many_todos(&self, done: Option<&bool>) -> Result<Vec<Todo>, FindManyTodoError> {
-		let mut todos: Vec<Todo> = self.todos.lock().unwrap().clone();
+	async fn find_many_todos(&self, query: &TodoQuery) -> Result<Vec<Todo>, FindManyTodoError> {
+		let now = chrono::Utc::now();
+		let terms = query.search.as_deref().map(tokenize);
+
+		let mut todos: Vec<Todo> = self
+			.todos
+			.lock()
+			.unwrap()
+			.iter()
+			.filter(|todo| matches_query(todo, query, terms.as_deref(), now))
+			.cloned()
+			.map(|mut todo| {
+				todo.snippet = terms.as_ref().map(|terms| highlight(&todo.description, terms));
+				todo
+			})
+			.collect();
+
+		todos.sort_by(|a, b| compare(&query.sort, terms.as_deref(), a, b));
+
+		if let Some(cursor) = &query.cursor {
+			// the cursor todo may no longer match the filters, so it is looked up in every todo
+			let cursor = match self.todos.lock().unwrap().iter().find(|todo| todo.id == *cursor) {
+				Some(cursor) => cursor.clone(),
+				None => return Ok(vec![]),
+			};
 
-		if let Some(done) = done {
-			todos = todos.iter().filter(|todo| todo.done == *done).cloned().collect::<Vec<Todo>>();
+			todos.retain(|todo| {
+				compare(&query.sort, terms.as_deref(), todo, &cursor) == Ordering::Greater
+			});
 		}
 
-		todos.sort_by_cached_key(|todo| Reverse(todo.created_at));
+		if let Some(limit) = query.limit {
+			todos.truncate(limit as usize);
+		}
 
 		Ok(todos)
 	}
 
+	async fn find_descendants(&self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		let todos = self.todos.lock().unwrap();
+
+		Ok(descendants_of(&todos, &id)
+			.into_iter()
+			.filter(|todo| todo.deleted_at.is_none())
+			.cloned()
+			.collect())
+	}
+
 	async fn update(&self, update_todo: Todo) -> Result<Todo, UpdateError> {
-		let mut todos = self.todos.lock().unwrap();
+		let _write = self.write_lock.lock().await;
 
-		let index = todos
+		self.replace(update_todo)
+	}
+
+	async fn delete(&self, id: String) -> Result<(), DeleteError> {
+		let _write = self.write_lock.lock().await;
+
+		self.trash_todo(id)
+	}
+
+	async fn delete_where_done(
+		&self,
+		list_id: Option<&String>,
+	) -> Result<Vec<Todo>, DeleteManyError> {
+		let _write = self.write_lock.lock().await;
+
+		Ok(self.trash_done(list_id))
+	}
+
+	async fn find_deleted(&self) -> Result<Vec<Todo>, FindManyTodoError> {
+		let mut todos: Vec<Todo> = self
+			.todos
+			.lock()
+			.unwrap()
 			.iter()
-			.position(|todo: &Todo| todo.id == update_todo.id)
-			.ok_or(UpdateError::NotFound)?;
+			.filter(|todo| todo.deleted_at.is_some())
+			.cloned()
+			.collect();
 
-		todos[index] = update_todo.clone();
+		todos.sort_by_cached_key(|todo| Reverse(todo.deleted_at));
 
-		Ok(update_todo)
+		Ok(todos)
 	}
 
-	async fn delete(&self, id: String) -> Result<(), DeleteError> {
-		let mut todos = self.todos.lock().unwrap();
+	async fn restore(&self, id: String) -> Result<Todo, RestoreError> {
+		let _write = self.write_lock.lock().await;
 
-		let index = todos
+		self.restore_todo(id)
+	}
+
+	async fn purge_deleted(
+		&self,
+		deleted_before: chrono::DateTime<chrono::Utc>,
+	) -> Result<u64, PurgeError> {
+		let _write = self.write_lock.lock().await;
+
+		Ok(self.purge(deleted_before))
+	}
+
+	async fn count(&self, query: &TodoQuery) -> Result<i64, CountTodoError> {
+		let now = chrono::Utc::now();
+		let terms = query.search.as_deref().map(tokenize);
+
+		let count = self
+			.todos
+			.lock()
+			.unwrap()
+			.iter()
+			.filter(|todo| matches_query(todo, query, terms.as_deref(), now))
+			.count();
+
+		Ok(count as i64)
+	}
+
+	async fn begin(&self) -> Result<Box<dyn TodoUnitOfWork>, UnitOfWorkError> {
+		let write = self.write_lock.clone().lock_owned().await;
+		let snapshot = self.todos.lock().unwrap().clone();
+
+		Ok(Box::new(TodoInMemoryUnitOfWork {
+			_write: write,
+			repo: self.clone(),
+			snapshot: Some(snapshot),
+		}))
+	}
+}
+
+/// Unit of work holding the write lock, the todos are put back as they were when it is
+/// dropped uncommitted.
+struct TodoInMemoryUnitOfWork {
+	_write: OwnedMutexGuard<()>,
+	repo: TodoInMemoryRepository,
+	/// Todos when the unit of work started, `None` once committed
+	snapshot: Option<Vec<Todo>>,
+}
+
+#[async_trait]
+impl TodoUnitOfWork for TodoInMemoryUnitOfWork {
+	async fn create_todo(&mut self, todo: Todo) -> Result<Todo, CreateTodoError> {
+		Ok(self.repo.insert(todo))
+	}
+
+	async fn find_by_id(&mut self, id: String) -> Result<Todo, FindTodoError> {
+		self.repo.find_by_id(id).await
+	}
+
+	async fn find_descendants(&mut self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		self.repo.find_descendants(id).await
+	}
+
+	async fn find_siblings(
+		&mut self,
+		list_id: Option<String>,
+		parent_id: Option<String>,
+	) -> Result<Vec<Todo>, FindManyTodoError> {
+		let mut todos: Vec<Todo> = self
+			.repo
+			.todos
+			.lock()
+			.unwrap()
 			.iter()
-			.position(|todo: &Todo| todo.id == id)
-			.ok_or(DeleteError::NotFound)?;
+			.filter(|todo| {
+				todo.deleted_at.is_none() && todo.list_id == list_id && todo.parent_id == parent_id
+			})
+			.cloned()
+			.collect();
 
-		todos.remove(index);
+		todos.sort_by(|a, b| a.position.total_cmp(&b.position).then_with(|| a.id.cmp(&b.id)));
+
+		Ok(todos)
+	}
+
+	async fn update(&mut self, todo: Todo) -> Result<Todo, UpdateError> {
+		self.repo.replace(todo)
+	}
+
+	async fn delete(&mut self, id: String) -> Result<(), DeleteError> {
+		self.repo.trash_todo(id)
+	}
+
+	async fn commit(mut self: Box<Self>) -> Result<(), UnitOfWorkError> {
+		self.snapshot = None;
 
 		Ok(())
 	}
+}
 
-	async fn count(&self, done: Option<&bool>) -> Result<i64, CountTodoError> {
-		let todos: Vec<Todo> = self.todos.lock().unwrap().clone();
+impl Drop for TodoInMemoryUnitOfWork {
+	fn drop(&mut self) {
+		if let Some(snapshot) = self.snapshot.take() {
+			*self.repo.todos.lock().unwrap() = snapshot;
+		}
+	}
+}
 
-		let count = match done {
-			Some(done) => todos.iter().filter(|todo| todo.done == *done).count(),
-			None => todos.len(),
-		};
+/// Searches rank by relevance, ties and cursors are then resolved by id.
+fn compare(sort: &TodoSort, terms: Option<&[String]>, a: &Todo, b: &Todo) -> Ordering {
+	if let Some(terms) = terms {
+		let rank = |todo: &Todo| search_rank(todo, terms).unwrap_or_default();
 
-		Ok(count as i64)
+		return rank(b).total_cmp(&rank(a)).then_with(|| b.id.cmp(&a.id));
+	}
+
+	let ordering = match sort.order {
+		TodoOrder::CreatedAt => a.created_at.cmp(&b.created_at),
+		TodoOrder::Priority => {
+			a.priority.cmp(&b.priority).then_with(|| a.created_at.cmp(&b.created_at))
+		},
+		TodoOrder::Manual => a.position.total_cmp(&b.position),
+	}
+	.then_with(|| a.id.cmp(&b.id));
+
+	match sort.direction {
+		SortDirection::Asc => ordering,
+		SortDirection::Desc => ordering.reverse(),
+	}
+}
+
+/// Lowercased words of the text, split on anything but letters and digits.
+fn tokenize(text: &str) -> Vec<String> {
+	text.split(|c: char| !c.is_alphanumeric())
+		.filter(|word| !word.is_empty())
+		.map(str::to_lowercase)
+		.collect()
+}
+
+/// Words match a term they start with, loosely following the stemming of Postgres.
+fn matches_term(word: &str, terms: &[String]) -> bool {
+	let word = word.to_lowercase();
+
+	terms.iter().any(|term| word.starts_with(term.as_str()))
+}
+
+/// Share of the description words matching a term, `None` unless every term matches.
+fn search_rank(todo: &Todo, terms: &[String]) -> Option<f64> {
+	let words = tokenize(&todo.description);
+
+	if terms.is_empty()
+		|| !terms
+			.iter()
+			.all(|term| words.iter().any(|word| word.starts_with(term.as_str())))
+	{
+		return None;
 	}
+
+	let matches = words.iter().filter(|word| matches_term(word, terms)).count();
+
+	Some(matches as f64 / words.len().max(1) as f64)
+}
+
+/// Wraps the description words matching a term in `<mark>`.
+fn highlight(description: &str, terms: &[String]) -> String {
+	let mut snippet = String::with_capacity(description.len());
+	let mut rest = description;
+
+	while let Some(start) = rest.find(char::is_alphanumeric) {
+		let (before, word) = rest.split_at(start);
+		let end = word.find(|c: char| !c.is_alphanumeric()).unwrap_or(word.len());
+		let (word, after) = word.split_at(end);
+
+		snippet.push_str(before);
+		if matches_term(word, terms) {
+			snippet.push_str("<mark>");
+			snippet.push_str(word);
+			snippet.push_str("</mark>");
+		} else {
+			snippet.push_str(word);
+		}
+		rest = after;
+	}
+	snippet.push_str(rest);
+
+	snippet
+}
+
+fn matches_query(
+	todo: &Todo,
+	query: &TodoQuery,
+	terms: Option<&[String]>,
+	now: chrono::DateTime<chrono::Utc>,
+) -> bool {
+	todo.deleted_at.is_none()
+		&& query.status.map_or(true, |status| todo.done == status.is_done())
+		&& query.due.map_or(true, |due| due.matches(todo, now))
+		&& query.priority.map_or(true, |priority| todo.priority == priority)
+		&& query.tag.as_ref().map_or(true, |tag| todo.tags.contains(tag))
+		&& query
+			.list_id
+			.as_ref()
+			.map_or(true, |list_id| todo.list_id.as_ref() == Some(list_id))
+		&& query.created.contains(Some(todo.created_at))
+		&& query.done.contains(todo.done_at)
+		&& query.contains.as_ref().map_or(true, |contains| {
+			todo.description.to_lowercase().contains(&contains.to_lowercase())
+		}) && terms.map_or(true, |terms| search_rank(todo, terms).is_some())
+}
+
+fn descendants_of<'a>(todos: &'a [Todo], id: &String) -> Vec<&'a Todo> {
+	let mut descendants: Vec<&Todo> = vec![];
+	let mut parent_ids: Vec<&String> = vec![id];
+
+	while let Some(parent_id) = parent_ids.pop() {
+		for todo in todos.iter().filter(|todo| todo.parent_id.as_ref() == Some(parent_id)) {
+			descendants.push(todo);
+			parent_ids.push(&todo.id);
+		}
+	}
+
+	descendants
+}
+
+/// Moves the todos and their subtasks not yet in the trash to the trash, returns them.
+fn trash(todos: &mut [Todo], ids: &[String], now: chrono::DateTime<chrono::Utc>) -> Vec<Todo> {
+	let mut trashed_ids: Vec<String> = ids.to_vec();
+	for id in ids {
+		trashed_ids.extend(descendants_of(todos, id).into_iter().map(|todo| todo.id.clone()));
+	}
+
+	todos
+		.iter_mut()
+		.filter(|todo| todo.deleted_at.is_none() && trashed_ids.contains(&todo.id))
+		.map(|todo| {
+			todo.deleted_at = Some(now);
+			todo.version += 1;
+			todo.clone()
+		})
+		.collect()
 }
 
 fn random_date_in_range(
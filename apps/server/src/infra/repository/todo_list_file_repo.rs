@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use tokio::sync::Mutex;
+
+use crate::domain::{
+	entity::todo_list::TodoList,
+	repository::todo_list_repository::{
+		CreateTodoListError, DeleteTodoListError, FindManyTodoListError, FindTodoListError,
+		TodoListRepository, UpdateTodoListError,
+	},
+};
+
+use super::{
+	todo_file_repo::{todo_changes, todo_states, Log, TodoFileRepository, TodoRecord},
+	todo_inmemory_repo::TodoInMemoryRepository,
+	todo_list_inmemory_repo::TodoListInMemoryRepository,
+};
+
+/// Lists of a `TodoFileRepository`, persisted to its log along with the todos removed
+/// with them.
+pub struct TodoListFileRepository {
+	memory: TodoInMemoryRepository,
+	lists: TodoListInMemoryRepository,
+	log: Arc<Mutex<Log>>,
+}
+
+impl TodoListFileRepository {
+	pub fn new(todo_repo: &TodoFileRepository) -> TodoListFileRepository {
+		Self {
+			memory: todo_repo.memory.clone(),
+			lists: TodoListInMemoryRepository::new(&todo_repo.memory),
+			log: todo_repo.log.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl TodoListRepository for TodoListFileRepository {
+	async fn create_list(&self, create_list: TodoList) -> Result<TodoList, CreateTodoListError> {
+		let mut log = self.log.lock().await;
+
+		let list = self.lists.create_list(create_list).await?;
+
+		log.append(
+			&self.memory,
+			&[TodoRecord::CreateList { list: list.clone() }],
+		)
+		.map_err(|err| {
+			tracing::error!("Error appending to the todos log: {:?}", err);
+			CreateTodoListError::DBInternalError
+		})?;
+
+		Ok(list)
+	}
+
+	async fn find_list_by_id(&self, id: String) -> Result<TodoList, FindTodoListError> {
+		self.lists.find_list_by_id(id).await
+	}
+
+	async fn find_many_lists(&self) -> Result<Vec<TodoList>, FindManyTodoListError> {
+		self.lists.find_many_lists().await
+	}
+
+	async fn update_list(&self, update_list: TodoList) -> Result<TodoList, UpdateTodoListError> {
+		let mut log = self.log.lock().await;
+
+		let list = self.lists.update_list(update_list).await?;
+
+		log.append(
+			&self.memory,
+			&[TodoRecord::UpdateList { list: list.clone() }],
+		)
+		.map_err(|err| {
+			tracing::error!("Error appending to the todos log: {:?}", err);
+			UpdateTodoListError::DBInternalError
+		})?;
+
+		Ok(list)
+	}
+
+	async fn delete_list(&self, id: String) -> Result<(), DeleteTodoListError> {
+		let mut log = self.log.lock().await;
+		let before = todo_states(&self.memory);
+
+		self.lists.delete_list(id.clone()).await?;
+
+		let mut records = vec![TodoRecord::DeleteList { id }];
+		records.extend(todo_changes(&self.memory, before));
+
+		log.append(&self.memory, &records).map_err(|err| {
+			tracing::error!("Error appending to the todos log: {:?}", err);
+			DeleteTodoListError::DBInternalError
+		})
+	}
+}
@@ -0,0 +1,90 @@
+use std::sync::{Arc, Mutex};
+
+use axum::async_trait;
+
+use crate::domain::{
+	entity::{todo::Todo, todo_list::TodoList},
+	repository::todo_list_repository::{
+		CreateTodoListError, DeleteTodoListError, FindManyTodoListError, FindTodoListError,
+		TodoListRepository, UpdateTodoListError,
+	},
+};
+
+use super::todo_inmemory_repo::TodoInMemoryRepository;
+
+pub struct TodoListInMemoryRepository {
+	pub lists: Arc<Mutex<Vec<TodoList>>>,
+	pub todos: Arc<Mutex<Vec<Todo>>>,
+	/// Write lock of the todos, a rolled back unit of work would otherwise bring back the
+	/// todos of a deleted list
+	write_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl TodoListInMemoryRepository {
+	pub fn new(todo_repo: &TodoInMemoryRepository) -> TodoListInMemoryRepository {
+		Self {
+			lists: todo_repo.lists.clone(),
+			todos: todo_repo.todos.clone(),
+			write_lock: todo_repo.write_lock.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl TodoListRepository for TodoListInMemoryRepository {
+	async fn create_list(&self, create_list: TodoList) -> Result<TodoList, CreateTodoListError> {
+		let mut lists = self.lists.lock().unwrap();
+
+		lists.push(create_list.clone());
+
+		Ok(create_list)
+	}
+
+	async fn find_list_by_id(&self, id: String) -> Result<TodoList, FindTodoListError> {
+		let lists = self.lists.lock().unwrap();
+
+		let list = lists
+			.iter()
+			.find(|list: &&TodoList| list.id == id)
+			.ok_or(FindTodoListError::NotFound)?;
+
+		Ok(list.clone())
+	}
+
+	async fn find_many_lists(&self) -> Result<Vec<TodoList>, FindManyTodoListError> {
+		let mut lists: Vec<TodoList> = self.lists.lock().unwrap().clone();
+
+		lists.sort_by_key(|list| list.created_at);
+
+		Ok(lists)
+	}
+
+	async fn update_list(&self, update_list: TodoList) -> Result<TodoList, UpdateTodoListError> {
+		let mut lists = self.lists.lock().unwrap();
+
+		let index = lists
+			.iter()
+			.position(|list: &TodoList| list.id == update_list.id)
+			.ok_or(UpdateTodoListError::NotFound)?;
+
+		lists[index] = update_list.clone();
+
+		Ok(update_list)
+	}
+
+	async fn delete_list(&self, id: String) -> Result<(), DeleteTodoListError> {
+		let _write = self.write_lock.lock().await;
+		let mut todos = self.todos.lock().unwrap();
+		let mut lists = self.lists.lock().unwrap();
+
+		let index = lists
+			.iter()
+			.position(|list: &TodoList| list.id == id)
+			.ok_or(DeleteTodoListError::NotFound)?;
+
+		lists.remove(index);
+		todos.retain(|todo| todo.list_id.as_ref() != Some(&id));
+
+		Ok(())
+	}
+}
@@ -0,0 +1,97 @@
+use axum::async_trait;
+use tracing::instrument;
+
+use crate::domain::{
+	entity::todo_list::TodoList,
+	repository::todo_list_repository::{
+		CreateTodoListError, DeleteTodoListError, FindManyTodoListError, FindTodoListError,
+		TodoListRepository, UpdateTodoListError,
+	},
+};
+
+#[derive(Debug)]
+pub struct TodoListPgRepository<'a> {
+	pool: &'a sqlx::Pool<sqlx::Postgres>,
+}
+
+impl<'a> TodoListPgRepository<'a> {
+	pub fn new(pool: &'a sqlx::Pool<sqlx::Postgres>) -> Self {
+		Self { pool }
+	}
+}
+
+#[async_trait]
+impl<'a> TodoListRepository for TodoListPgRepository<'a> {
+	#[instrument(name = "sqlx::create_list")]
+	async fn create_list(&self, list: TodoList) -> Result<TodoList, CreateTodoListError> {
+		sqlx::query_as::<_, TodoList>("INSERT INTO todo_lists (id, name, created_at, updated_at) VALUES ($1, $2, $3, $4) RETURNING *")
+			.bind(list.id)
+			.bind(list.name)
+			.bind(list.created_at)
+			.bind(list.updated_at)
+			.fetch_one(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error creating list: {:?}", err);
+				CreateTodoListError::DBInternalError
+			})
+	}
+
+	#[instrument(name = "sqlx::find_list_by_id")]
+	async fn find_list_by_id(&self, id: String) -> Result<TodoList, FindTodoListError> {
+		sqlx::query_as::<_, TodoList>("SELECT * FROM todo_lists WHERE id = $1")
+			.bind(id)
+			.fetch_optional(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error finding list: {:?}", err);
+				FindTodoListError::DBInternalError
+			})?
+			.ok_or(FindTodoListError::NotFound)
+	}
+
+	#[instrument(name = "sqlx::find_many_lists")]
+	async fn find_many_lists(&self) -> Result<Vec<TodoList>, FindManyTodoListError> {
+		sqlx::query_as::<_, TodoList>("SELECT * FROM todo_lists ORDER BY created_at")
+			.fetch_all(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error finding lists: {:?}", err);
+				FindManyTodoListError::DBInternalError
+			})
+	}
+
+	#[instrument(name = "sqlx::update_list")]
+	async fn update_list(&self, list: TodoList) -> Result<TodoList, UpdateTodoListError> {
+		sqlx::query_as::<_, TodoList>(
+			"UPDATE todo_lists SET name = $1, updated_at = $2 WHERE id = $3 RETURNING *",
+		)
+		.bind(list.name)
+		.bind(list.updated_at)
+		.bind(list.id)
+		.fetch_optional(self.pool)
+		.await
+		.map_err(|err| {
+			tracing::error!("Error updating list: {:?}", err);
+			UpdateTodoListError::DBInternalError
+		})?
+		.ok_or(UpdateTodoListError::NotFound)
+	}
+
+	#[instrument(name = "sqlx::delete_list")]
+	async fn delete_list(&self, id: String) -> Result<(), DeleteTodoListError> {
+		let result = sqlx::query("DELETE FROM todo_lists WHERE id = $1")
+			.bind(id)
+			.execute(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error deleting list: {:?}", err);
+				DeleteTodoListError::DBInternalError
+			})?;
+
+		match result.rows_affected() {
+			0 => Err(DeleteTodoListError::NotFound),
+			_ => Ok(()),
+		}
+	}
+}
@@ -1,12 +1,17 @@
 use axum::async_trait;
-use sqlx::prelude::FromRow;
+use sqlx::{prelude::FromRow, PgConnection, PgExecutor, Postgres, QueryBuilder, Transaction};
 use tracing::instrument;
 
 use crate::domain::{
-	entity::todo::Todo,
+	entity::{
+		tag::Tag,
+		todo::{DueFilter, Todo, TodoOrder},
+		todo_query::{DateRange, SortDirection, TodoQuery},
+	},
 	repository::todo_repository::{
-		CountTodoError, CreateTodoError, DeleteError, FindManyTodoError, FindTodoError,
-		TodoRepository, UpdateError,
+		CountTodoError, CreateTodoError, DeleteError, DeleteManyError, FindManyTodoError,
+		FindTodoError, PurgeError, RestoreError, TodoRepository, TodoUnitOfWork, UnitOfWorkError,
+		UpdateError,
 	},
 };
 
@@ -26,108 +31,555 @@ struct TodosCount {
 	count: i64,
 }
 
-#[async_trait]
-impl<'a> TodoRepository for TodoPgRepository<'a> {
-	#[instrument(name = "sqlx::create_todo")]
-	async fn create_todo(&self, todo: Todo) -> Result<Todo, CreateTodoError> {
-		sqlx::query_as::<_, Todo>("INSERT INTO todos (id, description, done, created_at, updated_at, done_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *")
+/// Selects todos along with the sorted names of their tags.
+const SELECT_TODOS: &str = "SELECT todos.*, ARRAY(SELECT tags.name FROM todo_tags JOIN tags ON tags.id = todo_tags.tag_id WHERE todo_tags.todo_id = todos.id ORDER BY tags.name) AS tags FROM todos";
+
+/// Locks the selected todos until the end of the transaction.
+const FOR_UPDATE: &str = " FOR UPDATE OF todos";
+
+/// Selects the todos to move to the trash, the conditions on them are pushed next and
+/// closed by `push_trash_subtasks`.
+const TRASH_TODOS: &str =
+	"WITH RECURSIVE trashed AS (SELECT id FROM todos WHERE deleted_at IS NULL";
+
+/// Brings back the todo `$1`, the subtasks trashed along with it and its trashed parents.
+const RESTORE_TODOS: &str = "WITH RECURSIVE target AS (SELECT id, deleted_at FROM todos WHERE id = $1 AND deleted_at IS NOT NULL), subtasks AS (SELECT id FROM target UNION SELECT todos.id FROM todos JOIN subtasks ON todos.parent_id = subtasks.id WHERE todos.deleted_at = (SELECT deleted_at FROM target)), parents AS (SELECT parent_id AS id FROM todos WHERE id IN (SELECT id FROM target) UNION SELECT todos.parent_id FROM todos JOIN parents ON todos.id = parents.id) UPDATE todos SET deleted_at = NULL, version = version + 1 WHERE deleted_at IS NOT NULL AND (id IN (SELECT id FROM subtasks) OR id IN (SELECT id FROM parents))";
+
+fn push_trash_subtasks(
+	builder: &mut QueryBuilder<'_, Postgres>,
+	now: chrono::DateTime<chrono::Utc>,
+) {
+	builder
+		.push(" UNION SELECT todos.id FROM todos JOIN trashed ON todos.parent_id = trashed.id WHERE todos.deleted_at IS NULL) UPDATE todos SET version = version + 1, deleted_at = ")
+		.push_bind(now)
+		.push(" WHERE id IN (SELECT id FROM trashed) RETURNING *");
+}
+
+async fn replace_todo_tags(
+	conn: &mut PgConnection,
+	todo_id: &str,
+	tags: &[String],
+) -> Result<Vec<String>, sqlx::Error> {
+	sqlx::query("DELETE FROM todo_tags WHERE todo_id = $1")
+		.bind(todo_id)
+		.execute(&mut *conn)
+		.await?;
+
+	for name in tags {
+		let tag = Tag::new(name.clone());
+
+		sqlx::query(
+			"INSERT INTO tags (id, name, created_at) VALUES ($1, $2, $3) ON CONFLICT (name) DO NOTHING",
+		)
+		.bind(tag.id)
+		.bind(tag.name)
+		.bind(tag.created_at)
+		.execute(&mut *conn)
+		.await?;
+	}
+
+	sqlx::query(
+		"INSERT INTO todo_tags (todo_id, tag_id) SELECT $1, id FROM tags WHERE name = ANY($2)",
+	)
+	.bind(todo_id)
+	.bind(tags)
+	.execute(&mut *conn)
+	.await?;
+
+	let mut tags = tags.to_vec();
+	tags.sort();
+
+	Ok(tags)
+}
+
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &TodoQuery) {
+	builder.push(" WHERE deleted_at IS NULL");
+
+	if let Some(status) = query.status {
+		builder.push(" AND done = ").push_bind(status.is_done());
+	}
+
+	if let Some(due) = query.due {
+		let (from, to) = due.bounds(chrono::Utc::now());
+
+		builder.push(" AND due_at IS NOT NULL");
+
+		if due == DueFilter::Overdue {
+			builder.push(" AND done = false");
+		}
+		push_date_range(builder, "due_at", &DateRange { from, to });
+	}
+
+	if let Some(priority) = query.priority {
+		builder.push(" AND priority = ").push_bind(priority);
+	}
+
+	if let Some(tag) = &query.tag {
+		builder
+			.push(" AND EXISTS (SELECT 1 FROM todo_tags JOIN tags ON tags.id = todo_tags.tag_id WHERE todo_tags.todo_id = todos.id AND tags.name = ")
+			.push_bind(tag.clone())
+			.push(")");
+	}
+
+	if let Some(list_id) = &query.list_id {
+		builder.push(" AND list_id = ").push_bind(list_id.clone());
+	}
+
+	push_date_range(builder, "created_at", &query.created);
+
+	if !query.done.is_unbounded() {
+		builder.push(" AND done_at IS NOT NULL");
+		push_date_range(builder, "done_at", &query.done);
+	}
+
+	if let Some(contains) = &query.contains {
+		builder
+			.push(" AND strpos(lower(description), lower(")
+			.push_bind(contains.clone())
+			.push(")) > 0");
+	}
+
+	if let Some(search) = &query.search {
+		builder.push(" AND search_vector @@ ");
+		push_search_query(builder, search);
+	}
+}
+
+fn push_date_range(builder: &mut QueryBuilder<'_, Postgres>, column: &str, range: &DateRange) {
+	if let Some(from) = range.from {
+		builder.push(format!(" AND {} >= ", column)).push_bind(from);
+	}
+	if let Some(to) = range.to {
+		builder.push(format!(" AND {} < ", column)).push_bind(to);
+	}
+}
+
+/// Columns of the sort, the last one being the unique id.
+fn sort_columns(order: TodoOrder) -> &'static [&'static str] {
+	match order {
+		TodoOrder::CreatedAt => &["created_at", "id"],
+		TodoOrder::Priority => &["priority", "created_at", "id"],
+		TodoOrder::Manual => &["position", "id"],
+	}
+}
+
+/// Parses the search like a web search engine: quoted phrases, `or` and `-` exclusions.
+fn push_search_query(builder: &mut QueryBuilder<'_, Postgres>, search: &str) {
+	builder
+		.push("websearch_to_tsquery('english', ")
+		.push_bind(search.to_string())
+		.push(")");
+}
+
+/// Inserts the todo along with its tags, `conn` being in a transaction.
+async fn insert_todo(conn: &mut PgConnection, todo: Todo) -> Result<Todo, CreateTodoError> {
+	let tags = todo.tags.clone();
+
+	let result: Result<Todo, sqlx::Error> = async {
+		let mut new_todo = sqlx::query_as::<_, Todo>("INSERT INTO todos (id, description, done, created_at, updated_at, done_at, due_at, priority, list_id, parent_id, recurrence, position) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING *")
 			.bind(todo.id)
 			.bind(todo.description)
 			.bind(todo.done)
 			.bind(todo.created_at)
 			.bind(todo.updated_at)
 			.bind(todo.done_at)
-			.fetch_one(self.pool)
-			.await
-			.map_err(|err| {
-				tracing::error!("Error creating todo: {:?}", err);
-				CreateTodoError::DBInternalError
-			})
+			.bind(todo.due_at)
+			.bind(todo.priority)
+			.bind(todo.list_id)
+			.bind(todo.parent_id)
+			.bind(todo.recurrence)
+			.bind(todo.position)
+			.fetch_one(&mut *conn)
+			.await?;
+
+		new_todo.tags = replace_todo_tags(conn, &new_todo.id, &tags).await?;
+
+		Ok(new_todo)
+	}
+	.await;
+
+	result.map_err(|err| {
+		tracing::error!("Error creating todo: {:?}", err);
+		CreateTodoError::DBInternalError
+	})
+}
+
+/// `for_update` locks the todo until the end of the transaction.
+async fn select_todo(
+	executor: impl PgExecutor<'_>,
+	id: String,
+	for_update: bool,
+) -> Result<Todo, FindTodoError> {
+	sqlx::query_as::<_, Todo>(&format!(
+		"{} WHERE id = $1 AND deleted_at IS NULL{}",
+		SELECT_TODOS,
+		if for_update { FOR_UPDATE } else { "" }
+	))
+	.bind(id)
+	.fetch_optional(executor)
+	.await
+	.map_err(|err| {
+		tracing::error!("Error finding todo: {:?}", err);
+		FindTodoError::DBInternalError
+	})?
+	.ok_or(FindTodoError::NotFound)
+}
+
+/// `for_update` locks the subtasks until the end of the transaction.
+async fn select_descendants(
+	executor: impl PgExecutor<'_>,
+	id: String,
+	for_update: bool,
+) -> Result<Vec<Todo>, FindManyTodoError> {
+	sqlx::query_as::<_, Todo>(&format!(
+		"WITH RECURSIVE descendants AS (SELECT id FROM todos WHERE parent_id = $1 AND deleted_at IS NULL UNION ALL SELECT todos.id FROM todos JOIN descendants ON todos.parent_id = descendants.id WHERE todos.deleted_at IS NULL) {} WHERE id IN (SELECT id FROM descendants){}",
+		SELECT_TODOS,
+		if for_update { FOR_UPDATE } else { "" }
+	))
+	.bind(id)
+	.fetch_all(executor)
+	.await
+	.map_err(|err| {
+		tracing::error!("Error finding subtasks: {:?}", err);
+		FindManyTodoError::DBInternalError
+	})
+}
+
+/// Saves the todo if its version did not change, `conn` being in a transaction.
+async fn update_todo(conn: &mut PgConnection, update_todo: Todo) -> Result<Todo, UpdateError> {
+	let tags = update_todo.tags.clone();
+	let id = update_todo.id.clone();
+
+	let result: Result<Option<Todo>, sqlx::Error> = async {
+		let todo = sqlx::query_as::<_, Todo>("UPDATE todos SET description = $1, done = $2, updated_at = $3, done_at = $4, due_at = $5, priority = $6, list_id = $7, recurrence = $8, position = $9, version = version + 1 WHERE id = $10 AND version = $11 RETURNING *")
+			.bind(update_todo.description)
+			.bind(update_todo.done)
+			.bind(update_todo.updated_at)
+			.bind(update_todo.done_at)
+			.bind(update_todo.due_at)
+			.bind(update_todo.priority)
+			.bind(update_todo.list_id)
+			.bind(update_todo.recurrence)
+			.bind(update_todo.position)
+			.bind(update_todo.id)
+			.bind(update_todo.version)
+			.fetch_optional(&mut *conn)
+			.await?;
+
+		let mut todo = match todo {
+			Some(todo) => todo,
+			None => return Ok(None),
+		};
+
+		todo.tags = replace_todo_tags(conn, &todo.id, &tags).await?;
+
+		Ok(Some(todo))
+	}
+	.await;
+
+	match result {
+		Ok(Some(todo)) => Ok(todo),
+		// nothing matched the version, the todo was either written meanwhile or removed
+		Ok(None) => match select_todo(conn, id, false).await {
+			Ok(_) => Err(UpdateError::Conflict),
+			Err(FindTodoError::NotFound) => Err(UpdateError::NotFound),
+			Err(_) => Err(UpdateError::DBInternalError),
+		},
+		Err(err) => {
+			tracing::error!("Error updating todo: {:?}", err);
+			Err(UpdateError::DBInternalError)
+		},
+	}
+}
+
+async fn trash_todo(executor: impl PgExecutor<'_>, id: String) -> Result<(), DeleteError> {
+	let mut builder = QueryBuilder::<Postgres>::new(TRASH_TODOS);
+
+	builder.push(" AND id = ").push_bind(id);
+	push_trash_subtasks(&mut builder, chrono::Utc::now());
+
+	let result = builder.build().execute(executor).await.map_err(|err| {
+		tracing::error!("Error deleting todo: {:?}", err);
+		DeleteError::DBInternalError
+	})?;
+
+	match result.rows_affected() {
+		0 => Err(DeleteError::NotFound),
+		_ => Ok(()),
+	}
+}
+
+#[async_trait]
+impl<'a> TodoRepository for TodoPgRepository<'a> {
+	#[instrument(name = "sqlx::create_todo")]
+	async fn create_todo(&self, todo: Todo) -> Result<Todo, CreateTodoError> {
+		let mut tx = self.pool.begin().await.map_err(|err| {
+			tracing::error!("Error creating todo: {:?}", err);
+			CreateTodoError::DBInternalError
+		})?;
+
+		let todo = insert_todo(&mut tx, todo).await?;
+
+		tx.commit().await.map_err(|err| {
+			tracing::error!("Error creating todo: {:?}", err);
+			CreateTodoError::DBInternalError
+		})?;
+
+		Ok(todo)
 	}
 
 	#[instrument(name = "sqlx::find_by_id")]
 	async fn find_by_id(&self, id: String) -> Result<Todo, FindTodoError> {
-		sqlx::query_as::<_, Todo>("SELECT * FROM todos WHERE id = $1")
-			.bind(id)
-			.fetch_one(self.pool)
-			.await
-			.map_err(|err| {
-				tracing::error!("Error finding todo: {:?}", err);
-				FindTodoError::NotFound
-			})
+		select_todo(self.pool, id, false).await
 	}
 
 	#[instrument(name = "sqlx::find_many_todos")]
-	async fn find_many_todos(&self, done: Option<&bool>) -> Result<Vec<Todo>, FindManyTodoError> {
-		match done {
-			Some(done) => {
-				sqlx::query_as::<_, Todo>(
-					"SELECT * FROM todos WHERE done = $1 ORDER BY created_at DESC",
-				)
-				.bind(done)
-				.fetch_all(self.pool)
-				.await
-			},
-			None => {
-				sqlx::query_as::<_, Todo>("SELECT * FROM todos ORDER BY created_at DESC")
-					.fetch_all(self.pool)
-					.await
-			},
+	async fn find_many_todos(&self, query: &TodoQuery) -> Result<Vec<Todo>, FindManyTodoError> {
+		let mut builder = QueryBuilder::<Postgres>::new("SELECT ");
+
+		if let Some(search) = &query.search {
+			builder.push("ts_headline('english', description, ");
+			push_search_query(&mut builder, search);
+			builder.push(", 'StartSel=<mark>, StopSel=</mark>') AS snippet, ");
 		}
-		.map_err(|err| {
+		builder.push(SELECT_TODOS.trim_start_matches("SELECT "));
+
+		push_filters(&mut builder, query);
+
+		if let (Some(cursor), Some(search)) = (&query.cursor, &query.search) {
+			builder.push(" AND (ts_rank(search_vector, ");
+			push_search_query(&mut builder, search);
+			builder.push("), id) < (SELECT ts_rank(search_vector, ");
+			push_search_query(&mut builder, search);
+			builder.push("), id FROM todos WHERE id = ").push_bind(cursor.clone()).push(")");
+		} else if let Some(cursor) = &query.cursor {
+			let columns = sort_columns(query.sort.order).join(", ");
+			let operator = match query.sort.direction {
+				SortDirection::Asc => ">",
+				SortDirection::Desc => "<",
+			};
+
+			builder
+				.push(format!(
+					" AND ({}) {} (SELECT {} FROM todos WHERE id = ",
+					columns, operator, columns
+				))
+				.push_bind(cursor.clone())
+				.push(")");
+		}
+
+		if let Some(search) = &query.search {
+			builder.push(" ORDER BY ts_rank(search_vector, ");
+			push_search_query(&mut builder, search);
+			builder.push(") DESC, id DESC");
+		} else {
+			let direction = query.sort.direction.as_sql();
+			let columns: Vec<String> = sort_columns(query.sort.order)
+				.iter()
+				.map(|column| format!("{} {}", column, direction))
+				.collect();
+
+			builder.push(" ORDER BY ").push(columns.join(", "));
+		}
+
+		if let Some(limit) = query.limit {
+			builder.push(" LIMIT ").push_bind(limit);
+		}
+
+		builder.build_query_as::<Todo>().fetch_all(self.pool).await.map_err(|err| {
 			tracing::error!("Error finding todos: {:?}", err);
 			FindManyTodoError::DBInternalError
 		})
 	}
 
+	#[instrument(name = "sqlx::find_descendants")]
+	async fn find_descendants(&self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		select_descendants(self.pool, id, false).await
+	}
+
 	#[instrument(name = "sqlx::update_todo")]
-	async fn update(&self, update_todo: Todo) -> Result<Todo, UpdateError> {
-		sqlx::query_as::<_, Todo>("UPDATE todos SET description = $1, done = $2, updated_at = $3, done_at = $4 WHERE id = $5 RETURNING *")
-			.bind(update_todo.description)
-			.bind(update_todo.done)
-			.bind(update_todo.updated_at)
-			.bind(update_todo.done_at)
-			.bind(update_todo.id)
-			.fetch_one(self.pool)
-			.await
-			.map_err(|err| {
-				tracing::error!("Error updating todo: {:?}", err);
-				UpdateError::DBInternalError
-			})
+	async fn update(&self, todo: Todo) -> Result<Todo, UpdateError> {
+		let mut tx = self.pool.begin().await.map_err(|err| {
+			tracing::error!("Error updating todo: {:?}", err);
+			UpdateError::DBInternalError
+		})?;
+
+		let todo = update_todo(&mut tx, todo).await?;
+
+		tx.commit().await.map_err(|err| {
+			tracing::error!("Error updating todo: {:?}", err);
+			UpdateError::DBInternalError
+		})?;
+
+		Ok(todo)
 	}
 
 	#[instrument(name = "sqlx::delete_todo")]
 	async fn delete(&self, id: String) -> Result<(), DeleteError> {
-		sqlx::query("DELETE FROM todos WHERE id = $1")
-			.bind(id)
+		trash_todo(self.pool, id).await
+	}
+
+	#[instrument(name = "sqlx::delete_done_todos")]
+	async fn delete_where_done(
+		&self,
+		list_id: Option<&String>,
+	) -> Result<Vec<Todo>, DeleteManyError> {
+		let mut builder = QueryBuilder::<Postgres>::new(TRASH_TODOS);
+
+		builder.push(" AND done = true");
+		if let Some(list_id) = list_id {
+			builder.push(" AND list_id = ").push_bind(list_id.clone());
+		}
+		push_trash_subtasks(&mut builder, chrono::Utc::now());
+
+		builder.build_query_as::<Todo>().fetch_all(self.pool).await.map_err(|err| {
+			tracing::error!("Error deleting done todos: {:?}", err);
+			DeleteManyError::DBInternalError
+		})
+	}
+
+	#[instrument(name = "sqlx::find_deleted_todos")]
+	async fn find_deleted(&self) -> Result<Vec<Todo>, FindManyTodoError> {
+		sqlx::query_as::<_, Todo>(&format!(
+			"{} WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+			SELECT_TODOS
+		))
+		.fetch_all(self.pool)
+		.await
+		.map_err(|err| {
+			tracing::error!("Error finding deleted todos: {:?}", err);
+			FindManyTodoError::DBInternalError
+		})
+	}
+
+	#[instrument(name = "sqlx::restore_todo")]
+	async fn restore(&self, id: String) -> Result<Todo, RestoreError> {
+		let result: Result<Option<Todo>, sqlx::Error> = async {
+			let mut tx = self.pool.begin().await?;
+
+			let restored =
+				sqlx::query(RESTORE_TODOS).bind(&id).execute(&mut *tx).await?.rows_affected();
+
+			if restored == 0 {
+				return Ok(None);
+			}
+
+			let todo = sqlx::query_as::<_, Todo>(&format!("{} WHERE id = $1", SELECT_TODOS))
+				.bind(&id)
+				.fetch_one(&mut *tx)
+				.await?;
+
+			tx.commit().await?;
+
+			Ok(Some(todo))
+		}
+		.await;
+
+		match result {
+			Ok(Some(todo)) => Ok(todo),
+			Ok(None) => Err(RestoreError::NotFound),
+			Err(err) => {
+				tracing::error!("Error restoring todo: {:?}", err);
+				Err(RestoreError::DBInternalError)
+			},
+		}
+	}
+
+	#[instrument(name = "sqlx::purge_deleted_todos")]
+	async fn purge_deleted(
+		&self,
+		deleted_before: chrono::DateTime<chrono::Utc>,
+	) -> Result<u64, PurgeError> {
+		sqlx::query("DELETE FROM todos WHERE deleted_at < $1")
+			.bind(deleted_before)
 			.execute(self.pool)
 			.await
 			.map_err(|err| {
-				tracing::error!("Error deleting todo: {:?}", err);
-				DeleteError::DBInternalError
+				tracing::error!("Error purging deleted todos: {:?}", err);
+				PurgeError::DBInternalError
 			})
-			.map(|_| ())
+			.map(|result| result.rows_affected())
 	}
 
 	#[instrument(name = "sqlx::count_todos")]
-	async fn count(&self, done: Option<&bool>) -> Result<i64, CountTodoError> {
-		match done {
-			Some(done) => {
-				sqlx::query_as::<_, TodosCount>("SELECT COUNT(*) FROM todos WHERE done = $1")
-					.bind(done)
-					.fetch_one(self.pool)
-					.await
-			},
-			None => {
-				sqlx::query_as::<_, TodosCount>("SELECT COUNT(*) FROM todos")
-					.fetch_one(self.pool)
-					.await
+	async fn count(&self, query: &TodoQuery) -> Result<i64, CountTodoError> {
+		let mut builder = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM todos");
+
+		push_filters(&mut builder, query);
+
+		builder
+			.build_query_as::<TodosCount>()
+			.fetch_one(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error counting todos: {:?}", err);
+				CountTodoError::DBInternalError
+			})
+			.map(|count| count.count)
+	}
+
+	#[instrument(name = "sqlx::begin")]
+	async fn begin(&self) -> Result<Box<dyn TodoUnitOfWork>, UnitOfWorkError> {
+		match self.pool.begin().await {
+			Ok(tx) => Ok(Box::new(TodoPgUnitOfWork { tx })),
+			Err(err) => {
+				tracing::error!("Error starting transaction: {:?}", err);
+				Err(UnitOfWorkError::DBInternalError)
 			},
 		}
+	}
+}
+
+/// Unit of work in a transaction, the todos it reads are locked with `FOR UPDATE`.
+struct TodoPgUnitOfWork {
+	tx: Transaction<'static, Postgres>,
+}
+
+#[async_trait]
+impl TodoUnitOfWork for TodoPgUnitOfWork {
+	async fn create_todo(&mut self, todo: Todo) -> Result<Todo, CreateTodoError> {
+		insert_todo(&mut self.tx, todo).await
+	}
+
+	async fn find_by_id(&mut self, id: String) -> Result<Todo, FindTodoError> {
+		select_todo(&mut *self.tx, id, true).await
+	}
+
+	async fn find_descendants(&mut self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		select_descendants(&mut *self.tx, id, true).await
+	}
+
+	async fn find_siblings(
+		&mut self,
+		list_id: Option<String>,
+		parent_id: Option<String>,
+	) -> Result<Vec<Todo>, FindManyTodoError> {
+		sqlx::query_as::<_, Todo>(&format!(
+			"{} WHERE deleted_at IS NULL AND list_id IS NOT DISTINCT FROM $1 AND parent_id IS NOT DISTINCT FROM $2 ORDER BY position ASC, id ASC{}",
+			SELECT_TODOS, FOR_UPDATE
+		))
+		.bind(list_id)
+		.bind(parent_id)
+		.fetch_all(&mut *self.tx)
+		.await
 		.map_err(|err| {
-			tracing::error!("Error counting todos: {:?}", err);
-			CountTodoError::DBInternalError
+			tracing::error!("Error finding sibling todos: {:?}", err);
+			FindManyTodoError::DBInternalError
+		})
+	}
+
+	async fn update(&mut self, todo: Todo) -> Result<Todo, UpdateError> {
+		update_todo(&mut self.tx, todo).await
+	}
+
+	async fn delete(&mut self, id: String) -> Result<(), DeleteError> {
+		trash_todo(&mut *self.tx, id).await
+	}
+
+	async fn commit(self: Box<Self>) -> Result<(), UnitOfWorkError> {
+		self.tx.commit().await.map_err(|err| {
+			tracing::error!("Error committing transaction: {:?}", err);
+			UnitOfWorkError::DBInternalError
 		})
-		.map(|count| count.count)
 	}
 }
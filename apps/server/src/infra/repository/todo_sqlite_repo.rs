@@ -0,0 +1,666 @@
+use axum::async_trait;
+use sqlx::{prelude::FromRow, QueryBuilder, Sqlite, SqliteConnection, SqliteExecutor, Transaction};
+use tracing::instrument;
+
+use crate::domain::{
+	entity::{
+		tag::Tag,
+		todo::{DueFilter, Todo, TodoOrder, TodoPriority},
+		todo_query::{DateRange, SortDirection, TodoQuery},
+	},
+	repository::todo_repository::{
+		CountTodoError, CreateTodoError, DeleteError, DeleteManyError, FindManyTodoError,
+		FindTodoError, PurgeError, RestoreError, TodoRepository, TodoUnitOfWork, UnitOfWorkError,
+		UpdateError,
+	},
+};
+
+#[derive(Debug)]
+pub struct TodoSqliteRepository<'a> {
+	pool: &'a sqlx::Pool<sqlx::Sqlite>,
+}
+
+impl<'a> TodoSqliteRepository<'a> {
+	pub fn new(pool: &'a sqlx::Pool<sqlx::Sqlite>) -> Self {
+		Self { pool }
+	}
+}
+
+#[derive(FromRow)]
+struct TodosCount {
+	count: i64,
+}
+
+/// Row of the `todos` table, SQLite has no arrays so the tag names are joined by
+/// `TAGS_SEPARATOR`.
+#[derive(FromRow)]
+struct TodoRow {
+	id: String,
+	description: String,
+	done: bool,
+	created_at: chrono::DateTime<chrono::Utc>,
+	updated_at: chrono::DateTime<chrono::Utc>,
+	done_at: Option<chrono::DateTime<chrono::Utc>>,
+	due_at: Option<chrono::DateTime<chrono::Utc>>,
+	priority: TodoPriority,
+	list_id: Option<String>,
+	parent_id: Option<String>,
+	recurrence: Option<String>,
+	position: f64,
+	deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+	version: i32,
+	#[sqlx(default)]
+	tags: Option<String>,
+	#[sqlx(default)]
+	snippet: Option<String>,
+}
+
+const TAGS_SEPARATOR: char = '\u{1f}';
+
+impl From<TodoRow> for Todo {
+	fn from(row: TodoRow) -> Self {
+		let mut tags: Vec<String> = row
+			.tags
+			.map(|tags| tags.split(TAGS_SEPARATOR).map(str::to_string).collect())
+			.unwrap_or_default();
+		tags.sort();
+
+		Self {
+			id: row.id,
+			description: row.description,
+			done: row.done,
+			created_at: row.created_at,
+			updated_at: row.updated_at,
+			done_at: row.done_at,
+			due_at: row.due_at,
+			priority: row.priority,
+			tags,
+			list_id: row.list_id,
+			parent_id: row.parent_id,
+			recurrence: row.recurrence,
+			position: row.position,
+			deleted_at: row.deleted_at,
+			version: row.version,
+			snippet: row.snippet,
+		}
+	}
+}
+
+/// Selects todos along with the names of their tags.
+const SELECT_TODOS: &str = "SELECT todos.*, (SELECT group_concat(tags.name, char(31)) FROM todo_tags JOIN tags ON tags.id = todo_tags.tag_id WHERE todo_tags.todo_id = todos.id) AS tags FROM todos";
+
+/// Selects the todos to move to the trash, the conditions on them are pushed next and
+/// closed by `push_trash_subtasks`.
+const TRASH_TODOS: &str =
+	"WITH RECURSIVE trashed AS (SELECT id FROM todos WHERE deleted_at IS NULL";
+
+/// Brings back the todo `$1`, the subtasks trashed along with it and its trashed parents.
+const RESTORE_TODOS: &str = "WITH RECURSIVE target AS (SELECT id, deleted_at FROM todos WHERE id = $1 AND deleted_at IS NOT NULL), subtasks AS (SELECT id FROM target UNION SELECT todos.id FROM todos JOIN subtasks ON todos.parent_id = subtasks.id WHERE todos.deleted_at = (SELECT deleted_at FROM target)), parents AS (SELECT parent_id AS id FROM todos WHERE id IN (SELECT id FROM target) UNION SELECT todos.parent_id FROM todos JOIN parents ON todos.id = parents.id) UPDATE todos SET deleted_at = NULL, version = version + 1 WHERE deleted_at IS NOT NULL AND (id IN (SELECT id FROM subtasks) OR id IN (SELECT id FROM parents))";
+
+fn push_trash_subtasks(builder: &mut QueryBuilder<'_, Sqlite>, now: chrono::DateTime<chrono::Utc>) {
+	builder
+		.push(" UNION SELECT todos.id FROM todos JOIN trashed ON todos.parent_id = trashed.id WHERE todos.deleted_at IS NULL) UPDATE todos SET version = version + 1, deleted_at = ")
+		.push_bind(now)
+		.push(" WHERE id IN (SELECT id FROM trashed) RETURNING *");
+}
+
+async fn replace_todo_tags(
+	conn: &mut SqliteConnection,
+	todo_id: &str,
+	tags: &[String],
+) -> Result<Vec<String>, sqlx::Error> {
+	sqlx::query("DELETE FROM todo_tags WHERE todo_id = $1")
+		.bind(todo_id)
+		.execute(&mut *conn)
+		.await?;
+
+	for name in tags {
+		let tag = Tag::new(name.clone());
+
+		sqlx::query(
+			"INSERT INTO tags (id, name, created_at) VALUES ($1, $2, $3) ON CONFLICT (name) DO NOTHING",
+		)
+		.bind(tag.id)
+		.bind(tag.name)
+		.bind(tag.created_at)
+		.execute(&mut *conn)
+		.await?;
+
+		sqlx::query(
+			"INSERT OR IGNORE INTO todo_tags (todo_id, tag_id) SELECT $1, id FROM tags WHERE name = $2",
+		)
+		.bind(todo_id)
+		.bind(name)
+		.execute(&mut *conn)
+		.await?;
+	}
+
+	let mut tags = tags.to_vec();
+	tags.sort();
+
+	Ok(tags)
+}
+
+/// Joins the full-text index when searching, which ranks the todos in `todos_fts.rank`.
+fn push_from(builder: &mut QueryBuilder<'_, Sqlite>, query: &TodoQuery) {
+	builder.push(" FROM todos");
+
+	if let Some(search) = &query.search {
+		builder
+			.push(" JOIN todos_fts ON todos_fts.rowid = todos.seq AND todos_fts MATCH ")
+			.push_bind(match_expression(search));
+	}
+}
+
+/// Prefix matches every word of the search, quoted so the FTS5 syntax can not be injected.
+fn match_expression(search: &str) -> String {
+	let words: Vec<String> = search
+		.split(|c: char| !c.is_alphanumeric())
+		.filter(|word| !word.is_empty())
+		.map(|word| format!("\"{}\"*", word))
+		.collect();
+
+	match words.is_empty() {
+		true => "\"\"".to_string(),
+		false => words.join(" "),
+	}
+}
+
+fn push_filters(builder: &mut QueryBuilder<'_, Sqlite>, query: &TodoQuery) {
+	builder.push(" WHERE todos.deleted_at IS NULL");
+
+	if let Some(status) = query.status {
+		builder.push(" AND todos.done = ").push_bind(status.is_done());
+	}
+
+	if let Some(due) = query.due {
+		let (from, to) = due.bounds(chrono::Utc::now());
+
+		builder.push(" AND todos.due_at IS NOT NULL");
+
+		if due == DueFilter::Overdue {
+			builder.push(" AND todos.done = false");
+		}
+		push_date_range(builder, "todos.due_at", &DateRange { from, to });
+	}
+
+	if let Some(priority) = query.priority {
+		builder.push(" AND todos.priority = ").push_bind(priority);
+	}
+
+	if let Some(tag) = &query.tag {
+		builder
+			.push(" AND EXISTS (SELECT 1 FROM todo_tags JOIN tags ON tags.id = todo_tags.tag_id WHERE todo_tags.todo_id = todos.id AND tags.name = ")
+			.push_bind(tag.clone())
+			.push(")");
+	}
+
+	if let Some(list_id) = &query.list_id {
+		builder.push(" AND todos.list_id = ").push_bind(list_id.clone());
+	}
+
+	push_date_range(builder, "todos.created_at", &query.created);
+
+	if !query.done.is_unbounded() {
+		builder.push(" AND todos.done_at IS NOT NULL");
+		push_date_range(builder, "todos.done_at", &query.done);
+	}
+
+	if let Some(contains) = &query.contains {
+		builder
+			.push(" AND instr(lower(todos.description), lower(")
+			.push_bind(contains.clone())
+			.push(")) > 0");
+	}
+}
+
+/// Dates are stored as RFC 3339 UTC strings, which compare like the dates.
+fn push_date_range(builder: &mut QueryBuilder<'_, Sqlite>, column: &str, range: &DateRange) {
+	if let Some(from) = range.from {
+		builder.push(format!(" AND {} >= ", column)).push_bind(from);
+	}
+	if let Some(to) = range.to {
+		builder.push(format!(" AND {} < ", column)).push_bind(to);
+	}
+}
+
+/// Columns of the sort, the last one being the unique id.
+fn sort_columns(order: TodoOrder) -> &'static [&'static str] {
+	match order {
+		TodoOrder::CreatedAt => &["todos.created_at", "todos.id"],
+		TodoOrder::Priority => &["todos.priority", "todos.created_at", "todos.id"],
+		TodoOrder::Manual => &["todos.position", "todos.id"],
+	}
+}
+
+/// Inserts the todo along with its tags, `conn` being in a transaction.
+async fn insert_todo(conn: &mut SqliteConnection, todo: Todo) -> Result<Todo, CreateTodoError> {
+	let tags = todo.tags.clone();
+
+	let result: Result<Todo, sqlx::Error> = async {
+		let mut new_todo: Todo = sqlx::query_as::<_, TodoRow>("INSERT INTO todos (id, description, done, created_at, updated_at, done_at, due_at, priority, list_id, parent_id, recurrence, position) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING *")
+			.bind(todo.id)
+			.bind(todo.description)
+			.bind(todo.done)
+			.bind(todo.created_at)
+			.bind(todo.updated_at)
+			.bind(todo.done_at)
+			.bind(todo.due_at)
+			.bind(todo.priority)
+			.bind(todo.list_id)
+			.bind(todo.parent_id)
+			.bind(todo.recurrence)
+			.bind(todo.position)
+			.fetch_one(&mut *conn)
+			.await?
+			.into();
+
+		new_todo.tags = replace_todo_tags(conn, &new_todo.id, &tags).await?;
+
+		Ok(new_todo)
+	}
+	.await;
+
+	result.map_err(|err| {
+		tracing::error!("Error creating todo: {:?}", err);
+		CreateTodoError::DBInternalError
+	})
+}
+
+async fn select_todo(executor: impl SqliteExecutor<'_>, id: String) -> Result<Todo, FindTodoError> {
+	sqlx::query_as::<_, TodoRow>(&format!(
+		"{} WHERE todos.id = $1 AND todos.deleted_at IS NULL",
+		SELECT_TODOS
+	))
+	.bind(id)
+	.fetch_optional(executor)
+	.await
+	.map_err(|err| {
+		tracing::error!("Error finding todo: {:?}", err);
+		FindTodoError::DBInternalError
+	})?
+	.map(Todo::from)
+	.ok_or(FindTodoError::NotFound)
+}
+
+async fn select_descendants(
+	executor: impl SqliteExecutor<'_>,
+	id: String,
+) -> Result<Vec<Todo>, FindManyTodoError> {
+	sqlx::query_as::<_, TodoRow>(&format!(
+		"WITH RECURSIVE descendants AS (SELECT id FROM todos WHERE parent_id = $1 AND deleted_at IS NULL UNION ALL SELECT todos.id FROM todos JOIN descendants ON todos.parent_id = descendants.id WHERE todos.deleted_at IS NULL) {} WHERE todos.id IN (SELECT id FROM descendants)",
+		SELECT_TODOS
+	))
+	.bind(id)
+	.fetch_all(executor)
+	.await
+	.map_err(|err| {
+		tracing::error!("Error finding subtasks: {:?}", err);
+		FindManyTodoError::DBInternalError
+	})
+	.map(|rows| rows.into_iter().map(Todo::from).collect())
+}
+
+/// Saves the todo if its version did not change, `conn` being in a transaction.
+async fn update_todo(conn: &mut SqliteConnection, update_todo: Todo) -> Result<Todo, UpdateError> {
+	let tags = update_todo.tags.clone();
+	let id = update_todo.id.clone();
+
+	let result: Result<Option<Todo>, sqlx::Error> = async {
+		let todo = sqlx::query_as::<_, TodoRow>("UPDATE todos SET description = $1, done = $2, updated_at = $3, done_at = $4, due_at = $5, priority = $6, list_id = $7, recurrence = $8, position = $9, version = version + 1 WHERE id = $10 AND version = $11 RETURNING *")
+			.bind(update_todo.description)
+			.bind(update_todo.done)
+			.bind(update_todo.updated_at)
+			.bind(update_todo.done_at)
+			.bind(update_todo.due_at)
+			.bind(update_todo.priority)
+			.bind(update_todo.list_id)
+			.bind(update_todo.recurrence)
+			.bind(update_todo.position)
+			.bind(update_todo.id)
+			.bind(update_todo.version)
+			.fetch_optional(&mut *conn)
+			.await?
+			.map(Todo::from);
+
+		let mut todo = match todo {
+			Some(todo) => todo,
+			None => return Ok(None),
+		};
+
+		todo.tags = replace_todo_tags(conn, &todo.id, &tags).await?;
+
+		Ok(Some(todo))
+	}
+	.await;
+
+	match result {
+		Ok(Some(todo)) => Ok(todo),
+		// nothing matched the version, the todo was either written meanwhile or removed
+		Ok(None) => match select_todo(conn, id).await {
+			Ok(_) => Err(UpdateError::Conflict),
+			Err(FindTodoError::NotFound) => Err(UpdateError::NotFound),
+			Err(_) => Err(UpdateError::DBInternalError),
+		},
+		Err(err) => {
+			tracing::error!("Error updating todo: {:?}", err);
+			Err(UpdateError::DBInternalError)
+		},
+	}
+}
+
+async fn trash_todo(executor: impl SqliteExecutor<'_>, id: String) -> Result<(), DeleteError> {
+	let mut builder = QueryBuilder::<Sqlite>::new(TRASH_TODOS);
+
+	builder.push(" AND id = ").push_bind(id);
+	push_trash_subtasks(&mut builder, chrono::Utc::now());
+
+	let result = builder.build().execute(executor).await.map_err(|err| {
+		tracing::error!("Error deleting todo: {:?}", err);
+		DeleteError::DBInternalError
+	})?;
+
+	match result.rows_affected() {
+		0 => Err(DeleteError::NotFound),
+		_ => Ok(()),
+	}
+}
+
+#[async_trait]
+impl<'a> TodoRepository for TodoSqliteRepository<'a> {
+	#[instrument(name = "sqlx::create_todo")]
+	async fn create_todo(&self, todo: Todo) -> Result<Todo, CreateTodoError> {
+		let mut tx = self.pool.begin().await.map_err(|err| {
+			tracing::error!("Error creating todo: {:?}", err);
+			CreateTodoError::DBInternalError
+		})?;
+
+		let todo = insert_todo(&mut tx, todo).await?;
+
+		tx.commit().await.map_err(|err| {
+			tracing::error!("Error creating todo: {:?}", err);
+			CreateTodoError::DBInternalError
+		})?;
+
+		Ok(todo)
+	}
+
+	#[instrument(name = "sqlx::find_by_id")]
+	async fn find_by_id(&self, id: String) -> Result<Todo, FindTodoError> {
+		select_todo(self.pool, id).await
+	}
+
+	#[instrument(name = "sqlx::find_many_todos")]
+	async fn find_many_todos(&self, query: &TodoQuery) -> Result<Vec<Todo>, FindManyTodoError> {
+		let mut builder = QueryBuilder::<Sqlite>::new("SELECT ");
+
+		if query.search.is_some() {
+			builder.push("snippet(todos_fts, 0, '<mark>', '</mark>', '…', 16) AS snippet, ");
+		}
+		builder.push(SELECT_TODOS.trim_start_matches("SELECT ").trim_end_matches(" FROM todos"));
+
+		push_from(&mut builder, query);
+		push_filters(&mut builder, query);
+
+		if let (Some(cursor), Some(search)) = (&query.cursor, &query.search) {
+			// bm25 ranks are lower for better matches
+			builder
+				.push(" AND (-todos_fts.rank, todos.id) < (SELECT -todos_fts.rank, todos.id FROM todos_fts JOIN todos ON todos.seq = todos_fts.rowid WHERE todos_fts MATCH ")
+				.push_bind(match_expression(search))
+				.push(" AND todos.id = ")
+				.push_bind(cursor.clone())
+				.push(")");
+		} else if let Some(cursor) = &query.cursor {
+			let columns = sort_columns(query.sort.order).join(", ");
+			let operator = match query.sort.direction {
+				SortDirection::Asc => ">",
+				SortDirection::Desc => "<",
+			};
+
+			builder
+				.push(format!(
+					" AND ({}) {} (SELECT {} FROM todos WHERE todos.id = ",
+					columns, operator, columns
+				))
+				.push_bind(cursor.clone())
+				.push(")");
+		}
+
+		if query.search.is_some() {
+			builder.push(" ORDER BY todos_fts.rank ASC, todos.id DESC");
+		} else {
+			let direction = query.sort.direction.as_sql();
+			let columns: Vec<String> = sort_columns(query.sort.order)
+				.iter()
+				.map(|column| format!("{} {}", column, direction))
+				.collect();
+
+			builder.push(" ORDER BY ").push(columns.join(", "));
+		}
+
+		if let Some(limit) = query.limit {
+			builder.push(" LIMIT ").push_bind(limit);
+		}
+
+		builder
+			.build_query_as::<TodoRow>()
+			.fetch_all(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error finding todos: {:?}", err);
+				FindManyTodoError::DBInternalError
+			})
+			.map(|rows| rows.into_iter().map(Todo::from).collect())
+	}
+
+	#[instrument(name = "sqlx::find_descendants")]
+	async fn find_descendants(&self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		select_descendants(self.pool, id).await
+	}
+
+	#[instrument(name = "sqlx::update_todo")]
+	async fn update(&self, todo: Todo) -> Result<Todo, UpdateError> {
+		let mut tx = self.pool.begin().await.map_err(|err| {
+			tracing::error!("Error updating todo: {:?}", err);
+			UpdateError::DBInternalError
+		})?;
+
+		let todo = update_todo(&mut tx, todo).await?;
+
+		tx.commit().await.map_err(|err| {
+			tracing::error!("Error updating todo: {:?}", err);
+			UpdateError::DBInternalError
+		})?;
+
+		Ok(todo)
+	}
+
+	#[instrument(name = "sqlx::delete_todo")]
+	async fn delete(&self, id: String) -> Result<(), DeleteError> {
+		trash_todo(self.pool, id).await
+	}
+
+	#[instrument(name = "sqlx::delete_done_todos")]
+	async fn delete_where_done(
+		&self,
+		list_id: Option<&String>,
+	) -> Result<Vec<Todo>, DeleteManyError> {
+		let mut builder = QueryBuilder::<Sqlite>::new(TRASH_TODOS);
+
+		builder.push(" AND done = true");
+		if let Some(list_id) = list_id {
+			builder.push(" AND list_id = ").push_bind(list_id.clone());
+		}
+		push_trash_subtasks(&mut builder, chrono::Utc::now());
+
+		builder
+			.build_query_as::<TodoRow>()
+			.fetch_all(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error deleting done todos: {:?}", err);
+				DeleteManyError::DBInternalError
+			})
+			.map(|rows| rows.into_iter().map(Todo::from).collect())
+	}
+
+	#[instrument(name = "sqlx::find_deleted_todos")]
+	async fn find_deleted(&self) -> Result<Vec<Todo>, FindManyTodoError> {
+		sqlx::query_as::<_, TodoRow>(&format!(
+			"{} WHERE todos.deleted_at IS NOT NULL ORDER BY todos.deleted_at DESC",
+			SELECT_TODOS
+		))
+		.fetch_all(self.pool)
+		.await
+		.map_err(|err| {
+			tracing::error!("Error finding deleted todos: {:?}", err);
+			FindManyTodoError::DBInternalError
+		})
+		.map(|rows| rows.into_iter().map(Todo::from).collect())
+	}
+
+	#[instrument(name = "sqlx::restore_todo")]
+	async fn restore(&self, id: String) -> Result<Todo, RestoreError> {
+		let result: Result<Option<Todo>, sqlx::Error> = async {
+			let mut tx = self.pool.begin().await?;
+
+			let restored =
+				sqlx::query(RESTORE_TODOS).bind(&id).execute(&mut *tx).await?.rows_affected();
+
+			if restored == 0 {
+				return Ok(None);
+			}
+
+			let todo =
+				sqlx::query_as::<_, TodoRow>(&format!("{} WHERE todos.id = $1", SELECT_TODOS))
+					.bind(&id)
+					.fetch_one(&mut *tx)
+					.await?;
+
+			tx.commit().await?;
+
+			Ok(Some(todo.into()))
+		}
+		.await;
+
+		match result {
+			Ok(Some(todo)) => Ok(todo),
+			Ok(None) => Err(RestoreError::NotFound),
+			Err(err) => {
+				tracing::error!("Error restoring todo: {:?}", err);
+				Err(RestoreError::DBInternalError)
+			},
+		}
+	}
+
+	#[instrument(name = "sqlx::purge_deleted_todos")]
+	async fn purge_deleted(
+		&self,
+		deleted_before: chrono::DateTime<chrono::Utc>,
+	) -> Result<u64, PurgeError> {
+		sqlx::query("DELETE FROM todos WHERE deleted_at < $1")
+			.bind(deleted_before)
+			.execute(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error purging deleted todos: {:?}", err);
+				PurgeError::DBInternalError
+			})
+			.map(|result| result.rows_affected())
+	}
+
+	#[instrument(name = "sqlx::count_todos")]
+	async fn count(&self, query: &TodoQuery) -> Result<i64, CountTodoError> {
+		let mut builder = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) AS count");
+
+		push_from(&mut builder, query);
+		push_filters(&mut builder, query);
+
+		builder
+			.build_query_as::<TodosCount>()
+			.fetch_one(self.pool)
+			.await
+			.map_err(|err| {
+				tracing::error!("Error counting todos: {:?}", err);
+				CountTodoError::DBInternalError
+			})
+			.map(|count| count.count)
+	}
+
+	#[instrument(name = "sqlx::begin")]
+	async fn begin(&self) -> Result<Box<dyn TodoUnitOfWork>, UnitOfWorkError> {
+		let result: Result<Transaction<'static, Sqlite>, sqlx::Error> = async {
+			let mut tx = self.pool.begin().await?;
+
+			// SQLite has no row locks, writing first takes the database write lock up front
+			// like `BEGIN IMMEDIATE`, so other writers wait for the unit of work
+			sqlx::query("UPDATE todos SET seq = seq WHERE 0").execute(&mut *tx).await?;
+
+			Ok(tx)
+		}
+		.await;
+
+		match result {
+			Ok(tx) => Ok(Box::new(TodoSqliteUnitOfWork { tx })),
+			Err(err) => {
+				tracing::error!("Error starting transaction: {:?}", err);
+				Err(UnitOfWorkError::DBInternalError)
+			},
+		}
+	}
+}
+
+/// Unit of work in a transaction holding the database write lock.
+struct TodoSqliteUnitOfWork {
+	tx: Transaction<'static, Sqlite>,
+}
+
+#[async_trait]
+impl TodoUnitOfWork for TodoSqliteUnitOfWork {
+	async fn create_todo(&mut self, todo: Todo) -> Result<Todo, CreateTodoError> {
+		insert_todo(&mut self.tx, todo).await
+	}
+
+	async fn find_by_id(&mut self, id: String) -> Result<Todo, FindTodoError> {
+		select_todo(&mut *self.tx, id).await
+	}
+
+	async fn find_descendants(&mut self, id: String) -> Result<Vec<Todo>, FindManyTodoError> {
+		select_descendants(&mut *self.tx, id).await
+	}
+
+	async fn find_siblings(
+		&mut self,
+		list_id: Option<String>,
+		parent_id: Option<String>,
+	) -> Result<Vec<Todo>, FindManyTodoError> {
+		sqlx::query_as::<_, TodoRow>(&format!(
+			"{} WHERE deleted_at IS NULL AND list_id IS $1 AND parent_id IS $2 ORDER BY position ASC, id ASC",
+			SELECT_TODOS
+		))
+		.bind(list_id)
+		.bind(parent_id)
+		.fetch_all(&mut *self.tx)
+		.await
+		.map_err(|err| {
+			tracing::error!("Error finding sibling todos: {:?}", err);
+			FindManyTodoError::DBInternalError
+		})
+		.map(|rows| rows.into_iter().map(Todo::from).collect())
+	}
+
+	async fn update(&mut self, todo: Todo) -> Result<Todo, UpdateError> {
+		update_todo(&mut self.tx, todo).await
+	}
+
+	async fn delete(&mut self, id: String) -> Result<(), DeleteError> {
+		trash_todo(&mut *self.tx, id).await
+	}
+
+	async fn commit(self: Box<Self>) -> Result<(), UnitOfWorkError> {
+		self.tx.commit().await.map_err(|err| {
+			tracing::error!("Error committing transaction: {:?}", err);
+			UnitOfWorkError::DBInternalError
+		})
+	}
+}
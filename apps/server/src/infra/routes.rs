@@ -7,11 +7,22 @@ pub fn api_routes() -> Router<AppState> {
 		.route(
 			"/api/todos",
 			routing::get(controller::todo_ctrl::get_all_todos_ctrl)
-				.post(controller::todo_ctrl::create_todo_ctrl),
+				.post(controller::todo_ctrl::create_todo_ctrl)
+				.delete(controller::todo_ctrl::delete_many_todos_ctrl),
+		)
+		.route(
+			"/api/todos/batch",
+			routing::post(controller::todo_ctrl::batch_todos_ctrl),
 		)
 		.route(
 			"/api/todos/:id",
-			routing::delete(controller::todo_ctrl::delete_todo_ctrl),
+			routing::get(controller::todo_ctrl::get_todo_ctrl)
+				.patch(controller::todo_ctrl::update_todo_ctrl)
+				.delete(controller::todo_ctrl::delete_todo_ctrl),
+		)
+		.route(
+			"/api/todos/:id/subtasks",
+			routing::post(controller::todo_ctrl::create_subtask_ctrl),
 		)
 		.route(
 			"/api/todos/:id/mark_as_done",
@@ -21,10 +32,122 @@ pub fn api_routes() -> Router<AppState> {
 			"/api/todos/:id/mark_as_undone",
 			routing::patch(controller::todo_ctrl::mark_as_undone_todo_ctrl),
 		)
+		.route(
+			"/api/todos/:id/move",
+			routing::post(controller::todo_ctrl::move_todo_ctrl),
+		)
+		.route(
+			"/api/todos/:id/restore",
+			routing::post(controller::trash_ctrl::restore_todo_ctrl),
+		)
+		.route(
+			"/api/trash",
+			routing::get(controller::trash_ctrl::get_trash_ctrl),
+		)
+		.route(
+			"/api/cache",
+			routing::get(controller::common_ctrl::cache_stats_ctrl),
+		)
 		.route(
 			"/api/todos/count",
 			routing::get(controller::todo_ctrl::count_todos_ctrl),
 		)
+		.route(
+			"/api/lists",
+			routing::get(controller::todo_list_ctrl::get_all_todo_lists_ctrl)
+				.post(controller::todo_list_ctrl::create_todo_list_ctrl),
+		)
+		.route(
+			"/api/lists/:id",
+			routing::get(controller::todo_list_ctrl::get_todo_list_ctrl)
+				.patch(controller::todo_list_ctrl::rename_todo_list_ctrl)
+				.delete(controller::todo_list_ctrl::delete_todo_list_ctrl),
+		)
+		.route(
+			"/api/lists/:id/todos",
+			routing::get(controller::todo_list_ctrl::get_list_todos_ctrl)
+				.post(controller::todo_list_ctrl::create_list_todo_ctrl),
+		)
+		.route(
+			"/api/tags",
+			routing::get(controller::tag_ctrl::get_all_tags_ctrl)
+				.post(controller::tag_ctrl::create_tag_ctrl),
+		)
+		.route(
+			"/api/tags/:id",
+			routing::patch(controller::tag_ctrl::rename_tag_ctrl)
+				.delete(controller::tag_ctrl::delete_tag_ctrl),
+		)
+}
+
+pub fn api_v2_routes() -> Router<AppState> {
+	Router::new()
+		.route(
+			"/api/v2/todos",
+			routing::get(controller::v2::todo_ctrl::get_all_todos_ctrl)
+				.post(controller::v2::todo_ctrl::create_todo_ctrl)
+				.delete(controller::v2::todo_ctrl::delete_many_todos_ctrl),
+		)
+		.route(
+			"/api/v2/todos/count",
+			routing::get(controller::v2::todo_ctrl::count_todos_ctrl),
+		)
+		.route(
+			"/api/v2/todos/batch",
+			routing::post(controller::v2::todo_ctrl::batch_todos_ctrl),
+		)
+		.route(
+			"/api/v2/todos/:id",
+			routing::get(controller::v2::todo_ctrl::get_todo_ctrl)
+				.patch(controller::v2::todo_ctrl::update_todo_ctrl)
+				.delete(controller::v2::todo_ctrl::delete_todo_ctrl),
+		)
+		.route(
+			"/api/v2/todos/:id/subtasks",
+			routing::post(controller::v2::todo_ctrl::create_subtask_ctrl),
+		)
+		.route(
+			"/api/v2/todos/:id/move",
+			routing::post(controller::v2::todo_ctrl::move_todo_ctrl),
+		)
+		.route(
+			"/api/v2/todos/:id/restore",
+			routing::post(controller::v2::trash_ctrl::restore_todo_ctrl),
+		)
+		.route(
+			"/api/v2/trash",
+			routing::get(controller::v2::trash_ctrl::get_trash_ctrl),
+		)
+		.route(
+			"/api/v2/cache",
+			routing::get(controller::v2::common_ctrl::cache_stats_ctrl),
+		)
+		.route(
+			"/api/v2/lists",
+			routing::get(controller::v2::todo_list_ctrl::get_all_todo_lists_ctrl)
+				.post(controller::v2::todo_list_ctrl::create_todo_list_ctrl),
+		)
+		.route(
+			"/api/v2/lists/:id",
+			routing::get(controller::v2::todo_list_ctrl::get_todo_list_ctrl)
+				.patch(controller::v2::todo_list_ctrl::rename_todo_list_ctrl)
+				.delete(controller::v2::todo_list_ctrl::delete_todo_list_ctrl),
+		)
+		.route(
+			"/api/v2/lists/:id/todos",
+			routing::get(controller::v2::todo_list_ctrl::get_list_todos_ctrl)
+				.post(controller::v2::todo_list_ctrl::create_list_todo_ctrl),
+		)
+		.route(
+			"/api/v2/tags",
+			routing::get(controller::v2::tag_ctrl::get_all_tags_ctrl)
+				.post(controller::v2::tag_ctrl::create_tag_ctrl),
+		)
+		.route(
+			"/api/v2/tags/:id",
+			routing::patch(controller::v2::tag_ctrl::rename_tag_ctrl)
+				.delete(controller::v2::tag_ctrl::delete_tag_ctrl),
+		)
 }
 
 pub fn views_routes() -> Router<AppState> {
@@ -45,6 +168,38 @@ pub fn views_routes() -> Router<AppState> {
 			"/create_todo",
 			routing::post(controller::todos_views_ctrl::create_todo_ctrl),
 		)
+		.route(
+			"/create_list",
+			routing::post(controller::todos_views_ctrl::create_todo_list_ctrl),
+		)
+		.route(
+			"/create_subtask/:id",
+			routing::post(controller::todos_views_ctrl::create_subtask_ctrl),
+		)
+		.route(
+			"/move_todo/:id",
+			routing::post(controller::todos_views_ctrl::move_todo_ctrl),
+		)
+		.route(
+			"/trash",
+			routing::get(controller::todos_views_ctrl::render_trash_ctrl),
+		)
+		.route(
+			"/restore_todo/:id",
+			routing::post(controller::todos_views_ctrl::restore_todo_ctrl),
+		)
+		.route(
+			"/todo/:id",
+			routing::get(controller::todos_views_ctrl::get_todo_ctrl),
+		)
+		.route(
+			"/edit_todo/:id",
+			routing::get(controller::todos_views_ctrl::edit_todo_ctrl),
+		)
+		.route(
+			"/update_todo/:id",
+			routing::post(controller::todos_views_ctrl::update_todo_ctrl),
+		)
 		.route(
 			"/mark_as_done/:id",
 			routing::post(controller::todos_views_ctrl::mark_as_done_todo_ctrl),
@@ -57,10 +212,10 @@ pub fn views_routes() -> Router<AppState> {
 			"/remove_todo/:id",
 			routing::delete(controller::todos_views_ctrl::delete_todo_ctrl),
 		)
-		// .route(
-		// 	"/clear_all_completed_todos",
-		// 	routing::post(controller::todos_views_ctrl::clear_all_completed_todos_ctrl),
-		// )
+		.route(
+			"/clear_all_completed_todos",
+			routing::post(controller::todos_views_ctrl::clear_all_completed_todos_ctrl),
+		)
 		.route(
 			"/count_todos",
 			routing::get(controller::todos_views_ctrl::count_todos_ctrl),
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
-use axum::http::{HeaderName, Method};
-use axum::routing;
+use axum::http::{header, HeaderName, Method};
+use axum::{middleware, routing};
 use axum::{routing::get, Router};
 
 use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
@@ -10,16 +10,45 @@ use tower_http::cors::{self, CorsLayer};
 
 use utoipa::OpenApi;
 
-use crate::domain::repository::todo_repository::DynTodoRepository;
+use crate::domain::repository::{
+	idempotency_repository::DynIdempotencyRepository, tag_repository::DynTagRepository,
+	todo_list_repository::DynTodoListRepository, todo_repository::DynTodoRepository,
+};
+
+use crate::usecase::{
+	purge_idempotency_keys_usecase::PurgeIdempotencyKeysUsecase,
+	purge_trash_usecase::PurgeTrashUsecase,
+};
 
 use super::controller::todos_views_ctrl::UpdateTodoTmpl;
+use super::idempotency::{self, IDEMPOTENCY_KEY};
+use super::migrations::{PG_MIGRATOR, SQLITE_MIGRATOR};
 use super::pg::create_pg_pool;
-use super::repository;
-use super::{controller, routes};
+use super::repository::todo_cached_repo::{
+	TagCacheInvalidatingRepository, TodoCache, TodoCachedRepository,
+	TodoListCacheInvalidatingRepository,
+};
+use super::repository::{
+	self, idempotency_inmemory_repo::IdempotencyInMemoryRepository,
+	todo_file_repo::TodoFileRepository,
+};
+use super::sqlite::create_sqlite_pool;
+use super::{api_response, api_version, controller, routes};
 
 #[derive(Clone)]
 pub struct AppState {
 	pub todo_repo: DynTodoRepository,
+	pub tag_repo: DynTagRepository,
+	pub todo_list_repo: DynTodoListRepository,
+	pub idempotency_repo: DynIdempotencyRepository,
+	/// How long the responses to the requests sent with an `Idempotency-Key` are replayed
+	pub idempotency_ttl: chrono::Duration,
+	/// Date the v1 API stops being served, announced in its `Sunset` header
+	pub api_v1_sunset: Option<chrono::DateTime<chrono::Utc>>,
+	/// Days deleted todos stay in the trash before being purged
+	pub trash_retention_days: i64,
+	/// Cache of the todo queries, when enabled
+	pub todo_cache: Option<Arc<TodoCache>>,
 	pub tx: Arc<Sender<UpdateTodoTmpl>>,
 }
 
@@ -37,20 +66,155 @@ impl AppState {
 pub async fn create_server() -> Router {
 	let tracing_enabled: bool = std::env::var("TRACING").unwrap_or_else(|_| "0".to_string()) == "1";
 	let inmemory_mode = std::env::var("INMEMORY_MODE").unwrap_or_else(|_| "0".to_string()) == "1";
+	let trash_retention_days: i64 = std::env::var("TRASH_RETENTION_DAYS")
+		.ok()
+		.and_then(|days| days.parse().ok())
+		.unwrap_or(30);
+	let idempotency_ttl_hours: i64 = std::env::var("IDEMPOTENCY_TTL_HOURS")
+		.ok()
+		.and_then(|hours| hours.parse().ok())
+		.unwrap_or(24);
+	// RFC 3339 date the v1 API is removed, announced to its clients once set
+	let api_v1_sunset = std::env::var("API_V1_SUNSET")
+		.ok()
+		.and_then(|date| chrono::DateTime::parse_from_rfc3339(&date).ok())
+		.map(|date| date.with_timezone(&chrono::Utc));
+	// applies the pending migrations on boot, disable to run `bin/migrate` by hand instead
+	let auto_migrate = std::env::var("AUTO_MIGRATE").unwrap_or_else(|_| "1".to_string()) == "1";
 
 	let doc: utoipa::openapi::OpenApi = super::api_doc::ApiDoc::openapi();
+	let doc_v2: utoipa::openapi::OpenApi = super::api_doc::ApiDocV2::openapi();
+
+	// `sqlite:` URLs select the SQLite backend, any other one Postgres
+	let database_url = std::env::var("DATABASE_URL").unwrap_or_default();
+	let sqlite_mode = database_url.starts_with("sqlite:");
+	// caches the todo lists and counts of the repository, up to this many queries
+	let todos_cache = std::env::var("TODOS_CACHE").unwrap_or_else(|_| "0".to_string()) == "1";
+	let todos_cache_capacity: usize = std::env::var("TODOS_CACHE_CAPACITY")
+		.ok()
+		.and_then(|capacity| capacity.parse().ok())
+		.unwrap_or(1000);
+	// persists the in-memory todos to this JSON-lines log
+	let todos_file = std::env::var("TODOS_FILE").ok().filter(|path| !path.is_empty());
+
+	let (todo_repo, tag_repo, todo_list_repo, idempotency_repo): (
+		DynTodoRepository,
+		DynTagRepository,
+		DynTodoListRepository,
+		DynIdempotencyRepository,
+	) = match (inmemory_mode, todos_file, sqlite_mode) {
+		(_, Some(todos_file), _) => {
+			let todo_repo = Arc::new(
+				TodoFileRepository::open(todos_file).expect("Failed to open the todos file"),
+			);
+			let tag_repo = repository::tag_file_repo::TagFileRepository::new(&todo_repo);
+			let todo_list_repo =
+				repository::todo_list_file_repo::TodoListFileRepository::new(&todo_repo);
+
+			tokio::spawn(compact_todos_file_periodically(todo_repo.clone()));
+
+			(
+				todo_repo,
+				Arc::new(tag_repo),
+				Arc::new(todo_list_repo),
+				Arc::new(IdempotencyInMemoryRepository::new()),
+			)
+		},
+		(true, None, _) => {
+			let todo_repo = repository::todo_inmemory_repo::TodoInMemoryRepository::new();
+			let tag_repo = repository::tag_inmemory_repo::TagInMemoryRepository::new(&todo_repo);
+			let todo_list_repo =
+				repository::todo_list_inmemory_repo::TodoListInMemoryRepository::new(&todo_repo);
+
+			(
+				Arc::new(todo_repo),
+				Arc::new(tag_repo),
+				Arc::new(todo_list_repo),
+				Arc::new(IdempotencyInMemoryRepository::new()),
+			)
+		},
+		(false, None, true) => {
+			let sqlite_pool = create_sqlite_pool(&database_url).await;
+
+			if auto_migrate {
+				SQLITE_MIGRATOR
+					.run(sqlite_pool)
+					.await
+					.expect("Failed to migrate the SQLite database");
+			}
 
-	let pg_pool = create_pg_pool().await;
+			(
+				Arc::new(repository::todo_sqlite_repo::TodoSqliteRepository::new(
+					sqlite_pool,
+				)),
+				Arc::new(repository::tag_sqlite_repo::TagSqliteRepository::new(
+					sqlite_pool,
+				)),
+				Arc::new(
+					repository::todo_list_sqlite_repo::TodoListSqliteRepository::new(sqlite_pool),
+				),
+				Arc::new(
+					repository::idempotency_sqlite_repo::IdempotencySqliteRepository::new(
+						sqlite_pool,
+					),
+				),
+			)
+		},
+		(false, None, false) => {
+			let pg_pool = create_pg_pool().await;
 
-	let todo_repo: DynTodoRepository = match inmemory_mode {
-		true => Arc::new(repository::todo_inmemory_repo::TodoInMemoryRepository::new()),
-		false => Arc::new(repository::todo_pg_repo::TodoPgRepository::new(pg_pool)),
+			if auto_migrate {
+				PG_MIGRATOR.run(pg_pool).await.expect("Failed to migrate the database");
+			}
+
+			(
+				Arc::new(repository::todo_pg_repo::TodoPgRepository::new(pg_pool)),
+				Arc::new(repository::tag_pg_repo::TagPgRepository::new(pg_pool)),
+				Arc::new(repository::todo_list_pg_repo::TodoListPgRepository::new(
+					pg_pool,
+				)),
+				Arc::new(repository::idempotency_pg_repo::IdempotencyPgRepository::new(pg_pool)),
+			)
+		},
+	};
+
+	let todo_cache = todos_cache.then(|| Arc::new(TodoCache::new(todos_cache_capacity)));
+
+	let (todo_repo, tag_repo, todo_list_repo): (
+		DynTodoRepository,
+		DynTagRepository,
+		DynTodoListRepository,
+	) = match &todo_cache {
+		Some(cache) => (
+			Arc::new(TodoCachedRepository::new(todo_repo, cache.clone())),
+			Arc::new(TagCacheInvalidatingRepository::new(tag_repo, cache.clone())),
+			Arc::new(TodoListCacheInvalidatingRepository::new(
+				todo_list_repo,
+				cache.clone(),
+			)),
+		),
+		None => (todo_repo, tag_repo, todo_list_repo),
 	};
 
+	tokio::spawn(purge_trash_periodically(
+		todo_repo.clone(),
+		chrono::Duration::days(trash_retention_days),
+	));
+	tokio::spawn(purge_idempotency_keys_periodically(
+		idempotency_repo.clone(),
+	));
+
 	let (tx, _rx) = channel::<UpdateTodoTmpl>(10);
 
 	let app_state = AppState {
 		todo_repo,
+		tag_repo,
+		todo_list_repo,
+		idempotency_repo,
+		idempotency_ttl: chrono::Duration::hours(idempotency_ttl_hours),
+		api_v1_sunset,
+		trash_retention_days,
+		todo_cache,
 		tx: Arc::new(tx),
 	};
 
@@ -66,17 +230,45 @@ pub async fn create_server() -> Router {
 		.allow_headers(vec![
 			HeaderName::from_static("authorization"),
 			HeaderName::from_static("content-type"),
-		]);
+			header::IF_MATCH,
+			IDEMPOTENCY_KEY,
+		])
+		.expose_headers([header::ETAG]);
 
 	let openapi_json = doc.to_pretty_json().unwrap();
+	let openapi_v2_json = doc_v2.to_pretty_json().unwrap();
 
 	let mut app = Router::new()
-		.merge(routes::api_routes())
+		.merge(
+			routes::api_routes()
+				.route_layer(middleware::from_fn_with_state(
+					app_state.clone(),
+					idempotency::idempotent_request,
+				))
+				.route_layer(middleware::from_fn_with_state(
+					app_state.clone(),
+					api_version::deprecated_v1,
+				)),
+		)
+		.merge(
+			routes::api_v2_routes()
+				.route_layer(middleware::from_fn(api_response::not_found_problem))
+				.route_layer(middleware::from_fn_with_state(
+					app_state.clone(),
+					idempotency::idempotent_request,
+				)),
+		)
 		.merge(routes::views_routes())
 		.with_state(app_state)
 		.fallback(controller::catchers_ctrl::not_found_ctrl)
 		.route("/api/openapi", routing::get(openapi_json.clone()))
+		.route("/api/v2/openapi", routing::get(openapi_v2_json.clone()))
 		.route("/health", get(controller::common_ctrl::health))
+		.route(
+			"/problems/:problem_type",
+			get(controller::common_ctrl::problem_type_ctrl),
+		)
+		.layer(middleware::from_fn(api_response::complete_problem))
 		.layer(cors)
 		.layer(super::tracing::add_fmt_layer());
 
@@ -90,6 +282,7 @@ pub async fn create_server() -> Router {
 	#[cfg(debug_assertions)]
 	{
 		std::fs::write("openapi.json", openapi_json).unwrap();
+		std::fs::write("openapi.v2.json", openapi_v2_json).unwrap();
 	}
 
 	#[cfg(not(debug_assertions))]
@@ -128,9 +321,53 @@ pub async fn create_server() -> Router {
 
 		tracing::info!("Reloading!");
 
-		app.merge(SwaggerUi::new("/swagger").url("/openapi.json", doc))
-			.layer(livereload)
+		app.merge(
+			SwaggerUi::new("/swagger")
+				.url("/openapi.json", doc)
+				.url("/openapi.v2.json", doc_v2),
+		)
+		.layer(livereload)
 	};
 
 	app
 }
+
+/// Purges the trash every hour, starting on boot.
+async fn purge_trash_periodically(todo_repo: DynTodoRepository, retention: chrono::Duration) {
+	let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+
+	loop {
+		interval.tick().await;
+
+		match PurgeTrashUsecase::new(&todo_repo).exec(retention).await {
+			Ok(count) => tracing::info!("Purged {} todos from the trash", count),
+			Err(err) => tracing::error!("Error purging the trash: {:?}", err),
+		}
+	}
+}
+
+async fn purge_idempotency_keys_periodically(idempotency_repo: DynIdempotencyRepository) {
+	let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+
+	loop {
+		interval.tick().await;
+
+		match PurgeIdempotencyKeysUsecase::new(&idempotency_repo).exec().await {
+			Ok(count) => tracing::info!("Purged {} expired idempotency keys", count),
+			Err(err) => tracing::error!("Error purging the idempotency keys: {:?}", err),
+		}
+	}
+}
+
+async fn compact_todos_file_periodically(todo_repo: Arc<TodoFileRepository>) {
+	let mut interval = tokio::time::interval(std::time::Duration::from_secs(10 * 60));
+
+	loop {
+		interval.tick().await;
+
+		match todo_repo.compact().await {
+			Ok(count) => tracing::info!("Compacted {} records from the todos file", count),
+			Err(err) => tracing::error!("Error compacting the todos file: {:?}", err),
+		}
+	}
+}
@@ -0,0 +1,19 @@
+use std::str::FromStr;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+
+/// Opens the database file of `database_url`, creating it when missing.
+pub async fn create_sqlite_pool(database_url: &str) -> &'static sqlx::Pool<sqlx::Sqlite> {
+	let options = SqliteConnectOptions::from_str(database_url)
+		.expect("DATABASE_URL is not a valid SQLite URL")
+		.create_if_missing(true)
+		.journal_mode(SqliteJournalMode::Wal);
+
+	let pool = SqlitePoolOptions::new()
+		.max_connections(5)
+		.connect_with(options)
+		.await
+		.expect("Failed to open the SQLite database");
+
+	Box::leak(Box::new(pool))
+}
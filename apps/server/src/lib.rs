@@ -1,3 +1,4 @@
+pub mod conformance;
 pub mod domain;
 pub mod infra;
 pub mod usecase;
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{
+	entity::todo::Todo,
+	exception::TodoException,
+	repository::todo_repository::{
+		DynTodoRepository, TodoRepository, TodoUnitOfWork, UnitOfWorkError,
+	},
+};
+
+use super::{
+	create_todo_usecase::{CreateTodoParams, CreateTodoUsecase},
+	delete_todo_usecase::{DeleteTodoOutput, DeleteTodoUsecase},
+	mark_as_done_todo_usecase::{MarkAsDoneTodoOutput, MarkAsDoneTodoUsecase},
+	update_todo_usecase::{UpdateTodoParams, UpdateTodoUsecase},
+};
+
+pub const MAX_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, ToSchema, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchMode {
+	/// Every operation is applied, or none when one fails
+	#[default]
+	Atomic,
+	/// Each operation is applied on its own, the failed ones are skipped
+	BestEffort,
+}
+
+/// Operation of a batch, `version` being the version the todo must be at like the
+/// `If-Match` header of the single operations.
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchOperation {
+	Create {
+		todo: CreateTodoParams,
+	},
+	Update {
+		id: String,
+		version: Option<i32>,
+		changes: UpdateTodoParams,
+	},
+	MarkDone {
+		id: String,
+		version: Option<i32>,
+	},
+	MarkUndone {
+		id: String,
+		version: Option<i32>,
+	},
+	Delete {
+		id: String,
+		version: Option<i32>,
+	},
+}
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+pub struct BatchTodosParams {
+	#[serde(default)]
+	pub mode: BatchMode,
+	/// Applied in order, at most 500
+	pub operations: Vec<BatchOperation>,
+}
+
+pub enum BatchOutcome {
+	Created(Todo),
+	Updated(Todo),
+	Marked {
+		output: Box<MarkAsDoneTodoOutput>,
+		done: bool,
+	},
+	Deleted(DeleteTodoOutput),
+}
+
+impl BatchOutcome {
+	/// Todo the operation applied to
+	pub fn todo(&self) -> &Todo {
+		match self {
+			BatchOutcome::Created(todo) | BatchOutcome::Updated(todo) => todo,
+			BatchOutcome::Marked { output, .. } => &output.todo,
+			BatchOutcome::Deleted(output) => &output.todo,
+		}
+	}
+}
+
+/// Failure of an atomic batch, none of its operations were applied.
+#[derive(Debug)]
+pub struct BatchFailure {
+	/// Position of the failed operation, `None` when the batch itself is invalid or
+	/// could not be committed
+	pub index: Option<usize>,
+	pub error: TodoException,
+}
+
+impl From<TodoException> for BatchFailure {
+	fn from(error: TodoException) -> Self {
+		Self { index: None, error }
+	}
+}
+
+pub struct BatchTodosUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> BatchTodosUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	/// Returns the outcome of every operation, in order. An atomic batch runs in a single
+	/// unit of work and fails as a whole, its outcomes are then all successful.
+	pub async fn exec(
+		&self,
+		params: BatchTodosParams,
+	) -> Result<Vec<Result<BatchOutcome, TodoException>>, BatchFailure> {
+		if params.operations.is_empty() || params.operations.len() > MAX_BATCH_SIZE {
+			return Err(TodoException::InvalidBatch.into());
+		}
+
+		match params.mode {
+			BatchMode::Atomic => self.exec_atomic(params.operations).await,
+			BatchMode::BestEffort => Ok(self.exec_best_effort(params.operations).await),
+		}
+	}
+
+	async fn exec_atomic(
+		&self,
+		operations: Vec<BatchOperation>,
+	) -> Result<Vec<Result<BatchOutcome, TodoException>>, BatchFailure> {
+		let mut uow = match self.todo_repo.begin().await {
+			Ok(uow) => uow,
+			Err(UnitOfWorkError::DBInternalError) => return Err(TodoException::Storage.into()),
+		};
+
+		let mut outcomes = Vec::with_capacity(operations.len());
+
+		for (index, operation) in operations.into_iter().enumerate() {
+			match Self::apply(uow.as_mut(), operation).await {
+				Ok(outcome) => outcomes.push(Ok(outcome)),
+				Err(error) => {
+					return Err(BatchFailure {
+						index: Some(index),
+						error,
+					})
+				},
+			}
+		}
+
+		if uow.commit().await.is_err() {
+			return Err(TodoException::Storage.into());
+		}
+
+		Ok(outcomes)
+	}
+
+	async fn exec_best_effort(
+		&self,
+		operations: Vec<BatchOperation>,
+	) -> Vec<Result<BatchOutcome, TodoException>> {
+		let mut outcomes = Vec::with_capacity(operations.len());
+
+		for operation in operations {
+			let mut uow = match self.todo_repo.begin().await {
+				Ok(uow) => uow,
+				Err(UnitOfWorkError::DBInternalError) => {
+					outcomes.push(Err(TodoException::Storage));
+					continue;
+				},
+			};
+
+			let outcome = match Self::apply(uow.as_mut(), operation).await {
+				Ok(outcome) => match uow.commit().await {
+					Ok(()) => Ok(outcome),
+					Err(UnitOfWorkError::DBInternalError) => Err(TodoException::Storage),
+				},
+				Err(error) => Err(error),
+			};
+
+			outcomes.push(outcome);
+		}
+
+		outcomes
+	}
+
+	async fn apply(
+		uow: &mut dyn TodoUnitOfWork,
+		operation: BatchOperation,
+	) -> Result<BatchOutcome, TodoException> {
+		match operation {
+			BatchOperation::Create { todo } => {
+				CreateTodoUsecase::exec_in(uow, todo).await.map(BatchOutcome::Created)
+			},
+			BatchOperation::Update {
+				id,
+				version,
+				changes,
+			} => UpdateTodoUsecase::exec_in(uow, id, changes, if_match(&version))
+				.await
+				.map(BatchOutcome::Updated),
+			BatchOperation::MarkDone { id, version } => {
+				MarkAsDoneTodoUsecase::exec_in(uow, id, true, if_match(&version)).await.map(
+					|output| BatchOutcome::Marked {
+						output: Box::new(output),
+						done: true,
+					},
+				)
+			},
+			BatchOperation::MarkUndone { id, version } => {
+				MarkAsDoneTodoUsecase::exec_in(uow, id, false, if_match(&version)).await.map(
+					|output| BatchOutcome::Marked {
+						output: Box::new(output),
+						done: false,
+					},
+				)
+			},
+			BatchOperation::Delete { id, version } => {
+				DeleteTodoUsecase::exec_in(uow, id, if_match(&version))
+					.await
+					.map(BatchOutcome::Deleted)
+			},
+		}
+	}
+}
+
+fn if_match(version: &Option<i32>) -> Option<&[i32]> {
+	version.as_ref().map(std::slice::from_ref)
+}
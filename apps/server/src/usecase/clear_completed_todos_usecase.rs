@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	entity::todo::Todo,
+	exception::TodoException,
+	repository::todo_repository::{DeleteManyError, DynTodoRepository, TodoRepository},
+};
+
+pub struct ClearCompletedTodosUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> ClearCompletedTodosUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	pub async fn exec(&self, list_id: Option<&String>) -> Result<Vec<Todo>, TodoException> {
+		match self.todo_repo.delete_where_done(list_id).await {
+			Ok(todos) => Ok(todos),
+			Err(DeleteManyError::DBInternalError) => Err(TodoException::Storage),
+		}
+	}
+}
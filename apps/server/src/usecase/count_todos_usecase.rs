@@ -1,6 +1,12 @@
 use std::sync::Arc;
 
-use crate::domain::repository::todo_repository::{DynTodoRepository, TodoRepository};
+use crate::{
+	domain::{
+		exception::TodoException,
+		repository::todo_repository::{DynTodoRepository, TodoRepository},
+	},
+	usecase::get_all_todos_usecase::TodoQueryParams,
+};
 
 pub struct CountTodosUsecase<'a> {
 	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
@@ -11,16 +17,9 @@ impl<'a> CountTodosUsecase<'a> {
 		Self { todo_repo }
 	}
 
-	pub async fn exec(&self, status: Option<&String>) -> i64 {
-		let done = match status {
-			Some(status) => match status.as_str() {
-				"done" => Some(&true),
-				"pending" => Some(&false),
-				_ => None,
-			},
-			None => None,
-		};
+	pub async fn exec(&self, params: &TodoQueryParams) -> Result<i64, TodoException> {
+		let query = params.parse()?;
 
-		self.todo_repo.count(done).await.unwrap_or(0)
+		self.todo_repo.count(&query).await.map_err(|_| TodoException::Storage)
 	}
 }
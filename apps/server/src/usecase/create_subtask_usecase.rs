@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::{
+	domain::{
+		entity::todo::Todo,
+		exception::TodoException,
+		repository::todo_repository::{DynTodoRepository, FindTodoError, TodoRepository},
+	},
+	usecase::create_todo_usecase::{CreateTodoParams, CreateTodoUsecase},
+};
+
+pub struct CreateSubtaskUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> CreateSubtaskUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	/// Creates the subtask in the same list as its parent.
+	pub async fn exec(
+		&self,
+		parent_id: String,
+		mut params: CreateTodoParams,
+	) -> Result<Todo, TodoException> {
+		let parent = match self.todo_repo.find_by_id(parent_id).await {
+			Ok(todo) => todo,
+			Err(FindTodoError::NotFound) => return Err(TodoException::NotFound),
+			Err(FindTodoError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		params.list_id = parent.list_id;
+		params.parent_id = Some(parent.id);
+
+		CreateTodoUsecase::new(self.todo_repo).exec(params).await
+	}
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{
+	entity::tag::Tag,
+	exception::TagException,
+	repository::tag_repository::{CreateTagError, DynTagRepository, TagRepository},
+};
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+pub struct CreateTagParams {
+	#[schema(example = "work")]
+	pub name: String,
+}
+
+pub struct CreateTagUsecase<'a> {
+	pub tag_repo: &'a Arc<dyn TagRepository + Send + Sync>,
+}
+
+impl<'a> CreateTagUsecase<'a> {
+	pub fn new(tag_repo: &'a DynTagRepository) -> Self {
+		Self { tag_repo }
+	}
+
+	pub async fn exec(&self, params: CreateTagParams) -> Result<Tag, TagException> {
+		let tag = Tag::new(params.name);
+
+		if tag.name.is_empty() {
+			return Err(TagException::InvalidName);
+		}
+
+		match self.tag_repo.create_tag(tag).await {
+			Ok(tag) => Ok(tag),
+			Err(CreateTagError::AlreadyExists) => Err(TagException::AlreadyExists),
+			Err(CreateTagError::DBInternalError) => Err(TagException::Storage),
+		}
+	}
+}
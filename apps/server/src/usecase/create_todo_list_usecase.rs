@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{
+	entity::todo_list::TodoList,
+	exception::TodoListException,
+	repository::todo_list_repository::{
+		CreateTodoListError, DynTodoListRepository, TodoListRepository,
+	},
+};
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+pub struct CreateTodoListParams {
+	#[schema(example = "Sprint 42")]
+	pub name: String,
+}
+
+pub struct CreateTodoListUsecase<'a> {
+	pub todo_list_repo: &'a Arc<dyn TodoListRepository + Send + Sync>,
+}
+
+impl<'a> CreateTodoListUsecase<'a> {
+	pub fn new(todo_list_repo: &'a DynTodoListRepository) -> Self {
+		Self { todo_list_repo }
+	}
+
+	pub async fn exec(&self, params: CreateTodoListParams) -> Result<TodoList, TodoListException> {
+		let list = TodoList::new(params.name);
+
+		if list.name.is_empty() {
+			return Err(TodoListException::InvalidName);
+		}
+
+		match self.todo_list_repo.create_list(list).await {
+			Ok(list) => Ok(list),
+			Err(CreateTodoListError::DBInternalError) => Err(TodoListException::Storage),
+		}
+	}
+}
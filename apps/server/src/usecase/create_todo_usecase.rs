@@ -4,15 +4,39 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::domain::{
-	entity::todo::Todo,
+	entity::{
+		recurrence::Recurrence,
+		tag::Tag,
+		todo::{Todo, TodoPriority},
+	},
 	exception::TodoException,
-	repository::todo_repository::{DynTodoRepository, TodoRepository},
+	repository::todo_repository::{
+		CreateTodoError, DynTodoRepository, TodoRepository, TodoUnitOfWork,
+	},
 };
 
 #[derive(Debug, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateTodoParams {
 	#[schema(example = "Buy milk")]
 	pub description: String,
+	#[serde(default)]
+	pub due_at: Option<chrono::DateTime<chrono::Utc>>,
+	#[serde(default)]
+	pub priority: TodoPriority,
+	#[serde(default)]
+	#[schema(example = json!(["home"]))]
+	pub tags: Vec<String>,
+	/// `daily`, `weekly`, `monthly`, `yearly` or an RRULE subset
+	#[serde(default)]
+	#[schema(example = "FREQ=WEEKLY;BYDAY=MO")]
+	pub recurrence: Option<String>,
+	/// Set from the list-scoped routes
+	#[serde(skip)]
+	pub list_id: Option<String>,
+	/// Set when creating a subtask
+	#[serde(skip)]
+	pub parent_id: Option<String>,
 }
 
 pub struct CreateTodoUsecase<'a> {
@@ -25,13 +49,42 @@ impl<'a> CreateTodoUsecase<'a> {
 	}
 
 	pub async fn exec(&self, params: CreateTodoParams) -> Result<Todo, TodoException> {
-		let todo = Todo::new(params.description);
+		let todo = Self::build(params)?;
 
 		let new_todo = match self.todo_repo.create_todo(todo).await {
 			Ok(todo) => todo,
-			Err(_) => return Err(TodoException::Unknown),
+			Err(CreateTodoError::DBInternalError) => return Err(TodoException::Storage),
 		};
 
 		Ok(new_todo)
 	}
+
+	/// Creates the todo in the unit of work, left uncommitted.
+	pub async fn exec_in(
+		uow: &mut dyn TodoUnitOfWork,
+		params: CreateTodoParams,
+	) -> Result<Todo, TodoException> {
+		match uow.create_todo(Self::build(params)?).await {
+			Ok(todo) => Ok(todo),
+			Err(CreateTodoError::DBInternalError) => Err(TodoException::Storage),
+		}
+	}
+
+	fn build(params: CreateTodoParams) -> Result<Todo, TodoException> {
+		let mut todo = Todo::new(params.description);
+		todo.due_at = params.due_at;
+		todo.priority = params.priority;
+		todo.tags = Tag::normalize_names(&params.tags);
+		todo.list_id = params.list_id;
+		todo.parent_id = params.parent_id;
+		todo.recurrence = match params.recurrence {
+			Some(rule) => match rule.parse::<Recurrence>() {
+				Ok(recurrence) => Some(recurrence.to_string()),
+				Err(_) => return Err(TodoException::InvalidRecurrence),
+			},
+			None => None,
+		};
+
+		Ok(todo)
+	}
 }
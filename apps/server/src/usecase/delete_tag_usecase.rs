@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	exception::TagException,
+	repository::tag_repository::{DeleteTagError, DynTagRepository, TagRepository},
+};
+
+pub struct DeleteTagUsecase<'a> {
+	pub tag_repo: &'a Arc<dyn TagRepository + Send + Sync>,
+}
+
+impl<'a> DeleteTagUsecase<'a> {
+	pub fn new(tag_repo: &'a DynTagRepository) -> Self {
+		Self { tag_repo }
+	}
+
+	pub async fn exec(&self, id: String) -> Result<(), TagException> {
+		match self.tag_repo.delete_tag(id).await {
+			Ok(()) => Ok(()),
+			Err(DeleteTagError::NotFound) => Err(TagException::NotFound),
+			Err(DeleteTagError::DBInternalError) => Err(TagException::Storage),
+		}
+	}
+}
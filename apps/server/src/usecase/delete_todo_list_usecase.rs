@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	exception::TodoListException,
+	repository::todo_list_repository::{
+		DeleteTodoListError, DynTodoListRepository, TodoListRepository,
+	},
+};
+
+pub struct DeleteTodoListUsecase<'a> {
+	pub todo_list_repo: &'a Arc<dyn TodoListRepository + Send + Sync>,
+}
+
+impl<'a> DeleteTodoListUsecase<'a> {
+	pub fn new(todo_list_repo: &'a DynTodoListRepository) -> Self {
+		Self { todo_list_repo }
+	}
+
+	pub async fn exec(&self, id: String) -> Result<(), TodoListException> {
+		match self.todo_list_repo.delete_list(id).await {
+			Ok(()) => Ok(()),
+			Err(DeleteTodoListError::NotFound) => Err(TodoListException::NotFound),
+			Err(DeleteTodoListError::DBInternalError) => Err(TodoListException::Storage),
+		}
+	}
+}
@@ -1,10 +1,20 @@
 use std::sync::Arc;
 
 use crate::domain::{
+	entity::todo::Todo,
 	exception::TodoException,
-	repository::todo_repository::{DeleteError, DynTodoRepository, TodoRepository},
+	repository::todo_repository::{
+		DeleteError, DynTodoRepository, FindManyTodoError, FindTodoError, TodoRepository,
+		TodoUnitOfWork, UnitOfWorkError,
+	},
 };
 
+pub struct DeleteTodoOutput {
+	pub todo: Todo,
+	/// Subtasks moved to the trash along with the todo
+	pub subtasks: Vec<Todo>,
+}
+
 pub struct DeleteTodoUsecase<'a> {
 	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
 }
@@ -14,11 +24,53 @@ impl<'a> DeleteTodoUsecase<'a> {
 		Self { todo_repo }
 	}
 
-	pub async fn exec(&self, id: String) -> Result<(), TodoException> {
-		match self.todo_repo.delete(id).await {
-			Ok(()) => Ok(()),
-			Err(DeleteError::NotFound) => Err(TodoException::NotFound),
-			Err(_) => Err(TodoException::Unknown),
+	/// `if_match` holds the versions the todo must be at, `None` to delete any version.
+	pub async fn exec(
+		&self,
+		id: String,
+		if_match: Option<&[i32]>,
+	) -> Result<DeleteTodoOutput, TodoException> {
+		let mut uow = match self.todo_repo.begin().await {
+			Ok(uow) => uow,
+			Err(UnitOfWorkError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		let output = Self::exec_in(uow.as_mut(), id, if_match).await?;
+
+		if uow.commit().await.is_err() {
+			return Err(TodoException::Storage);
 		}
+
+		Ok(output)
+	}
+
+	/// Moves the todo to the trash in the unit of work, left uncommitted.
+	pub async fn exec_in(
+		uow: &mut dyn TodoUnitOfWork,
+		id: String,
+		if_match: Option<&[i32]>,
+	) -> Result<DeleteTodoOutput, TodoException> {
+		let todo = match uow.find_by_id(id).await {
+			Ok(todo) => todo,
+			Err(FindTodoError::NotFound) => return Err(TodoException::NotFound),
+			Err(FindTodoError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		if !todo.has_version(if_match) {
+			return Err(TodoException::PreconditionFailed);
+		}
+
+		let subtasks = match uow.find_descendants(todo.id.clone()).await {
+			Ok(subtasks) => subtasks,
+			Err(FindManyTodoError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		match uow.delete(todo.id.clone()).await {
+			Ok(()) => (),
+			Err(DeleteError::NotFound) => return Err(TodoException::NotFound),
+			Err(DeleteError::DBInternalError) => return Err(TodoException::Storage),
+		}
+
+		Ok(DeleteTodoOutput { todo, subtasks })
 	}
 }
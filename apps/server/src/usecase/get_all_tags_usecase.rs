@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	entity::tag::Tag,
+	exception::TagException,
+	repository::tag_repository::{DynTagRepository, FindManyTagError, TagRepository},
+};
+
+pub struct GetAllTagsUsecase<'a> {
+	pub tag_repo: &'a Arc<dyn TagRepository + Send + Sync>,
+}
+
+impl<'a> GetAllTagsUsecase<'a> {
+	pub fn new(tag_repo: &'a DynTagRepository) -> Self {
+		Self { tag_repo }
+	}
+
+	pub async fn exec(&self) -> Result<Vec<Tag>, TagException> {
+		match self.tag_repo.find_many_tags().await {
+			Ok(tags) => Ok(tags),
+			Err(FindManyTagError::DBInternalError) => Err(TagException::Storage),
+		}
+	}
+}
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	entity::todo_list::TodoList,
+	exception::TodoListException,
+	repository::todo_list_repository::{
+		DynTodoListRepository, FindManyTodoListError, TodoListRepository,
+	},
+};
+
+pub struct GetAllTodoListsUsecase<'a> {
+	pub todo_list_repo: &'a Arc<dyn TodoListRepository + Send + Sync>,
+}
+
+impl<'a> GetAllTodoListsUsecase<'a> {
+	pub fn new(todo_list_repo: &'a DynTodoListRepository) -> Self {
+		Self { todo_list_repo }
+	}
+
+	pub async fn exec(&self) -> Result<Vec<TodoList>, TodoListException> {
+		match self.todo_list_repo.find_many_lists().await {
+			Ok(lists) => Ok(lists),
+			Err(FindManyTodoListError::DBInternalError) => Err(TodoListException::Storage),
+		}
+	}
+}
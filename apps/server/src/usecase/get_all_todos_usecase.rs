@@ -1,11 +1,130 @@
 use std::sync::Arc;
 
 use crate::domain::{
-	entity::todo::Todo,
+	entity::{
+		tag::Tag,
+		todo::{DueFilter, Todo, TodoOrder, TodoPriority},
+		todo_query::{DateRange, SortDirection, TodoQuery, TodoSort, TodoStatus},
+	},
 	exception::TodoException,
-	repository::todo_repository::{DynTodoRepository, TodoRepository},
+	repository::todo_repository::{DynTodoRepository, FindManyTodoError, TodoRepository},
 };
 
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MAX_PAGE_SIZE: i64 = 200;
+
+/// Raw query of the todos, as received by the controllers.
+#[derive(Debug, Default, Clone)]
+pub struct TodoQueryParams {
+	pub status: Option<String>,
+	pub due: Option<String>,
+	pub priority: Option<String>,
+	pub tag: Option<String>,
+	pub list_id: Option<String>,
+	pub created_after: Option<String>,
+	pub created_before: Option<String>,
+	pub done_after: Option<String>,
+	pub done_before: Option<String>,
+	pub contains: Option<String>,
+	pub search: Option<String>,
+	pub order: Option<String>,
+	pub direction: Option<String>,
+	pub cursor: Option<String>,
+	pub limit: Option<i64>,
+}
+
+impl TodoQueryParams {
+	/// Parses the params, empty values are ignored and unknown ones rejected.
+	pub fn parse(&self) -> Result<TodoQuery, TodoException> {
+		let status = match non_empty(&self.status) {
+			None | Some("all") => None,
+			Some("pending") => Some(TodoStatus::Pending),
+			Some("done") => Some(TodoStatus::Done),
+			Some(_) => return Err(TodoException::InvalidStatus),
+		};
+
+		let due = match non_empty(&self.due) {
+			None => None,
+			Some("overdue") => Some(DueFilter::Overdue),
+			Some("today") => Some(DueFilter::Today),
+			Some("upcoming") => Some(DueFilter::Upcoming),
+			Some(_) => return Err(TodoException::InvalidQuery),
+		};
+
+		let priority = match non_empty(&self.priority) {
+			None => None,
+			Some("none") => Some(TodoPriority::None),
+			Some("low") => Some(TodoPriority::Low),
+			Some("medium") => Some(TodoPriority::Medium),
+			Some("high") => Some(TodoPriority::High),
+			Some("urgent") => Some(TodoPriority::Urgent),
+			Some(_) => return Err(TodoException::InvalidQuery),
+		};
+
+		let mut sort = TodoSort::new(match non_empty(&self.order) {
+			None | Some("created_at") => TodoOrder::CreatedAt,
+			Some("priority") => TodoOrder::Priority,
+			Some("manual") => TodoOrder::Manual,
+			Some(_) => return Err(TodoException::InvalidQuery),
+		});
+		match non_empty(&self.direction) {
+			None => {},
+			Some("asc") => sort.direction = SortDirection::Asc,
+			Some("desc") => sort.direction = SortDirection::Desc,
+			Some(_) => return Err(TodoException::InvalidQuery),
+		}
+
+		Ok(TodoQuery {
+			status,
+			due,
+			priority,
+			tag: non_empty(&self.tag).map(Tag::normalize_name),
+			list_id: non_empty(&self.list_id).map(str::to_string),
+			created: DateRange {
+				from: parse_date(&self.created_after)?,
+				to: parse_date(&self.created_before)?,
+			},
+			done: DateRange {
+				from: parse_date(&self.done_after)?,
+				to: parse_date(&self.done_before)?,
+			},
+			contains: non_empty(&self.contains).map(str::to_string),
+			search: non_empty(&self.search).map(str::to_string),
+			sort,
+			cursor: non_empty(&self.cursor).map(str::to_string),
+			limit: self.limit,
+		})
+	}
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+	value.as_deref().map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Accepts RFC 3339 dates and plain `YYYY-MM-DD` days, starting at midnight UTC.
+fn parse_date(
+	value: &Option<String>,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, TodoException> {
+	let Some(value) = non_empty(value) else {
+		return Ok(None);
+	};
+
+	chrono::DateTime::parse_from_rfc3339(value)
+		.map(|date| date.with_timezone(&chrono::Utc))
+		.or_else(|_| {
+			chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+				.map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+		})
+		.map(Some)
+		.map_err(|_| TodoException::InvalidQuery)
+}
+
+pub struct TodosPage {
+	pub todos: Vec<Todo>,
+	/// Id of the last todo, `None` on the last page
+	pub next_cursor: Option<String>,
+}
+
 pub struct GetAllTodosUsecase<'a> {
 	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
 }
@@ -15,19 +134,41 @@ impl<'a> GetAllTodosUsecase<'a> {
 		Self { todo_repo }
 	}
 
-	pub async fn exec(&self, status: Option<&String>) -> Result<Vec<Todo>, TodoException> {
-		let done: Option<&bool> = match status {
-			Some(status) => match status.as_str() {
-				"done" => Some(&true),
-				"pending" => Some(&false),
-				_ => None,
-			},
-			None => None,
+	/// `default_limit` is the page size when the params have no limit, `None` returning
+	/// every todo after the cursor in a single page.
+	pub async fn exec(
+		&self,
+		params: &TodoQueryParams,
+		default_limit: Option<i64>,
+	) -> Result<TodosPage, TodoException> {
+		let mut query = params.parse()?;
+
+		let Some(limit) = query.limit.or(default_limit).map(|limit| limit.clamp(1, MAX_PAGE_SIZE))
+		else {
+			return match self.todo_repo.find_many_todos(&query).await {
+				Ok(todos) => Ok(TodosPage {
+					todos,
+					next_cursor: None,
+				}),
+				Err(FindManyTodoError::DBInternalError) => Err(TodoException::Storage),
+			};
 		};
 
-		match self.todo_repo.find_many_todos(done).await {
-			Ok(todos) => Ok(todos),
-			Err(_) => Err(TodoException::Unknown),
+		// one more todo is fetched to know if there is a next page
+		query.limit = Some(limit + 1);
+
+		match self.todo_repo.find_many_todos(&query).await {
+			Ok(mut todos) => {
+				let next_cursor = if todos.len() as i64 > limit {
+					todos.truncate(limit as usize);
+					todos.last().map(|todo| todo.id.clone())
+				} else {
+					None
+				};
+
+				Ok(TodosPage { todos, next_cursor })
+			},
+			Err(FindManyTodoError::DBInternalError) => Err(TodoException::Storage),
 		}
 	}
 }
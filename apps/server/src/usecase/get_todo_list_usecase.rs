@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	entity::todo_list::TodoList,
+	exception::TodoListException,
+	repository::todo_list_repository::{
+		DynTodoListRepository, FindTodoListError, TodoListRepository,
+	},
+};
+
+pub struct GetTodoListUsecase<'a> {
+	pub todo_list_repo: &'a Arc<dyn TodoListRepository + Send + Sync>,
+}
+
+impl<'a> GetTodoListUsecase<'a> {
+	pub fn new(todo_list_repo: &'a DynTodoListRepository) -> Self {
+		Self { todo_list_repo }
+	}
+
+	pub async fn exec(&self, id: String) -> Result<TodoList, TodoListException> {
+		match self.todo_list_repo.find_list_by_id(id).await {
+			Ok(list) => Ok(list),
+			Err(FindTodoListError::NotFound) => Err(TodoListException::NotFound),
+			Err(FindTodoListError::DBInternalError) => Err(TodoListException::Storage),
+		}
+	}
+}
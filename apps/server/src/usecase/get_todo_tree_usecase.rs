@@ -0,0 +1,76 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::domain::{
+	entity::todo::{SubtaskProgress, Todo, TodoTree},
+	exception::TodoException,
+	repository::todo_repository::{
+		DynTodoRepository, FindManyTodoError, FindTodoError, TodoRepository,
+	},
+};
+
+pub struct GetTodoTreeUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> GetTodoTreeUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	pub async fn exec(&self, id: String) -> Result<TodoTree, TodoException> {
+		let todo = match self.todo_repo.find_by_id(id).await {
+			Ok(todo) => todo,
+			Err(FindTodoError::NotFound) => return Err(TodoException::NotFound),
+			Err(FindTodoError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		let descendants = match self.todo_repo.find_descendants(todo.id.clone()).await {
+			Ok(todos) => todos,
+			Err(FindManyTodoError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		let mut children = group_by_parent(descendants);
+
+		Ok(build_tree(todo, &mut children))
+	}
+}
+
+/// Nests the todos under their parent when it is part of `todos`, the others are
+/// the roots of the forest and keep their order.
+///
+/// Progress only counts the subtasks found in `todos`.
+pub fn build_forest(todos: Vec<Todo>) -> Vec<TodoTree> {
+	let ids: Vec<String> = todos.iter().map(|todo| todo.id.clone()).collect();
+
+	let (roots, subtasks): (Vec<Todo>, Vec<Todo>) = todos.into_iter().partition(|todo| {
+		todo.parent_id.as_ref().map_or(true, |parent_id| !ids.contains(parent_id))
+	});
+
+	let mut children = group_by_parent(subtasks);
+
+	roots.into_iter().map(|todo| build_tree(todo, &mut children)).collect()
+}
+
+fn group_by_parent(todos: Vec<Todo>) -> HashMap<String, Vec<Todo>> {
+	let mut children: HashMap<String, Vec<Todo>> = HashMap::new();
+
+	for todo in todos {
+		if let Some(parent_id) = todo.parent_id.clone() {
+			children.entry(parent_id).or_default().push(todo);
+		}
+	}
+
+	children
+}
+
+/// Subtasks follow the manual order.
+fn build_tree(todo: Todo, children: &mut HashMap<String, Vec<Todo>>) -> TodoTree {
+	let mut subtasks = children.remove(&todo.id).unwrap_or_default();
+	subtasks.sort_by(|a, b| a.position.total_cmp(&b.position));
+
+	TodoTree {
+		progress: SubtaskProgress::of(&subtasks),
+		subtasks: subtasks.into_iter().map(|subtask| build_tree(subtask, children)).collect(),
+		todo,
+	}
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	entity::todo::Todo,
+	exception::TodoException,
+	repository::todo_repository::{DynTodoRepository, FindManyTodoError, TodoRepository},
+};
+
+pub struct GetTrashUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> GetTrashUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	pub async fn exec(&self) -> Result<Vec<Todo>, TodoException> {
+		match self.todo_repo.find_deleted().await {
+			Ok(todos) => Ok(todos),
+			Err(FindManyTodoError::DBInternalError) => Err(TodoException::Storage),
+		}
+	}
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	entity::idempotency_key::{IdempotencyKey, StoredResponse},
+	exception::IdempotencyException,
+	repository::idempotency_repository::{
+		ClaimKeyError, CompleteKeyError, DynIdempotencyRepository, IdempotencyRepository,
+		ReleaseKeyError,
+	},
+};
+
+pub enum IdempotentRequest {
+	/// First request made with the key, to handle then complete or release
+	New,
+	/// Retry of a request already handled, answered with its response
+	Replay(StoredResponse),
+}
+
+pub struct IdempotentRequestUsecase<'a> {
+	pub idempotency_repo: &'a Arc<dyn IdempotencyRepository + Send + Sync>,
+}
+
+impl<'a> IdempotentRequestUsecase<'a> {
+	pub fn new(idempotency_repo: &'a DynIdempotencyRepository) -> Self {
+		Self { idempotency_repo }
+	}
+
+	/// Claims the key for the request of `fingerprint` during `ttl`, unless the key was
+	/// already sent for the same request.
+	pub async fn exec(
+		&self,
+		key: String,
+		fingerprint: String,
+		ttl: chrono::Duration,
+	) -> Result<IdempotentRequest, IdempotencyException> {
+		if key.is_empty() || key.len() > 255 || !key.bytes().all(|byte| byte.is_ascii_graphic()) {
+			return Err(IdempotencyException::InvalidKey);
+		}
+
+		let claimed = IdempotencyKey {
+			key,
+			fingerprint,
+			response: None,
+			expires_at: chrono::Utc::now() + ttl,
+		};
+
+		let existing = match self.idempotency_repo.claim_key(claimed.clone()).await {
+			Ok(Some(existing)) => existing,
+			Ok(None) => return Ok(IdempotentRequest::New),
+			Err(ClaimKeyError::DBInternalError) => return Err(IdempotencyException::Storage),
+		};
+
+		if existing.fingerprint != claimed.fingerprint {
+			return Err(IdempotencyException::KeyReused);
+		}
+
+		match existing.response {
+			Some(response) => Ok(IdempotentRequest::Replay(response)),
+			None => Err(IdempotencyException::InProgress),
+		}
+	}
+
+	/// Saves the response to replay to the retries of the request which claimed the key.
+	pub async fn complete(
+		&self,
+		key: String,
+		response: StoredResponse,
+	) -> Result<(), IdempotencyException> {
+		match self.idempotency_repo.complete_key(key, response).await {
+			Ok(()) => Ok(()),
+			Err(CompleteKeyError::DBInternalError) => Err(IdempotencyException::Storage),
+		}
+	}
+
+	/// Frees the key when its request failed, so that a retry handles it again.
+	pub async fn release(&self, key: String) -> Result<(), IdempotencyException> {
+		match self.idempotency_repo.release_key(key).await {
+			Ok(()) => Ok(()),
+			Err(ReleaseKeyError::DBInternalError) => Err(IdempotencyException::Storage),
+		}
+	}
+}
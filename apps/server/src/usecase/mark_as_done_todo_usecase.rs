@@ -3,9 +3,20 @@ use std::sync::Arc;
 use crate::domain::{
 	entity::todo::Todo,
 	exception::TodoException,
-	repository::todo_repository::{DynTodoRepository, TodoRepository, UpdateError},
+	repository::todo_repository::{
+		CreateTodoError, DynTodoRepository, FindManyTodoError, FindTodoError, TodoRepository,
+		TodoUnitOfWork, UnitOfWorkError, UpdateError,
+	},
 };
 
+pub struct MarkAsDoneTodoOutput {
+	pub todo: Todo,
+	/// Subtasks or parents whose state changed along with the todo
+	pub cascaded: Vec<Todo>,
+	/// Created when completing a recurring todo
+	pub next_occurrence: Option<Todo>,
+}
+
 pub struct MarkAsDoneTodoUsecase<'a> {
 	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
 }
@@ -15,17 +26,107 @@ impl<'a> MarkAsDoneTodoUsecase<'a> {
 		Self { todo_repo }
 	}
 
-	pub async fn exec(&self, id: String, done: bool) -> Result<Todo, TodoException> {
-		let mut todo = self.todo_repo.find_by_id(id).await.unwrap();
+	/// A todo can not be done while one of its subtasks is pending: marking a todo
+	/// as done completes its pending subtasks, marking it as undone reopens its done
+	/// parents.
+	///
+	/// Completing a pending recurring todo creates its next occurrence. Every change is
+	/// made in a single unit of work.
+	///
+	/// `if_match` holds the versions the todo must be at, `None` to mark any version.
+	pub async fn exec(
+		&self,
+		id: String,
+		done: bool,
+		if_match: Option<&[i32]>,
+	) -> Result<MarkAsDoneTodoOutput, TodoException> {
+		let mut uow = match self.todo_repo.begin().await {
+			Ok(uow) => uow,
+			Err(UnitOfWorkError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		let output = Self::exec_in(uow.as_mut(), id, done, if_match).await?;
+
+		if uow.commit().await.is_err() {
+			return Err(TodoException::Storage);
+		}
+
+		Ok(output)
+	}
+
+	/// Marks the todo in the unit of work, left uncommitted.
+	pub async fn exec_in(
+		uow: &mut dyn TodoUnitOfWork,
+		id: String,
+		done: bool,
+		if_match: Option<&[i32]>,
+	) -> Result<MarkAsDoneTodoOutput, TodoException> {
+		let mut todo = Self::find(uow, id).await?;
+
+		if !todo.has_version(if_match) {
+			return Err(TodoException::PreconditionFailed);
+		}
+
+		let next_occurrence = match done && !todo.done {
+			true => todo.next_occurrence(chrono::Utc::now()),
+			false => None,
+		};
 
-		todo = todo.mark_as_done(done).to_owned();
+		todo = Self::save(uow, todo.mark_as_done(done).to_owned()).await?;
 
-		match self.todo_repo.update(todo.clone()).await {
-			Ok(todo) => todo,
-			Err(UpdateError::NotFound) => return Err(TodoException::NotFound),
-			Err(_) => return Err(TodoException::Unknown),
+		let next_occurrence = match next_occurrence {
+			Some(next) => match uow.create_todo(next).await {
+				Ok(next) => Some(next),
+				Err(CreateTodoError::DBInternalError) => return Err(TodoException::Storage),
+			},
+			None => None,
 		};
 
-		Ok(todo)
+		let mut cascaded: Vec<Todo> = vec![];
+
+		if done {
+			let descendants = match uow.find_descendants(todo.id.clone()).await {
+				Ok(todos) => todos,
+				Err(FindManyTodoError::DBInternalError) => return Err(TodoException::Storage),
+			};
+
+			for mut subtask in descendants.into_iter().filter(|subtask| !subtask.done) {
+				cascaded.push(Self::save(uow, subtask.mark_as_done(true).to_owned()).await?);
+			}
+		} else {
+			let mut parent_id = todo.parent_id.clone();
+
+			while let Some(id) = parent_id {
+				let mut parent = Self::find(uow, id).await?;
+				parent_id = parent.parent_id.clone();
+
+				if parent.done {
+					cascaded.push(Self::save(uow, parent.mark_as_done(false).to_owned()).await?);
+				}
+			}
+		}
+
+		Ok(MarkAsDoneTodoOutput {
+			todo,
+			cascaded,
+			next_occurrence,
+		})
+	}
+
+	async fn find(uow: &mut dyn TodoUnitOfWork, id: String) -> Result<Todo, TodoException> {
+		match uow.find_by_id(id).await {
+			Ok(todo) => Ok(todo),
+			Err(FindTodoError::NotFound) => Err(TodoException::NotFound),
+			Err(FindTodoError::DBInternalError) => Err(TodoException::Storage),
+		}
+	}
+
+	async fn save(uow: &mut dyn TodoUnitOfWork, todo: Todo) -> Result<Todo, TodoException> {
+		match uow.update(todo).await {
+			Ok(todo) => Ok(todo),
+			Err(UpdateError::NotFound) => Err(TodoException::NotFound),
+			Err(UpdateError::Conflict) => Err(TodoException::Conflict),
+			Err(UpdateError::DBInternalError) => Err(TodoException::Storage),
+		}
 	}
 }
@@ -1,6 +1,27 @@
+pub mod batch_todos_usecase;
+pub mod clear_completed_todos_usecase;
 pub mod count_todos_usecase;
+pub mod create_subtask_usecase;
+pub mod create_tag_usecase;
+pub mod create_todo_list_usecase;
 pub mod create_todo_usecase;
+pub mod delete_tag_usecase;
+pub mod delete_todo_list_usecase;
 pub mod delete_todo_usecase;
+pub mod get_all_tags_usecase;
+pub mod get_all_todo_lists_usecase;
 pub mod get_all_todos_usecase;
+pub mod get_todo_list_usecase;
+pub mod get_todo_tree_usecase;
+pub mod get_trash_usecase;
 pub mod health_usecase;
+pub mod idempotent_request_usecase;
 pub mod mark_as_done_todo_usecase;
+pub mod move_todo_usecase;
+pub mod patch_todo_usecase;
+pub mod purge_idempotency_keys_usecase;
+pub mod purge_trash_usecase;
+pub mod rename_tag_usecase;
+pub mod rename_todo_list_usecase;
+pub mod restore_todo_usecase;
+pub mod update_todo_usecase;
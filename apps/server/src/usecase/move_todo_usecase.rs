@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{
+	entity::todo::Todo,
+	exception::TodoException,
+	repository::todo_repository::{
+		DynTodoRepository, FindManyTodoError, FindTodoError, TodoRepository, TodoUnitOfWork,
+		UnitOfWorkError, UpdateError,
+	},
+};
+
+/// Neighbours of the todo once moved in the manual order, at least one is required.
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveTodoParams {
+	/// Todo right above the moved one, omitted to move it to the top
+	#[serde(default)]
+	pub after_id: Option<String>,
+	/// Todo right below the moved one, omitted to move it to the bottom
+	#[serde(default)]
+	pub before_id: Option<String>,
+}
+
+pub struct MoveTodoUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> MoveTodoUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	/// `if_match` holds the versions the todo must be at, `None` to move any version.
+	pub async fn exec(
+		&self,
+		id: String,
+		params: MoveTodoParams,
+		if_match: Option<&[i32]>,
+	) -> Result<Todo, TodoException> {
+		let mut uow = match self.todo_repo.begin().await {
+			Ok(uow) => uow,
+			Err(UnitOfWorkError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		let mut todo = Self::find(uow.as_mut(), id).await?;
+
+		if !todo.has_version(if_match) {
+			return Err(TodoException::PreconditionFailed);
+		}
+
+		let after = match params.after_id {
+			Some(id) => Some(Self::find_neighbour(uow.as_mut(), id, &todo).await?),
+			None => None,
+		};
+		let before = match params.before_id {
+			Some(id) => Some(Self::find_neighbour(uow.as_mut(), id, &todo).await?),
+			None => None,
+		};
+
+		let after_position = after.as_ref().map(|after| after.position);
+		let before_position = before.as_ref().map(|before| before.position);
+		if todo.move_between(after_position, before_position).is_none() {
+			// The neighbours may only be too close, spread the positions and try again.
+			let (after, before) = Self::renumber(uow.as_mut(), &todo, after, before).await?;
+
+			if todo.move_between(after, before).is_none() {
+				return Err(TodoException::InvalidMove);
+			}
+		}
+
+		let todo = Self::update(uow.as_mut(), todo).await?;
+
+		if uow.commit().await.is_err() {
+			return Err(TodoException::Storage);
+		}
+
+		Ok(todo)
+	}
+
+	/// The neighbours have to be in the list and under the parent of the moved todo.
+	async fn find_neighbour(
+		uow: &mut dyn TodoUnitOfWork,
+		id: String,
+		todo: &Todo,
+	) -> Result<Todo, TodoException> {
+		let neighbour = Self::find(uow, id).await?;
+
+		if neighbour.list_id != todo.list_id || neighbour.parent_id != todo.parent_id {
+			return Err(TodoException::InvalidMove);
+		}
+
+		Ok(neighbour)
+	}
+
+	/// Renumbers the siblings of the moved todo one apart in their manual order, returns
+	/// the new positions of the neighbours.
+	async fn renumber(
+		uow: &mut dyn TodoUnitOfWork,
+		todo: &Todo,
+		after: Option<Todo>,
+		before: Option<Todo>,
+	) -> Result<(Option<f64>, Option<f64>), TodoException> {
+		let siblings = match uow.find_siblings(todo.list_id.clone(), todo.parent_id.clone()).await {
+			Ok(siblings) => siblings,
+			Err(FindManyTodoError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		let mut positions = (None, None);
+		let siblings = siblings.into_iter().filter(|sibling| sibling.id != todo.id);
+		for (index, mut sibling) in siblings.enumerate() {
+			let position = index as f64;
+
+			if after.as_ref().is_some_and(|after| after.id == sibling.id) {
+				positions.0 = Some(position);
+			}
+			if before.as_ref().is_some_and(|before| before.id == sibling.id) {
+				positions.1 = Some(position);
+			}
+
+			if sibling.position != position {
+				sibling.position = position;
+				Self::update(uow, sibling).await?;
+			}
+		}
+
+		Ok(positions)
+	}
+
+	async fn update(uow: &mut dyn TodoUnitOfWork, todo: Todo) -> Result<Todo, TodoException> {
+		match uow.update(todo).await {
+			Ok(todo) => Ok(todo),
+			Err(UpdateError::NotFound) => Err(TodoException::NotFound),
+			Err(UpdateError::Conflict) => Err(TodoException::Conflict),
+			Err(UpdateError::DBInternalError) => Err(TodoException::Storage),
+		}
+	}
+
+	async fn find(uow: &mut dyn TodoUnitOfWork, id: String) -> Result<Todo, TodoException> {
+		match uow.find_by_id(id).await {
+			Ok(todo) => Ok(todo),
+			Err(FindTodoError::NotFound) => Err(TodoException::NotFound),
+			Err(FindTodoError::DBInternalError) => Err(TodoException::Storage),
+		}
+	}
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use json_patch::PatchErrorKind;
+use serde_json::Value;
+
+use crate::domain::{
+	entity::todo::Todo,
+	exception::TodoException,
+	repository::todo_repository::{
+		DynTodoRepository, FindTodoError, TodoRepository, UnitOfWorkError, UpdateError,
+	},
+};
+
+use super::mark_as_done_todo_usecase::MarkAsDoneTodoUsecase;
+
+/// Patch document applied to the serialized todo.
+pub enum TodoPatch {
+	/// RFC 7396 JSON Merge Patch
+	Merge(Value),
+	/// RFC 6902 JSON Patch
+	Json(json_patch::Patch),
+}
+
+pub struct PatchTodoUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> PatchTodoUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	/// Only `description` and `done` are writable, the patch may still test or copy the
+	/// other fields as long as it leaves them unchanged. Changing `done` follows the
+	/// mark as done rules, cascading to the subtasks or parents and creating the next
+	/// occurrence of a recurring todo.
+	///
+	/// `if_match` holds the versions the todo must be at, `None` to patch any version.
+	pub async fn exec(
+		&self,
+		id: String,
+		patch: TodoPatch,
+		if_match: Option<&[i32]>,
+	) -> Result<Todo, TodoException> {
+		let mut uow = match self.todo_repo.begin().await {
+			Ok(uow) => uow,
+			Err(UnitOfWorkError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		let mut todo = match uow.find_by_id(id).await {
+			Ok(todo) => todo,
+			Err(FindTodoError::NotFound) => return Err(TodoException::NotFound),
+			Err(FindTodoError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		if !todo.has_version(if_match) {
+			return Err(TodoException::PreconditionFailed);
+		}
+
+		let (description, done) = Self::patched_fields(&todo, patch)?;
+
+		if description != todo.description {
+			todo = match uow.update(todo.update_description(description).to_owned()).await {
+				Ok(todo) => todo,
+				Err(UpdateError::NotFound) => return Err(TodoException::NotFound),
+				Err(UpdateError::Conflict) => return Err(TodoException::Conflict),
+				Err(UpdateError::DBInternalError) => return Err(TodoException::Storage),
+			};
+		}
+
+		if done != todo.done {
+			todo = MarkAsDoneTodoUsecase::exec_in(uow.as_mut(), todo.id, done, None).await?.todo;
+		}
+
+		if uow.commit().await.is_err() {
+			return Err(TodoException::Storage);
+		}
+
+		Ok(todo)
+	}
+
+	/// Applies the patch to the serialized todo, returns the description and done state
+	/// it ends up with.
+	fn patched_fields(todo: &Todo, patch: TodoPatch) -> Result<(String, bool), TodoException> {
+		let Ok(Value::Object(mut original)) = serde_json::to_value(todo) else {
+			return Err(TodoException::Storage);
+		};
+
+		let mut patched = Value::Object(original.clone());
+		match patch {
+			TodoPatch::Merge(patch) => json_patch::merge(&mut patched, &patch),
+			TodoPatch::Json(patch) => match json_patch::patch(&mut patched, &patch) {
+				Ok(()) => (),
+				// a failed test is how a patch checks the todo is unchanged
+				Err(err) if matches!(err.kind, PatchErrorKind::TestFailed) => {
+					return Err(TodoException::Conflict)
+				},
+				Err(_) => return Err(TodoException::InvalidPatch),
+			},
+		}
+
+		let Value::Object(mut patched) = patched else {
+			return Err(TodoException::InvalidPatch);
+		};
+
+		let description = patched.remove("description");
+		let done = patched.remove("done");
+
+		original.remove("description");
+		original.remove("done");
+		if original != patched {
+			return Err(TodoException::ReadOnlyField);
+		}
+
+		match (description, done) {
+			(Some(Value::String(description)), Some(Value::Bool(done))) => Ok((description, done)),
+			_ => Err(TodoException::InvalidPatch),
+		}
+	}
+}
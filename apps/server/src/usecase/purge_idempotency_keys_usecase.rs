@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	exception::IdempotencyException,
+	repository::idempotency_repository::{
+		DynIdempotencyRepository, IdempotencyRepository, PurgeKeysError,
+	},
+};
+
+pub struct PurgeIdempotencyKeysUsecase<'a> {
+	pub idempotency_repo: &'a Arc<dyn IdempotencyRepository + Send + Sync>,
+}
+
+impl<'a> PurgeIdempotencyKeysUsecase<'a> {
+	pub fn new(idempotency_repo: &'a DynIdempotencyRepository) -> Self {
+		Self { idempotency_repo }
+	}
+
+	/// Deletes the expired idempotency keys, returns how many were purged.
+	pub async fn exec(&self) -> Result<u64, IdempotencyException> {
+		match self.idempotency_repo.purge_expired_keys(chrono::Utc::now()).await {
+			Ok(count) => Ok(count),
+			Err(PurgeKeysError::DBInternalError) => Err(IdempotencyException::Storage),
+		}
+	}
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	exception::TodoException,
+	repository::todo_repository::{DynTodoRepository, PurgeError, TodoRepository},
+};
+
+pub struct PurgeTrashUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> PurgeTrashUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	/// Permanently deletes the todos that stayed in the trash longer than `retention`,
+	/// returns how many were purged.
+	pub async fn exec(&self, retention: chrono::Duration) -> Result<u64, TodoException> {
+		match self.todo_repo.purge_deleted(chrono::Utc::now() - retention).await {
+			Ok(count) => Ok(count),
+			Err(PurgeError::DBInternalError) => Err(TodoException::Storage),
+		}
+	}
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{
+	entity::tag::Tag,
+	exception::TagException,
+	repository::tag_repository::{DynTagRepository, FindTagError, TagRepository, UpdateTagError},
+};
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+pub struct RenameTagParams {
+	#[schema(example = "office")]
+	pub name: String,
+}
+
+pub struct RenameTagUsecase<'a> {
+	pub tag_repo: &'a Arc<dyn TagRepository + Send + Sync>,
+}
+
+impl<'a> RenameTagUsecase<'a> {
+	pub fn new(tag_repo: &'a DynTagRepository) -> Self {
+		Self { tag_repo }
+	}
+
+	pub async fn exec(&self, id: String, params: RenameTagParams) -> Result<Tag, TagException> {
+		let mut tag = match self.tag_repo.find_tag_by_id(id).await {
+			Ok(tag) => tag,
+			Err(FindTagError::NotFound) => return Err(TagException::NotFound),
+			Err(FindTagError::DBInternalError) => return Err(TagException::Storage),
+		};
+
+		tag.rename(params.name);
+
+		if tag.name.is_empty() {
+			return Err(TagException::InvalidName);
+		}
+
+		match self.tag_repo.update_tag(tag).await {
+			Ok(tag) => Ok(tag),
+			Err(UpdateTagError::NotFound) => Err(TagException::NotFound),
+			Err(UpdateTagError::AlreadyExists) => Err(TagException::AlreadyExists),
+			Err(UpdateTagError::DBInternalError) => Err(TagException::Storage),
+		}
+	}
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{
+	entity::todo_list::TodoList,
+	exception::TodoListException,
+	repository::todo_list_repository::{
+		DynTodoListRepository, FindTodoListError, TodoListRepository, UpdateTodoListError,
+	},
+};
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+pub struct RenameTodoListParams {
+	#[schema(example = "Sprint 43")]
+	pub name: String,
+}
+
+pub struct RenameTodoListUsecase<'a> {
+	pub todo_list_repo: &'a Arc<dyn TodoListRepository + Send + Sync>,
+}
+
+impl<'a> RenameTodoListUsecase<'a> {
+	pub fn new(todo_list_repo: &'a DynTodoListRepository) -> Self {
+		Self { todo_list_repo }
+	}
+
+	pub async fn exec(
+		&self,
+		id: String,
+		params: RenameTodoListParams,
+	) -> Result<TodoList, TodoListException> {
+		let mut list = match self.todo_list_repo.find_list_by_id(id).await {
+			Ok(list) => list,
+			Err(FindTodoListError::NotFound) => return Err(TodoListException::NotFound),
+			Err(FindTodoListError::DBInternalError) => return Err(TodoListException::Storage),
+		};
+
+		list.rename(params.name);
+
+		if list.name.is_empty() {
+			return Err(TodoListException::InvalidName);
+		}
+
+		match self.todo_list_repo.update_list(list).await {
+			Ok(list) => Ok(list),
+			Err(UpdateTodoListError::NotFound) => Err(TodoListException::NotFound),
+			Err(UpdateTodoListError::DBInternalError) => Err(TodoListException::Storage),
+		}
+	}
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::domain::{
+	entity::todo::Todo,
+	exception::TodoException,
+	repository::todo_repository::{DynTodoRepository, RestoreError, TodoRepository},
+};
+
+pub struct RestoreTodoUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> RestoreTodoUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	pub async fn exec(&self, id: String) -> Result<Todo, TodoException> {
+		match self.todo_repo.restore(id).await {
+			Ok(todo) => Ok(todo),
+			Err(RestoreError::NotFound) => Err(TodoException::NotFound),
+			Err(RestoreError::DBInternalError) => Err(TodoException::Storage),
+		}
+	}
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{
+	entity::{
+		recurrence::Recurrence,
+		todo::{Todo, TodoPriority},
+	},
+	exception::TodoException,
+	repository::todo_repository::{
+		DynTodoRepository, FindTodoError, TodoRepository, TodoUnitOfWork, UnitOfWorkError,
+		UpdateError,
+	},
+};
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+pub struct UpdateTodoParams {
+	#[schema(example = "Buy oat milk")]
+	pub description: Option<String>,
+	pub priority: Option<TodoPriority>,
+	/// Replaces every tag of the todo
+	pub tags: Option<Vec<String>>,
+	/// `daily`, `weekly`, `monthly`, `yearly` or an RRULE subset, an empty rule stops the recurrence
+	#[schema(example = "FREQ=WEEKLY;BYDAY=MO")]
+	pub recurrence: Option<String>,
+}
+
+pub struct UpdateTodoUsecase<'a> {
+	pub todo_repo: &'a Arc<dyn TodoRepository + Send + Sync>,
+}
+
+impl<'a> UpdateTodoUsecase<'a> {
+	pub fn new(todo_repo: &'a DynTodoRepository) -> Self {
+		Self { todo_repo }
+	}
+
+	/// `if_match` holds the versions the todo must be at, `None` to update any version.
+	pub async fn exec(
+		&self,
+		id: String,
+		params: UpdateTodoParams,
+		if_match: Option<&[i32]>,
+	) -> Result<Todo, TodoException> {
+		let mut uow = match self.todo_repo.begin().await {
+			Ok(uow) => uow,
+			Err(UnitOfWorkError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		let todo = Self::exec_in(uow.as_mut(), id, params, if_match).await?;
+
+		if uow.commit().await.is_err() {
+			return Err(TodoException::Storage);
+		}
+
+		Ok(todo)
+	}
+
+	/// Updates the todo in the unit of work, left uncommitted.
+	pub async fn exec_in(
+		uow: &mut dyn TodoUnitOfWork,
+		id: String,
+		params: UpdateTodoParams,
+		if_match: Option<&[i32]>,
+	) -> Result<Todo, TodoException> {
+		let mut todo = match uow.find_by_id(id).await {
+			Ok(todo) => todo,
+			Err(FindTodoError::NotFound) => return Err(TodoException::NotFound),
+			Err(FindTodoError::DBInternalError) => return Err(TodoException::Storage),
+		};
+
+		if !todo.has_version(if_match) {
+			return Err(TodoException::PreconditionFailed);
+		}
+
+		if let Some(description) = params.description {
+			todo.update_description(description);
+		}
+		if let Some(priority) = params.priority {
+			todo.update_priority(priority);
+		}
+		if let Some(tags) = params.tags {
+			todo.update_tags(tags);
+		}
+		if let Some(rule) = params.recurrence {
+			let recurrence = match rule.trim() {
+				"" => None,
+				rule => match rule.parse::<Recurrence>() {
+					Ok(recurrence) => Some(recurrence),
+					Err(_) => return Err(TodoException::InvalidRecurrence),
+				},
+			};
+
+			todo.update_recurrence(recurrence);
+		}
+
+		match uow.update(todo).await {
+			Ok(todo) => Ok(todo),
+			Err(UpdateError::NotFound) => Err(TodoException::NotFound),
+			Err(UpdateError::Conflict) => Err(TodoException::Conflict),
+			Err(UpdateError::DBInternalError) => Err(TodoException::Storage),
+		}
+	}
+}
@@ -0,0 +1,299 @@
+use std::sync::{
+	atomic::{AtomicUsize, Ordering},
+	Arc,
+};
+
+use server::{
+	conformance::{check_todo_repository, check_todo_repository_persists, Repositories},
+	domain::{
+		entity::{
+			tag::Tag,
+			todo::{DueFilter, Todo},
+			todo_list::TodoList,
+			todo_query::TodoQuery,
+		},
+		repository::{
+			tag_repository::TagRepository,
+			todo_list_repository::TodoListRepository,
+			todo_repository::{DynTodoRepository, TodoRepository},
+		},
+	},
+	infra::{
+		migrations::{PG_MIGRATOR, SQLITE_MIGRATOR},
+		repository::{
+			tag_file_repo::TagFileRepository,
+			tag_inmemory_repo::TagInMemoryRepository,
+			todo_cached_repo::{
+				TodoCache, TodoCachedRepository, TodoListCacheInvalidatingRepository,
+			},
+			todo_file_repo::TodoFileRepository,
+			todo_inmemory_repo::TodoInMemoryRepository,
+			todo_list_file_repo::TodoListFileRepository,
+			todo_list_inmemory_repo::TodoListInMemoryRepository,
+			todo_list_pg_repo::TodoListPgRepository,
+			todo_list_sqlite_repo::TodoListSqliteRepository,
+			todo_pg_repo::TodoPgRepository,
+			todo_sqlite_repo::TodoSqliteRepository,
+		},
+		sqlite::create_sqlite_pool,
+	},
+};
+
+static FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Path in the temporary directory unique to this run, removed if it exists.
+fn temp_path(extension: &str) -> std::path::PathBuf {
+	let path = std::env::temp_dir().join(format!(
+		"todos-conformance-{}-{}.{}",
+		std::process::id(),
+		FILES.fetch_add(1, Ordering::Relaxed),
+		extension
+	));
+	let _ = std::fs::remove_file(&path);
+	path
+}
+
+#[tokio::test]
+async fn inmemory_repository_conforms() {
+	check_todo_repository(|| async {
+		let todo_repo = TodoInMemoryRepository::with_todos(vec![]);
+
+		Repositories {
+			lists: Arc::new(TodoListInMemoryRepository::new(&todo_repo)),
+			todos: Arc::new(todo_repo),
+		}
+	})
+	.await;
+}
+
+#[tokio::test]
+async fn file_repository_conforms() {
+	check_todo_repository(|| async {
+		let todo_repo = TodoFileRepository::open(temp_path("jsonl")).unwrap();
+
+		Repositories {
+			lists: Arc::new(TodoListFileRepository::new(&todo_repo)),
+			todos: Arc::new(todo_repo),
+		}
+	})
+	.await;
+}
+
+#[tokio::test]
+async fn file_repository_persists() {
+	let path = temp_path("jsonl");
+
+	check_todo_repository_persists(|| async {
+		Arc::new(TodoFileRepository::open(&path).unwrap()) as DynTodoRepository
+	})
+	.await;
+}
+
+/// The tags and lists go through the log too, along with the todos they change.
+#[tokio::test]
+async fn file_repository_replays_tags_and_lists() {
+	let path = temp_path("jsonl");
+
+	let (tag_ids, kept_list) = {
+		let todo_repo = TodoFileRepository::open(&path).unwrap();
+		let tag_repo = TagFileRepository::new(&todo_repo);
+		let list_repo = TodoListFileRepository::new(&todo_repo);
+
+		let kept_list = list_repo.create_list(TodoList::new("Kept".to_string())).await.unwrap();
+		let deleted_list =
+			list_repo.create_list(TodoList::new("Deleted".to_string())).await.unwrap();
+
+		todo_repo
+			.create_todo(Todo {
+				id: "tagged".to_string(),
+				tags: vec!["errands".to_string(), "home".to_string()],
+				list_id: Some(kept_list.id.clone()),
+				..Todo::new("Tagged".to_string())
+			})
+			.await
+			.unwrap();
+		todo_repo
+			.create_todo(Todo {
+				id: "listed".to_string(),
+				list_id: Some(deleted_list.id.clone()),
+				..Todo::new("Listed".to_string())
+			})
+			.await
+			.unwrap();
+		tag_repo.create_tag(Tag::new("unused".to_string())).await.unwrap();
+
+		let tags = tag_repo.find_many_tags().await.unwrap();
+		let tag = |name: &str| tags.iter().find(|tag| tag.name == name).unwrap().clone();
+
+		let mut errands = tag("errands");
+		tag_repo
+			.update_tag(errands.rename("chores".to_string()).to_owned())
+			.await
+			.unwrap();
+		tag_repo.delete_tag(tag("home").id).await.unwrap();
+		list_repo.delete_list(deleted_list.id).await.unwrap();
+
+		let mut tag_ids: Vec<String> =
+			tag_repo.find_many_tags().await.unwrap().into_iter().map(|tag| tag.id).collect();
+		tag_ids.sort();
+
+		(tag_ids, kept_list)
+	};
+
+	let todo_repo = TodoFileRepository::open(&path).unwrap();
+	let tag_repo = TagFileRepository::new(&todo_repo);
+	let list_repo = TodoListFileRepository::new(&todo_repo);
+
+	let tags = tag_repo.find_many_tags().await.unwrap();
+	let mut replayed_tag_ids: Vec<String> = tags.iter().map(|tag| tag.id.clone()).collect();
+	replayed_tag_ids.sort();
+	assert_eq!(
+		tags.iter().map(|tag| tag.name.as_str()).collect::<Vec<_>>(),
+		["chores", "unused"],
+		"created and renamed tags are kept, deleted ones are not"
+	);
+	assert_eq!(replayed_tag_ids, tag_ids, "tags keep their id");
+
+	let lists = list_repo.find_many_lists().await.unwrap();
+	assert_eq!(
+		lists.iter().map(|list| list.id.as_str()).collect::<Vec<_>>(),
+		[kept_list.id.as_str()],
+		"deleted lists are not replayed"
+	);
+
+	let todos = todo_repo.find_many_todos(&TodoQuery::default()).await.unwrap();
+	assert_eq!(
+		todos.iter().map(|todo| todo.id.as_str()).collect::<Vec<_>>(),
+		["tagged"],
+		"the todos of a deleted list are removed with it"
+	);
+	assert_eq!(
+		todos[0].tags,
+		["chores"],
+		"renamed and deleted tags are replayed on the todos"
+	);
+	assert_eq!(todos[0].list_id.as_ref(), Some(&kept_list.id));
+}
+
+/// Tag renames wait for the running unit of work, whose rollback would otherwise undo them.
+#[tokio::test]
+async fn inmemory_rollback_keeps_tag_renames() {
+	let todo_repo = TodoInMemoryRepository::with_todos(vec![]);
+	let tag_repo = Arc::new(TagInMemoryRepository::new(&todo_repo));
+
+	todo_repo
+		.create_todo(Todo {
+			id: "a".to_string(),
+			tags: vec!["errands".to_string()],
+			..Todo::new("Tagged".to_string())
+		})
+		.await
+		.unwrap();
+	let mut errands = tag_repo.find_many_tags().await.unwrap().remove(0);
+
+	let uow = todo_repo.begin().await.unwrap();
+	let rename = tokio::spawn({
+		let tag_repo = tag_repo.clone();
+		async move { tag_repo.update_tag(errands.rename("chores".to_string()).to_owned()).await }
+	});
+	tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+	drop(uow);
+	rename.await.unwrap().unwrap();
+
+	assert_eq!(
+		todo_repo.find_by_id("a".to_string()).await.unwrap().tags,
+		["chores"]
+	);
+}
+
+#[tokio::test]
+async fn cached_repository_conforms() {
+	check_todo_repository(|| async {
+		let todo_repo = TodoInMemoryRepository::with_todos(vec![]);
+		let cache = Arc::new(TodoCache::new(100));
+
+		Repositories {
+			lists: Arc::new(TodoListCacheInvalidatingRepository::new(
+				Arc::new(TodoListInMemoryRepository::new(&todo_repo)),
+				cache.clone(),
+			)),
+			todos: Arc::new(TodoCachedRepository::new(Arc::new(todo_repo), cache)),
+		}
+	})
+	.await;
+}
+
+#[tokio::test]
+async fn cached_repository_skips_due_queries() {
+	let cache = Arc::new(TodoCache::new(100));
+	let todo_repo = TodoCachedRepository::new(
+		Arc::new(TodoInMemoryRepository::with_todos(vec![])),
+		cache.clone(),
+	);
+	let query = TodoQuery {
+		due: Some(DueFilter::Overdue),
+		..Default::default()
+	};
+
+	todo_repo.find_many_todos(&query).await.unwrap();
+	todo_repo.count(&query).await.unwrap();
+
+	let stats = cache.stats();
+	assert_eq!((stats.hits, stats.misses, stats.entries), (0, 0, 0));
+}
+
+#[tokio::test]
+async fn sqlite_repository_conforms() {
+	check_todo_repository(|| async {
+		let url = format!("sqlite://{}", temp_path("db").display());
+		let pool = create_sqlite_pool(&url).await;
+		SQLITE_MIGRATOR.run(pool).await.unwrap();
+
+		Repositories {
+			todos: Arc::new(TodoSqliteRepository::new(pool)),
+			lists: Arc::new(TodoListSqliteRepository::new(pool)),
+		}
+	})
+	.await;
+}
+
+#[tokio::test]
+async fn sqlite_repository_persists() {
+	let url = format!("sqlite://{}", temp_path("db").display());
+
+	check_todo_repository_persists(|| async {
+		let pool = create_sqlite_pool(&url).await;
+		SQLITE_MIGRATOR.run(pool).await.unwrap();
+
+		Arc::new(TodoSqliteRepository::new(pool)) as DynTodoRepository
+	})
+	.await;
+}
+
+/// Runs on the database of `TEST_DATABASE_URL` when set, whose todos, tags and lists are
+/// deleted.
+#[tokio::test]
+async fn pg_repository_conforms() {
+	let Ok(url) = std::env::var("TEST_DATABASE_URL") else {
+		eprintln!("TEST_DATABASE_URL is not set, skipping the Postgres repository");
+		return;
+	};
+
+	let pool: &'static sqlx::PgPool = Box::leak(Box::new(
+		sqlx::postgres::PgPoolOptions::new().connect(&url).await.unwrap(),
+	));
+	PG_MIGRATOR.run(pool).await.unwrap();
+
+	check_todo_repository(|| async {
+		sqlx::query("TRUNCATE TABLE todos, tags, todo_lists CASCADE")
+			.execute(pool)
+			.await
+			.unwrap();
+
+		Repositories {
+			todos: Arc::new(TodoPgRepository::new(pool)),
+			lists: Arc::new(TodoListPgRepository::new(pool)),
+		}
+	})
+	.await;
+}